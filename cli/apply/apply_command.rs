@@ -0,0 +1,19 @@
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct ApplyCommand {
+    /// Plan file written by --plan-out (JSON or YAML)
+    pub plan_file: std::path::PathBuf,
+}
+
+use crate::command::CliCommand;
+use anyhow::Result;
+use core_ops::Executor;
+
+impl CliCommand for ApplyCommand {
+    fn run(&self, executor: &Executor) -> Result<()> {
+        let plan = core_plan::load_plan(&self.plan_file).map_err(|e| anyhow::anyhow!("{}", e))?;
+        executor.execute(&plan)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod apply_command;
+pub use apply_command::ApplyCommand;
@@ -0,0 +1,61 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use tempfile::TempDir;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+fn write_jar(path: &Path, version: &str) {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("manifest.json", FileOptions::default())
+        .unwrap();
+    write!(writer, r#"{{ "Name": "mod", "Version": "{}" }}"#, version).unwrap();
+    fs::write(path, writer.finish().unwrap().into_inner()).unwrap();
+}
+
+#[test]
+fn test_apply_diff_shows_changes_without_writing() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let config = temp_dir.path().join("config.yaml");
+    let icon = temp_dir.path().join("icon.png");
+    let built = temp_dir.path().join("mod-new.jar");
+    let installed = temp_dir.path().join("mod.jar");
+    fs::write(&config, "name: demo\nport: 5520\n").unwrap();
+    fs::write(&icon, [0u8, 1, 2]).unwrap();
+    write_jar(&built, "1.1.0");
+    write_jar(&installed, "1.0.0");
+
+    let plan = serde_json::json!({
+        "version": 1,
+        "steps": [
+            { "op": "write_file", "path": config, "content": "name: demo\nport: 5521\n" },
+            { "op": "write_bytes", "path": icon, "content": [0, 1, 2, 3] },
+            { "op": "copy_file", "from": built, "to": installed },
+        ]
+    });
+    let plan_path = temp_dir.path().join("plan.json");
+    fs::write(&plan_path, plan.to_string()).unwrap();
+
+    hymod(home_dir.path())
+        .args(["apply", plan_path.to_str().unwrap(), "--diff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "      -port: 5520\n      +port: 5521",
+        ))
+        .stdout(predicate::str::contains("binary: 3 bytes, sha256 "))
+        .stdout(predicate::str::contains(" -> 4 bytes, sha256 "))
+        .stdout(predicate::str::contains(format!(
+            "overwrites {} version 1.0.0 with version 1.1.0",
+            installed.display()
+        )));
+
+    assert_eq!(
+        fs::read_to_string(&config).unwrap(),
+        "name: demo\nport: 5520\n"
+    );
+}
@@ -0,0 +1,61 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_apply_plan_file() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let target = temp_dir.path().join("out").join("hello.txt");
+    let plan_path = temp_dir.path().join("plan.yaml");
+
+    fs::write(
+        &plan_path,
+        format!(
+            "version: 1\nsteps:\n  - op: write_file\n    path: {}\n    content: hello\n",
+            target.display()
+        ),
+    )
+    .unwrap();
+
+    hymod(home_dir.path())
+        .args(["apply", plan_path.to_str().unwrap()])
+        .assert()
+        .success();
+
+    assert_eq!(fs::read_to_string(target).unwrap(), "hello");
+}
+
+#[test]
+fn test_apply_rejects_unknown_version() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let plan_path = temp_dir.path().join("plan.json");
+    fs::write(&plan_path, r#"{"version": 2, "steps": []}"#).unwrap();
+
+    hymod(home_dir.path())
+        .args(["apply", plan_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported plan version 2"));
+}
+
+#[test]
+fn test_apply_child_reads_inherited_stdin() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let plan_path = temp_dir.path().join("plan.yaml");
+    fs::write(
+        &plan_path,
+        "version: 1\nsteps:\n  - op: run_process\n    cmd: sh\n    args: ['-c', 'read answer && echo \"got $answer\"']\n    cwd: null\n",
+    )
+    .unwrap();
+
+    hymod(home_dir.path())
+        .args(["apply", plan_path.to_str().unwrap()])
+        .write_stdin("yes\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("got yes"));
+}
@@ -4,6 +4,9 @@ use clap::Args;
 pub struct BuildCommand {
     #[arg(long)]
     pub release: bool,
+    /// Write the plan to this file (JSON or YAML) instead of executing it
+    #[arg(long)]
+    pub plan_out: Option<std::path::PathBuf>,
 }
 
 use crate::command::CliCommand;
@@ -16,6 +19,9 @@ impl CliCommand for BuildCommand {
             release: self.release,
         };
         let plan = features_build::generate_plan(args);
+        if let Some(plan_out) = &self.plan_out {
+            return crate::plan_out::write_plan(&plan, plan_out);
+        }
         executor.execute(&plan)?;
         Ok(())
     }
@@ -1,7 +1,12 @@
 use crate::command::CliCommand;
 use anyhow::Result;
 use clap::{Args, Subcommand};
-use core_config::UserConfig;
+use core_config::{
+    config_path, migrate_config_files, outdated_config_files, ConfigResolver, UserConfig,
+};
+use core_error::HymodError;
+use core_ops::{Executor, Verbosity};
+use core_plan::{Plan, Step};
 
 #[derive(Args)]
 pub struct ConfigCommand {
@@ -17,48 +22,139 @@ pub enum ConfigSubcommand {
     Get { key: String },
     /// List all configuration values
     List,
+    /// Show the value of a key and every layer that sets it
+    Explain { key: String },
+    /// Upgrade config and server files to the current schema, keeping backups
+    Migrate {
+        /// Only report files that need upgrading; exit with 4 if any do
+        #[arg(long)]
+        check: bool,
+    },
 }
 
 impl CliCommand for ConfigCommand {
-    fn run(&self, _executor: &core_ops::Executor) -> Result<()> {
-        let mut config = UserConfig::load();
-
+    fn run(&self, executor: &Executor) -> Result<()> {
         match &self.command {
             ConfigSubcommand::Set { key, value } => {
+                let mut config = UserConfig::load();
                 config
                     .set(key, value.clone())
                     .map_err(|e| anyhow::anyhow!("{}", e))?;
-                config.save().map_err(|e| anyhow::anyhow!("{}", e))?;
-                println!("Set {} = {}", key, value);
+                let content = config.to_yaml().map_err(|e| anyhow::anyhow!("{}", e))?;
+                executor.execute(&Plan {
+                    preflight: Vec::new(),
+                    steps: vec![Step::WriteFile {
+                        path: config_path().to_string_lossy().to_string(),
+                        content,
+                    }],
+                })?;
+                if !executor.is_dry_run() && executor.verbosity() != Verbosity::Quiet {
+                    println!("Set {} = {}", key, value);
+                }
             }
             ConfigSubcommand::Get { key } => {
-                if let Some(val) = config.get(key) {
+                let cwd = std::env::current_dir()?;
+                if let Some(val) = ConfigResolver::load(&cwd)?.get(key) {
                     println!("{}", val);
                 } else {
                     println!("Key '{}' not found.", key);
                 }
             }
-            ConfigSubcommand::List => {
-                println!("Global Configuration:");
-                println!(
-                    "  author: {}",
-                    config.author.as_deref().unwrap_or("<unset>")
-                );
-                println!(
-                    "  username: {}",
-                    config.username.as_deref().unwrap_or("<unset>")
-                );
-                println!("  group: {}", config.group.as_deref().unwrap_or("<unset>"));
-                println!(
-                    "  version: {}",
-                    config.version.as_deref().unwrap_or("<unset>")
-                );
-                println!(
-                    "  description: {}",
-                    config.desc.as_deref().unwrap_or("<unset>")
-                );
-            }
+            ConfigSubcommand::Explain { key } => explain(key)?,
+            ConfigSubcommand::Migrate { check } => migrate(*check, executor.is_dry_run())?,
+            ConfigSubcommand::List => list()?,
         }
         Ok(())
     }
 }
+
+/// Print the value of every key and the layer it comes from.
+fn list() -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let resolver = ConfigResolver::load(&cwd)?;
+    println!("Configuration:");
+    for key in resolver.keys() {
+        match resolver.resolve(&key) {
+            Some(resolved) => println!("  {}: {}  ({})", key, resolved.value, resolved.layer),
+            None => println!("  {}: <unset>", key),
+        }
+    }
+    Ok(())
+}
+
+/// Print the layers setting `key`, highest precedence first, marking the
+/// one whose value is used.
+fn explain(key: &str) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let layers = ConfigResolver::load(&cwd)?.explain(key)?;
+    let Some(used) = layers.first() else {
+        println!("{} is not set", key);
+        return Ok(());
+    };
+
+    println!("{} = {}", key, used.value);
+    let width = layers
+        .iter()
+        .map(|value| value.layer.to_string().len())
+        .max()
+        .unwrap_or(0);
+    for (index, value) in layers.iter().enumerate() {
+        let marker = if index == 0 { "  <- used" } else { "" };
+        println!(
+            "  {:<width$}  {}{}",
+            value.layer.to_string(),
+            value.value,
+            marker,
+            width = width
+        );
+    }
+    Ok(())
+}
+
+/// Upgrade every config file to the current schema or, with `check` or in
+/// a dry run, list the ones that need it.
+fn migrate(check: bool, dry_run: bool) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let files = if check || dry_run {
+        outdated_config_files(&cwd)
+    } else {
+        migrate_config_files(&cwd)
+    }
+    .map_err(HymodError::Other)?;
+
+    if files.is_empty() {
+        println!("All config files are up to date.");
+        return Ok(());
+    }
+    for file in &files {
+        let verb = match (check, dry_run) {
+            (true, _) => "Outdated",
+            (false, true) => "Would migrate",
+            (false, false) => "Migrated",
+        };
+        println!(
+            "{} {} (schema v{} -> v{})",
+            verb,
+            file.path.display(),
+            file.migrated.from,
+            file.migrated.to
+        );
+        for change in &file.migrated.changes {
+            println!("  - {}", change);
+        }
+        if let Some(backup) = &file.backup {
+            println!("  backup: {}", backup.display());
+        }
+    }
+    if check {
+        return Err(HymodError::InvalidConfig {
+            path: files[0].path.clone(),
+            reason: format!(
+                "{} file(s) use an older schema; run: hymod config migrate",
+                files.len()
+            ),
+        }
+        .into());
+    }
+    Ok(())
+}
@@ -2,40 +2,76 @@ use clap::Args;
 
 #[derive(Args, Debug)]
 pub struct DeployCommand {
-    pub server_name: Option<String>,
+    /// Servers to deploy to (default: the default remote server)
+    pub server_names: Vec<String>,
     #[arg(long)]
     pub transport: Option<String>,
     /// Restart server after upload (disabled by default)
     #[arg(long)]
     pub restart: bool,
-    #[arg(long)]
-    pub dry_run: bool,
     /// Path to the mod directory (default: current directory)
     #[arg(long, short)]
     pub path: Option<std::path::PathBuf>,
+    /// Write the plan to this file (JSON or YAML) instead of executing it
+    #[arg(long)]
+    pub plan_out: Option<std::path::PathBuf>,
 }
 
 use crate::command::CliCommand;
 use anyhow::Result;
+use core_error::HymodError;
 use core_ops::Executor;
+use core_plan::Plan;
 
 impl CliCommand for DeployCommand {
     fn run(&self, executor: &Executor) -> Result<()> {
         let args = features_deploy::DeployArgs {
-            server_name: self.server_name.clone(),
+            server_names: self.server_names.clone(),
             transport: self.transport.clone(),
             restart: self.restart,
-            dry_run: self.dry_run,
+            dry_run: executor.is_dry_run(),
             path: self.path.clone(),
         };
-        let plan = features_deploy::generate_plan(args);
-
-        if self.dry_run {
-            let dry_executor = Executor::new(true);
-            dry_executor.execute(&plan)?;
-        } else {
+        let plan = features_deploy::generate_plan(args)?;
+        if let Some(plan_out) = &self.plan_out {
+            return crate::plan_out::write_plan(&plan, plan_out);
+        }
+        if executor.is_dry_run() || plan.steps.is_empty() {
             executor.execute(&plan)?;
+            return Ok(());
         }
+
+        // Run the build on its own, like dev, so a failing build is reported
+        // as one rather than as a failed deploy.
+        let mut steps = plan.steps;
+        let build = steps.remove(0);
+        executor
+            .execute(&Plan {
+                preflight: plan.preflight,
+                steps: vec![build],
+            })
+            .map_err(build_failed)?;
+        executor.execute(&Plan {
+            preflight: Vec::new(),
+            steps,
+        })?;
         Ok(())
     }
 }
+
+fn build_failed(e: anyhow::Error) -> HymodError {
+    match e.downcast::<HymodError>() {
+        // Keep interruptions, timeouts and failed checks distinct from a failing build.
+        Ok(
+            err @ (HymodError::Interrupted { .. }
+            | HymodError::StepTimedOut { .. }
+            | HymodError::PreflightFailed { .. }),
+        ) => err,
+        Ok(err) => HymodError::BuildFailed {
+            reason: err.to_string(),
+        },
+        Err(e) => HymodError::BuildFailed {
+            reason: format!("{:#}", e),
+        },
+    }
+}
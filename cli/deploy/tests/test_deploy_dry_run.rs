@@ -1,4 +1,4 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -9,10 +9,8 @@ fn test_deploy_dry_run() {
     let home_dir = TempDir::new().expect("failed to create home dir");
 
     // Create a new project
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(temp_dir.path())
-        .env("HOME", home_dir.path())
         .args(["new", "testmod"])
         .assert()
         .success();
@@ -20,18 +18,14 @@ fn test_deploy_dry_run() {
     let project_dir = temp_dir.path().join("testmod");
 
     // Add a server
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", home_dir.path())
+    hymod(home_dir.path())
         .args(["server", "add", "local", "prod", "/tmp/server"])
         .assert()
         .success();
 
     // Run deploy with dry-run
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(&project_dir)
-        .env("HOME", home_dir.path())
         .args(["deploy", "prod", "--dry-run"])
         .assert()
         .success()
@@ -1,4 +1,4 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
@@ -8,10 +8,8 @@ fn test_deploy_dry_run_with_transport() {
     let home_dir = TempDir::new().expect("failed to create home dir");
 
     // Create a new project
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(temp_dir.path())
-        .env("HOME", home_dir.path())
         .args(["new", "testmod"])
         .assert()
         .success();
@@ -19,18 +17,14 @@ fn test_deploy_dry_run_with_transport() {
     let project_dir = temp_dir.path().join("testmod");
 
     // Add a REMOTE server
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", home_dir.path())
+    hymod(home_dir.path())
         .args(["server", "add", "remote", "prod", "user@example.com"])
         .assert()
         .success();
 
     // Run deploy with dry-run and transport rsync
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(&project_dir)
-        .env("HOME", home_dir.path())
         .args(["deploy", "prod", "--transport", "rsync", "--dry-run"])
         .assert()
         .success()
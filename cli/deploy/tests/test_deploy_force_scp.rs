@@ -1,4 +1,4 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
@@ -8,10 +8,8 @@ fn test_deploy_force_scp() {
     let home_dir = TempDir::new().expect("failed to create home dir");
 
     // Create a new project
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(temp_dir.path())
-        .env("HOME", home_dir.path())
         .args(["new", "testmod"])
         .assert()
         .success();
@@ -19,18 +17,14 @@ fn test_deploy_force_scp() {
     let project_dir = temp_dir.path().join("testmod");
 
     // Add a REMOTE server (dummy)
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", home_dir.path())
+    hymod(home_dir.path())
         .args(["server", "add", "remote", "prod", "user@host:/path"])
         .assert()
         .success();
 
     // Run deploy with no rsync in PATH: default should fall back to SCP.
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(&project_dir)
-        .env("HOME", home_dir.path())
         .env("PATH", "")
         .args(["deploy", "prod", "--dry-run"])
         .assert()
@@ -0,0 +1,34 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_uses_gradle_tasks_and_artifact_path_from_hymod_yaml() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    let project_dir = temp_dir.path().join("testmod");
+    fs::create_dir(&project_dir).unwrap();
+    fs::write(
+        project_dir.join("hymod.yaml"),
+        "mod:\n  id: testmod\n  name: Test Mod\n  version: 0.1.0\n  entrypoint: com.example.Main\nbuild:\n  gradle:\n    tasks: [clean, shadowJar]\n    artifact_path: build/custom/testmod-all.jar\n",
+    )
+    .unwrap();
+    let registry = project_dir.join(".hymod").join("servers.d");
+    fs::create_dir_all(&registry).unwrap();
+    fs::write(
+        registry.join("team.yaml"),
+        "server:\n  name: team\n  kind: local\n  server_root: /srv/team\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n",
+    )
+    .unwrap();
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["deploy", "team", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("clean shadowJar"))
+        .stdout(predicate::str::contains("build/custom/testmod-all.jar"))
+        .stdout(predicate::str::contains("/srv/team/mods/testmod-all.jar"));
+}
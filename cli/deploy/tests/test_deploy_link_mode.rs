@@ -0,0 +1,35 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_local_server_uses_link_mode() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .args(["server", "add", "local", "prod", "/tmp/server"])
+        .assert()
+        .success();
+
+    let config = home_dir.path().join(".hymod/servers.d/prod.yaml");
+    let content = fs::read_to_string(&config).unwrap();
+    fs::write(&config, format!("{}  link_mode: hardlink\n", content)).unwrap();
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path().join("testmod"))
+        .args(["deploy", "prod", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "LINK      /tmp/server/mods/testmod-0.1.0.jar -> ",
+        ))
+        .stdout(predicate::str::contains("(hardlink)"));
+}
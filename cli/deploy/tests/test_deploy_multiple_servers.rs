@@ -0,0 +1,50 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_multiple_servers() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    // Create a new project
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+
+    let project_dir = temp_dir.path().join("testmod");
+
+    for (name, uri) in [
+        ("eu", "user@eu.example.com:/srv/hytale"),
+        ("us", "user@us.example.com:/srv/hytale"),
+    ] {
+        hymod(home_dir.path())
+            .args(["server", "add", "remote", name, uri])
+            .assert()
+            .success();
+    }
+
+    // Uploads to both servers are grouped into one parallel stage after the build
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .env("PATH", "")
+        .args(["deploy", "eu", "us", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  1. RUN      ./gradlew build"))
+        .stdout(predicate::str::contains("  2. CHECK     "))
+        .stdout(predicate::str::contains("  3. PARALLEL  2 branches"))
+        .stdout(predicate::str::contains(
+            "     3.1.1. RUN       mkdir -p /srv/hytale/mods",
+        ))
+        .stdout(predicate::str::contains("     3.1.2. UPLOAD(SCP)"))
+        .stdout(predicate::str::contains(
+            "user@eu.example.com:/srv/hytale/mods/",
+        ))
+        .stdout(predicate::str::contains("     3.2.2. UPLOAD(SCP)"))
+        .stdout(predicate::str::contains(
+            "user@us.example.com:/srv/hytale/mods/",
+        ));
+}
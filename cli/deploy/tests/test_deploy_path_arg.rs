@@ -1,4 +1,4 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
@@ -8,10 +8,8 @@ fn test_deploy_path_arg() {
     let home_dir = TempDir::new().expect("failed to create home dir");
 
     // Create a new project "testmod"
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(temp_dir.path())
-        .env("HOME", home_dir.path())
         .args(["new", "testmod"])
         .assert()
         .success();
@@ -23,18 +21,14 @@ fn test_deploy_path_arg() {
     std::fs::create_dir(&outside_dir).unwrap();
 
     // Add a server
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", home_dir.path())
+    hymod(home_dir.path())
         .args(["server", "add", "local", "prod", "/tmp/server"])
         .assert()
         .success();
 
     // Run deploy FROM OUTSIDE, pointing to project_dir via --path
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(&outside_dir) // cwd is outside
-        .env("HOME", home_dir.path())
         .args([
             "deploy",
             "prod",
@@ -0,0 +1,47 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_path_uses_project_registry_of_mod_dir() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+
+    // The project defines its own server, which only exists as seen from it.
+    let project_dir = temp_dir.path().join("testmod");
+    fs::write(
+        project_dir.join("hymod.yaml"),
+        "mod:\n  id: testmod\n  name: Test Mod\n  version: 0.1.0\n  entrypoint: com.example.Main\n",
+    )
+    .unwrap();
+    let registry = project_dir.join(".hymod").join("servers.d");
+    fs::create_dir_all(&registry).unwrap();
+    fs::write(
+        registry.join("team.yaml"),
+        "server:\n  name: team\n  kind: local\n  server_root: /srv/team\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n",
+    )
+    .unwrap();
+
+    let outside_dir = temp_dir.path().join("outside");
+    fs::create_dir(&outside_dir).unwrap();
+
+    hymod(home_dir.path())
+        .current_dir(&outside_dir)
+        .args([
+            "deploy",
+            "team",
+            "--path",
+            project_dir.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/srv/team/mods/testmod-0.1.0.jar"));
+}
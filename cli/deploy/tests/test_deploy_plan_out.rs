@@ -0,0 +1,40 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_plan_out() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    // Create a new project
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+
+    let project_dir = temp_dir.path().join("testmod");
+    let plan_path = temp_dir.path().join("deploy-plan.json");
+
+    // Add a server
+    hymod(home_dir.path())
+        .args(["server", "add", "local", "prod", "/tmp/server"])
+        .assert()
+        .success();
+
+    // Write the plan instead of executing it
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["deploy", "prod", "--plan-out", plan_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Plan written to"));
+
+    let content = fs::read_to_string(&plan_path).unwrap();
+    assert!(content.contains("\"version\": 1"));
+    assert!(content.contains("\"op\": \"run_process\""));
+    assert!(content.contains("\"op\": \"copy_file\""));
+    assert!(content.contains("/tmp/server/mods/"));
+}
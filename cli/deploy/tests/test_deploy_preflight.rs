@@ -0,0 +1,60 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_preflight_fails_before_build() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+
+    let project_dir = temp_dir.path().join("testmod");
+    // The server root sits below a regular file, so its mods directory can never be created.
+    let blocker = temp_dir.path().join("not-a-dir");
+    fs::write(&blocker, "").unwrap();
+    let server_root = blocker.join("server");
+
+    hymod(home_dir.path())
+        .args([
+            "server",
+            "add",
+            "local",
+            "prod",
+            server_root.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["deploy", "prod", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PREFLIGHT"))
+        .stdout(predicate::str::contains("EXISTS    "))
+        .stdout(predicate::str::contains(format!(
+            "WRITABLE  {}",
+            server_root.join("mods").display()
+        )));
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["deploy", "prod"])
+        .assert()
+        .code(31)
+        .stderr(predicate::str::contains(
+            "Preflight failed with 1 problem(s)",
+        ))
+        .stderr(predicate::str::contains(format!(
+            "{} is not a directory",
+            blocker.display()
+        )));
+
+    assert!(!project_dir.join("build").exists());
+}
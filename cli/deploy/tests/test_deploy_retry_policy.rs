@@ -0,0 +1,46 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs::OpenOptions;
+use std::io::Write;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_retry_policy() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+
+    let project_dir = temp_dir.path().join("testmod");
+
+    hymod(home_dir.path())
+        .args(["server", "add", "remote", "prod", "user@host:/srv/hytale"])
+        .assert()
+        .success();
+
+    // Retry policies are set by hand in the server file
+    let server_file = home_dir.path().join(".hymod/servers.d/prod.yaml");
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(&server_file)
+        .expect("server file should exist");
+    writeln!(
+        file,
+        "  retry:\n    attempts: 3\n    backoff_ms: 2000\n    retry_on: [255]"
+    )
+    .unwrap();
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["deploy", "prod", "--transport", "scp", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "RUN       mkdir -p /srv/hytale/mods    (retry: 3 attempts)",
+        ))
+        .stdout(predicate::str::contains("(retry: 3 attempts)").count(2));
+}
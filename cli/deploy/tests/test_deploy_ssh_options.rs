@@ -0,0 +1,78 @@
+use crate::support::hymod;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_deploy_passes_server_ssh_options_and_upload_settings() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    let project_dir = temp_dir.path().join("testmod");
+    fs::create_dir(&project_dir).unwrap();
+    fs::write(project_dir.join("gradlew"), "#!/bin/sh\n").unwrap();
+    let plan_path = temp_dir.path().join("deploy-plan.json");
+
+    hymod(home_dir.path())
+        .args([
+            "server",
+            "add",
+            "remote",
+            "prod",
+            "deploy@example.com:/srv/hytale",
+            "--port",
+            "2222",
+            "--identity-file",
+            "/keys/hymod",
+            "--known-hosts",
+            "/keys/known_hosts",
+            "--upload-method",
+            "scp",
+            "--scp-opts",
+            "-C -l 8000",
+        ])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args([
+            "--dry-run",
+            "deploy",
+            "prod",
+            "--plan-out",
+            plan_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let plan: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&plan_path).unwrap()).unwrap();
+    let ssh = serde_json::json!({
+        "port": 2222,
+        "identity_file": "/keys/hymod",
+        "known_hosts_file": "/keys/known_hosts",
+    });
+
+    let reachable = plan["preflight"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["check"] == "ssh_reachable")
+        .expect("remote servers are checked for SSH access");
+    assert_eq!(reachable["ssh"], ssh);
+    assert!(plan["preflight"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|check| check["check"] == "binary" && check["name"] == "scp"));
+
+    let steps = plan["steps"].as_array().unwrap();
+    let mkdir = steps.iter().find(|step| step["op"] == "ssh_run").unwrap();
+    assert_eq!(mkdir["ssh"], ssh);
+    let upload = steps
+        .iter()
+        .find(|step| step["op"] == "upload_scp")
+        .expect("the configured upload method is used");
+    assert_eq!(upload["opts"], "-C -l 8000");
+    assert_eq!(upload["ssh"], ssh);
+}
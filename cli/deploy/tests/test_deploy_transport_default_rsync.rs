@@ -1,4 +1,4 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -25,27 +25,21 @@ fn test_deploy_transport_default_rsync() {
 
     make_fake_rsync(bin_dir.path());
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(temp_dir.path())
-        .env("HOME", home_dir.path())
         .args(["new", "testmod"])
         .assert()
         .success();
 
     let project_dir = temp_dir.path().join("testmod");
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", home_dir.path())
+    hymod(home_dir.path())
         .args(["server", "add", "remote", "prod", "user@host:/path"])
         .assert()
         .success();
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(&project_dir)
-        .env("HOME", home_dir.path())
         .env("PATH", bin_dir.path())
         .args(["deploy", "prod", "--dry-run"])
         .assert()
@@ -1,4 +1,21 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
 #[test]
 fn test_error_invalid_transport() {
-    panic!("Execute: hymod deploy --server prod --transport ftp, verify error 'Invalid transport: must be rsync or scp'.");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["server", "add", "remote", "prod", "user@host:/srv/hytale"])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .args(["deploy", "prod", "--transport", "ftp", "--dry-run"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "Invalid transport: must be rsync or scp",
+        ));
 }
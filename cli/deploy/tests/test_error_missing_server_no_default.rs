@@ -1,4 +1,19 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
 #[test]
 fn test_error_missing_server_no_default() {
-    panic!("Execute: hymod deploy (no --server, no default), verify error 'No server specified and no default configured'.");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["deploy", "--dry-run"])
+        .assert()
+        .code(6)
+        .stderr(predicate::str::contains(
+            "No server specified and no default remote server configured",
+        ))
+        .stderr(predicate::str::contains(
+            "hint: run: hymod server default remote <name>",
+        ));
 }
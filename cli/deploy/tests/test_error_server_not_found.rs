@@ -1,6 +1,18 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
 #[test]
 fn test_error_server_not_found() {
-    panic!(
-        "Execute: hymod deploy --server nonexistent, verify error 'Server nonexistent not found'."
-    );
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["deploy", "nonexistent", "--dry-run"])
+        .assert()
+        .code(5)
+        .stderr(predicate::str::contains(format!(
+            "Server configuration 'nonexistent' not found in {}",
+            home_dir.path().join(".hymod").join("servers.d").display()
+        )))
+        .stderr(predicate::str::contains("hint: run: hymod server list"));
 }
@@ -1,4 +1,26 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
 #[test]
 fn test_error_transport_unavailable() {
-    panic!("Execute: hymod deploy --server prod --transport rsync (no rsync), verify error 'Transport rsync not available'.");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let project_dir = TempDir::new().expect("failed to create project dir");
+
+    hymod(home_dir.path())
+        .args(["server", "add", "remote", "prod", "user@host:/srv/hytale"])
+        .assert()
+        .success();
+
+    // An empty PATH guarantees rsync cannot be found
+    hymod(home_dir.path())
+        .current_dir(project_dir.path())
+        .env("PATH", "")
+        .args(["deploy", "prod", "--transport", "rsync"])
+        .assert()
+        .code(20)
+        .stderr(predicate::str::contains("Transport rsync not available"))
+        .stderr(predicate::str::contains(
+            "hint: install rsync or pass --transport scp",
+        ));
 }
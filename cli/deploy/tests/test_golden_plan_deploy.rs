@@ -1,6 +1,53 @@
 // Golden Plan Tests (PRD §19)
 
+use crate::support::hymod;
+use tempfile::TempDir;
+
 #[test]
 fn test_golden_plan_deploy() {
-    panic!("Must produce snapshot of 'hymod deploy --server prod --dry-run' output matching expected format.");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let run = || {
+        let mut cmd = hymod(home_dir.path());
+        cmd.current_dir(temp_dir.path());
+        cmd
+    };
+    run().args(["new", "testmod"]).assert().success();
+    run()
+        .args([
+            "server",
+            "add",
+            "remote",
+            "prod",
+            "deploy@example.com:/srv/hytale",
+        ])
+        .assert()
+        .success();
+
+    let output = run()
+        .current_dir(temp_dir.path().join("testmod"))
+        .env("PATH", "")
+        .args(["deploy", "prod", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)
+        .unwrap()
+        .replace(temp_dir.path().to_str().unwrap(), "<tmp>");
+    assert_eq!(
+        stdout,
+        "\
+PREFLIGHT
+  - EXISTS    <tmp>/testmod/gradlew
+  - BINARY    ssh
+  - BINARY    scp
+  - SSH       deploy@example.com
+PLAN
+  1. RUN      ./gradlew build    (cwd: <tmp>/testmod)
+  2. CHECK     <tmp>/testmod/build/libs/testmod-0.1.0.jar    (requires: manifest.json, main class)
+  3. RUN       mkdir -p /srv/hytale/mods
+  4. UPLOAD(SCP)   <tmp>/testmod/build/libs/testmod-0.1.0.jar -> deploy@example.com:/srv/hytale/mods/testmod-0.1.0.jar
+"
+    );
 }
@@ -16,12 +16,12 @@ use anyhow::Result;
 use core_ops::Executor;
 
 impl CliCommand for DevCommand {
-    fn run(&self, _executor: &Executor) -> Result<()> {
+    fn run(&self, executor: &Executor) -> Result<()> {
         let args = features_dev::DevArgs {
             target: self.target.clone(),
             path: self.path.clone(),
         };
-        features_dev::run(args)?;
+        features_dev::run(args, executor)?;
         Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use predicates::prelude::*;
 use std::fs;
 use std::path::PathBuf;
@@ -8,6 +8,7 @@ use tempfile::TempDir;
 fn test_dev_deploy_to_target() {
     // 1. Setup temp workspace
     let temp = TempDir::new().unwrap();
+    let home_dir = TempDir::new().expect("failed to create home dir");
     let root = temp.path();
     let mod_dir = root.join("my-mod");
     let server_dir = root.join("my-server");
@@ -17,11 +18,16 @@ fn test_dev_deploy_to_target() {
 
     // 2. Create mock gradlew
     let gradlew_path = mod_dir.join("gradlew");
-    let gradlew_content = r#"#!/bin/sh
+    let fixture = root.join("fixture.jar");
+    crate::support::write_mod_jar(&fixture);
+    let gradlew_content = format!(
+        r#"#!/bin/sh
 mkdir -p build/libs
-echo "fake jar content" > build/libs/my-mod-1.0.jar
+cp '{}' build/libs/my-mod-1.0.jar
 exit 0
-"#;
+"#,
+        fixture.display()
+    );
     fs::write(&gradlew_path, gradlew_content).unwrap();
 
     #[cfg(unix)]
@@ -37,7 +43,7 @@ exit 0
     let target_str = server_dir.to_str().unwrap();
     let path_str = mod_dir.to_str().unwrap();
 
-    let mut cmd = Command::cargo_bin("hymod").unwrap();
+    let mut cmd = hymod(home_dir.path());
     cmd.arg("dev").arg(target_str).arg("--path").arg(path_str);
 
     cmd.assert()
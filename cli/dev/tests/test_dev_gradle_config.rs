@@ -0,0 +1,45 @@
+use crate::support::hymod;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_dev_uses_gradle_tasks_and_artifact_path_from_hymod_yaml() {
+    let temp = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    let mod_dir = temp.path().join("my-mod");
+    let server_dir = temp.path().join("my-server");
+    fs::create_dir(&mod_dir).unwrap();
+    fs::create_dir_all(server_dir.join("mods")).unwrap();
+    fs::write(
+        mod_dir.join("hymod.yaml"),
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.Main\nbuild:\n  gradle:\n    tasks: [shadowJar]\n    artifact_path: build/custom/my-mod-all.jar\n",
+    )
+    .unwrap();
+
+    let gradlew_path = mod_dir.join("gradlew");
+    let fixture = temp.path().join("fixture.jar");
+    crate::support::write_mod_jar(&fixture);
+    fs::write(
+        &gradlew_path,
+        format!(
+            "#!/bin/sh\n[ \"$1\" = shadowJar ] || exit 1\nmkdir -p build/custom\ncp '{}' build/custom/my-mod-all.jar\n",
+            fixture.display()
+        ),
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&gradlew_path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    hymod(home.path())
+        .arg("dev")
+        .arg(server_dir.to_str().unwrap())
+        .arg("--path")
+        .arg(mod_dir.to_str().unwrap())
+        .assert()
+        .success();
+
+    assert!(server_dir.join("mods").join("my-mod-all.jar").exists());
+}
@@ -1,9 +1,10 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use std::fs;
 use tempfile::TempDir;
 
 #[test]
 fn test_dev_manifest_naming() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
     let temp = TempDir::new().unwrap();
     let mod_dir = temp.path().join("my-mod");
     fs::create_dir(&mod_dir).unwrap();
@@ -30,11 +31,7 @@ fn test_dev_manifest_naming() {
     fs::create_dir_all(&build_libs).unwrap();
 
     // Create a dummy build artifact
-    fs::write(
-        build_libs.join("original-build-artifact.jar"),
-        "dummy content",
-    )
-    .unwrap();
+    crate::support::write_mod_jar(&build_libs.join("original-build-artifact.jar"));
 
     // Create a mock gradlew script that succeeds
     #[cfg(unix)]
@@ -55,7 +52,7 @@ fn test_dev_manifest_naming() {
     fs::create_dir_all(&target_dir).unwrap();
 
     // 4. Run hymod dev
-    let mut cmd = Command::cargo_bin("hymod").unwrap();
+    let mut cmd = hymod(home_dir.path());
     cmd.current_dir(&mod_dir)
         .arg("dev")
         .arg(target_dir.parent().unwrap()); // Pass server root (parent of mods) as target
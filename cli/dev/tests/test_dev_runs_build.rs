@@ -1,19 +1,24 @@
+use crate::support::hymod;
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
 
 #[test]
 fn test_dev_runs_build() {
     let temp_dir = tempfile::tempdir().expect("failed to create temp folder");
+    let home_dir = tempfile::tempdir().expect("failed to create home dir");
     let path = temp_dir.path().to_owned();
 
     // 1. Setup mock gradlew
     let gradlew_path = path.join("gradlew");
-    let script_content = r#"#!/bin/bash
+    let fixture = path.join("fixture.jar");
+    crate::support::write_mod_jar(&fixture);
+    let script_content = format!(
+        r#"#!/bin/bash
 mkdir -p build/libs
-touch build/libs/test-mod-1.0.jar
+cp '{}' build/libs/test-mod-1.0.jar
 echo "Mock Gradle Build Success"
-"#;
+"#,
+        fixture.display()
+    );
     fs::write(&gradlew_path, script_content).expect("failed to write mock gradlew");
 
     // Make executable
@@ -26,19 +31,14 @@ echo "Mock Gradle Build Success"
     }
 
     // 2. Run hymod dev
-    // We use `cargo run --bin hymod -- dev <path> --path <path>` (deploy to self)
-    let status = Command::new("cargo")
-        .args(&[
-            "run",
-            "--bin",
-            "hymod",
-            "--",
+    // Deploy to self: the mod directory is also the target
+    let status = hymod(home_dir.path())
+        .args([
             "dev",
             path.to_str().unwrap(),
             "--path",
             path.to_str().unwrap(),
         ])
-        .current_dir(std::env::current_dir().unwrap()) // Ensure we run from repo root
         .output()
         .expect("failed to execute hymod dev");
 
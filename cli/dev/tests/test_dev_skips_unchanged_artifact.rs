@@ -0,0 +1,58 @@
+use crate::support::hymod;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_dev_skips_unchanged_artifact() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp = TempDir::new().unwrap();
+    let mod_dir = temp.path().join("my-mod");
+    let server_dir = temp.path().join("my-server");
+    fs::create_dir(&mod_dir).unwrap();
+    fs::create_dir_all(server_dir.join("mods")).unwrap();
+
+    // The mock build always produces the same bytes
+    let gradlew_path = mod_dir.join("gradlew");
+    let fixture = temp.path().join("fixture.jar");
+    crate::support::write_mod_jar(&fixture);
+    fs::write(
+        &gradlew_path,
+        format!(
+            "#!/bin/sh\nmkdir -p build/libs\ncp '{}' build/libs/my-mod-1.0.jar\n",
+            fixture.display()
+        ),
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&gradlew_path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let run = || {
+        let mut cmd = hymod(home_dir.path());
+        cmd.arg("dev")
+            .arg(server_dir.to_str().unwrap())
+            .arg("--path")
+            .arg(mod_dir.to_str().unwrap());
+        cmd
+    };
+
+    run()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(up-to-date)").not());
+
+    run()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("COPY").and(predicate::str::contains("(up-to-date)")));
+
+    // A dry run reports the same without touching anything
+    run()
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(up-to-date)"));
+}
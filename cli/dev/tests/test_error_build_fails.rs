@@ -1,9 +1,10 @@
+use crate::support::hymod;
 use std::fs;
-use std::process::Command;
 
 #[test]
 fn test_error_build_fails() {
     let temp_dir = tempfile::tempdir().expect("failed to create temp folder");
+    let home_dir = tempfile::tempdir().expect("failed to create home dir");
     let path = temp_dir.path().to_owned();
 
     // 1. Setup mock gradlew that FAILS
@@ -25,17 +26,8 @@ exit 1
 
     // 2. Run hymod dev
     // 2. Run hymod dev
-    let status = Command::new("cargo")
-        .args(&[
-            "run",
-            "--bin",
-            "hymod",
-            "--",
-            "dev",
-            "--path",
-            path.to_str().unwrap(),
-        ])
-        .current_dir(std::env::current_dir().unwrap())
+    let status = hymod(home_dir.path())
+        .args(["dev", "--path", path.to_str().unwrap()])
         .output()
         .expect("failed to execute hymod dev");
 
@@ -14,7 +14,7 @@ impl CliCommand for LinkCommand {
         let args = features_link::LinkArgs {
             server_name: self.server_name.clone(),
         };
-        let plan = features_link::generate_plan(args);
+        let plan = features_link::generate_plan(args)?;
         executor.execute(&plan)?;
         Ok(())
     }
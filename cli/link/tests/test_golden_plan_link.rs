@@ -1,6 +1,48 @@
 // Golden Plan Tests (PRD §19)
 
+use crate::support::hymod;
+use tempfile::TempDir;
+
 #[test]
 fn test_golden_plan_link() {
-    panic!("Must produce snapshot of 'hymod link --server local --dry-run' output matching expected format.");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let run = || {
+        let mut cmd = hymod(home_dir.path());
+        cmd.current_dir(temp_dir.path());
+        cmd
+    };
+    run().args(["new", "testmod"]).assert().success();
+    let server_root = temp_dir.path().join("server");
+    run()
+        .args([
+            "server",
+            "add",
+            "local",
+            "local",
+            server_root.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let project_dir = temp_dir.path().join("testmod");
+    crate::support::write_mod_jar(&project_dir.join("build/libs/testmod-0.1.0.jar"));
+
+    let output = run()
+        .current_dir(&project_dir)
+        .args(["link", "local", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)
+        .unwrap()
+        .replace(temp_dir.path().to_str().unwrap(), "<tmp>");
+    assert_eq!(
+        stdout,
+        "\
+PLAN
+  1. CHECK     <tmp>/testmod/build/libs/testmod-0.1.0.jar    (requires: manifest.json, main class)
+  2. LINK      <tmp>/server/mods/testmod-0.1.0.jar -> <tmp>/testmod/build/libs/testmod-0.1.0.jar    (symlink)
+"
+    );
 }
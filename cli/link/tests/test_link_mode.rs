@@ -0,0 +1,79 @@
+use crate::support::hymod;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Create a project with a built jar and a local server `dev`, optionally
+/// with a `link_mode`. Returns the project directory.
+fn setup(temp_dir: &Path, home_dir: &Path, link_mode: Option<&str>) -> std::path::PathBuf {
+    hymod(home_dir)
+        .current_dir(temp_dir)
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+
+    let project_dir = temp_dir.join("testmod");
+    crate::support::write_mod_jar(&project_dir.join("build/libs/testmod-0.1.0.jar"));
+
+    hymod(home_dir)
+        .args([
+            "server",
+            "add",
+            "local",
+            "dev",
+            temp_dir.join("server").to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    if let Some(mode) = link_mode {
+        let config = home_dir.join(".hymod").join("servers.d").join("dev.yaml");
+        let content = fs::read_to_string(&config).unwrap();
+        fs::write(&config, format!("{}  link_mode: {}\n", content, mode)).unwrap();
+    }
+    project_dir
+}
+
+#[test]
+#[cfg(unix)]
+fn test_link_mode_defaults_to_symlink() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let project_dir = setup(temp_dir.path(), home_dir.path(), None);
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["link", "dev"])
+        .assert()
+        .success();
+
+    let link = temp_dir.path().join("server/mods/testmod-0.1.0.jar");
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(
+        fs::read_link(&link).unwrap(),
+        project_dir.join("build/libs/testmod-0.1.0.jar")
+    );
+}
+
+#[test]
+fn test_link_mode_copy_from_server_config() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let project_dir = setup(temp_dir.path(), home_dir.path(), Some("copy"));
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["link", "dev"])
+        .assert()
+        .success();
+
+    let link = temp_dir.path().join("server/mods/testmod-0.1.0.jar");
+    assert!(fs::symlink_metadata(&link).unwrap().file_type().is_file());
+    assert_eq!(
+        fs::read(&link).unwrap(),
+        fs::read(project_dir.join("build/libs/testmod-0.1.0.jar")).unwrap()
+    );
+}
@@ -0,0 +1,59 @@
+use crate::command::CliCommand;
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use core_config::logs::{self, RunLog};
+use core_ops::Executor;
+use std::fs;
+
+#[derive(Subcommand)]
+pub enum LogsCommand {
+    /// Print the log of the most recent run
+    Last,
+    /// List run logs, newest first
+    List,
+}
+
+impl CliCommand for LogsCommand {
+    fn run(&self, _executor: &Executor) -> Result<()> {
+        match self {
+            LogsCommand::Last => {
+                let Some(log) = logs::latest_log()? else {
+                    println!("No run logs in {}", logs::logs_dir().display());
+                    return Ok(());
+                };
+                let content = fs::read_to_string(&log.path)
+                    .with_context(|| format!("Failed to read {}", log.path.display()))?;
+                println!("# {}", log.path.display());
+                print!("{}", content);
+            }
+            LogsCommand::List => {
+                for log in logs::list_logs()? {
+                    println!(
+                        "{:<22} {:<8} {:<10} {}",
+                        log.timestamp,
+                        log.command,
+                        outcome(&log),
+                        log.path.display()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How the last plan in the log ended, from its closing `== plan ...` line.
+fn outcome(log: &RunLog) -> &'static str {
+    let Ok(content) = fs::read_to_string(&log.path) else {
+        return "unreadable";
+    };
+    match content
+        .lines()
+        .rev()
+        .find(|line| line.starts_with("== plan "))
+    {
+        Some(line) if line.starts_with("== plan succeeded") => "succeeded",
+        Some(line) if line.starts_with("== plan failed") => "failed",
+        _ => "incomplete",
+    }
+}
@@ -25,10 +25,8 @@ pub struct NewCommand {
     #[arg(long)]
     pub author: Option<String>,
 
-    /// Mod version
-    #[arg(long, default_value = "0.1.0")]
-    // Keep explicit default in CLI help? Or better rely on logic.
-    // Wait, if I set default here it will always be Some. Better to use logic default if None.
+    /// Mod version (default: `version` from the config, else 0.1.0)
+    #[arg(long)]
     pub version: Option<String>,
 
     /// Mod description
@@ -38,6 +36,10 @@ pub struct NewCommand {
     /// Enable interactive mode
     #[arg(short, long)]
     pub interactive: bool,
+
+    /// Write the plan to this file (JSON or YAML) instead of executing it
+    #[arg(long)]
+    pub plan_out: Option<std::path::PathBuf>,
 }
 
 use crate::command::CliCommand;
@@ -64,6 +66,9 @@ impl CliCommand for NewCommand {
         // So ../../assets/skeleton.zip.
         const SKELETON_BYTES: &[u8] = include_bytes!("../../assets/skeleton.zip");
         let plan = features_new::generate_plan(args, SKELETON_BYTES);
+        if let Some(plan_out) = &self.plan_out {
+            return crate::plan_out::write_plan(&plan, plan_out);
+        }
         executor.execute(&plan)?;
         Ok(())
     }
@@ -1,17 +1,17 @@
-use assert_cmd::Command;
+use crate::support::hymod;
 use std::fs;
 use tempfile::TempDir;
 
 #[test]
 fn test_new_all_options() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
     let temp_dir = TempDir::new().expect("failed to create temp dir");
     let temp_path = temp_dir.path().to_str().unwrap();
 
     let project_name = "test_overrides";
     let project_path = temp_dir.path().join(project_name);
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(home_dir.path())
         .current_dir(temp_path)
         .args([
             "new",
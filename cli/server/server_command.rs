@@ -15,16 +15,56 @@ pub enum ServerCommand {
     /// Add a new server configuration
     Add {
         /// Type of server (local or remote)
-        #[arg(index = 1)]
-        kind: String, // "local" or "remote"
+        #[arg(index = 1, required_unless_present = "interactive")]
+        kind: Option<String>, // "local" or "remote"
 
         /// Name of the server
-        #[arg(index = 2)]
-        name: String,
+        #[arg(index = 2, required_unless_present = "interactive")]
+        name: Option<String>,
 
         /// Path (for local) or URL (for remote, e.g., user@host)
-        #[arg(index = 3)]
-        uri: String,
+        #[arg(index = 3, required_unless_present = "interactive")]
+        uri: Option<String>,
+
+        /// Server root, instead of the path in the URI
+        #[arg(long)]
+        server_root: Option<String>,
+
+        /// Mods directory, relative to the server root (default: mods)
+        #[arg(long)]
+        mods_dir: Option<String>,
+
+        /// Command that restarts the server (default: systemctl restart hytale)
+        #[arg(long)]
+        restart_cmd: Option<String>,
+
+        /// SSH port of a remote server (default: 22)
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// SSH private key for a remote server
+        #[arg(long)]
+        identity_file: Option<String>,
+
+        /// known_hosts file for a remote server
+        #[arg(long = "known-hosts")]
+        known_hosts_file: Option<String>,
+
+        /// How mods are uploaded to a remote server (rsync or scp)
+        #[arg(long)]
+        upload_method: Option<String>,
+
+        /// Extra options for rsync uploads
+        #[arg(long, allow_hyphen_values = true)]
+        rsync_opts: Option<String>,
+
+        /// Extra options for scp uploads
+        #[arg(long, allow_hyphen_values = true)]
+        scp_opts: Option<String>,
+
+        /// Ask for each setting, then offer to check the connection before saving
+        #[arg(short, long)]
+        interactive: bool,
     },
 
     /// Set default server
@@ -51,24 +91,79 @@ pub enum ServerCommand {
         #[arg(index = 1)]
         name: String,
     },
+
+    /// Check server files and report every problem found
+    Validate {
+        /// Name of the server (default: every server file)
+        #[arg(index = 1)]
+        name: Option<String>,
+    },
+
+    /// Change one field of a server, e.g. `restart.cmd` or `remote.port`
+    Set {
+        /// Name of the server
+        #[arg(index = 1)]
+        name: String,
+
+        /// Dotted key under `server:` (an empty value clears optional fields)
+        #[arg(index = 2)]
+        key: String,
+
+        #[arg(index = 3)]
+        value: String,
+    },
+
+    /// Open a server file in $EDITOR and save it if it is valid
+    Edit {
+        /// Name of the server
+        #[arg(index = 1)]
+        name: String,
+    },
 }
 
 impl CliCommand for ServerCommand {
-    fn run(&self, _executor: &Executor) -> Result<()> {
+    fn run(&self, executor: &Executor) -> Result<()> {
         let cmd = match self {
             ServerCommand::List { kind } => features_server::ServerCommand::List(
                 features_server::args::list_args::ServerListArgs {
                     filter_kind: kind.clone(),
                 },
             ),
-            ServerCommand::Add { kind, name, uri } => features_server::ServerCommand::Add(
-                features_server::args::add_args::ServerAddArgs {
-                    name: name.clone(),
-                    kind: kind.clone(),
-                    uri: uri.clone(),
-                    server_root: None,
-                },
-            ),
+            ServerCommand::Add {
+                kind,
+                name,
+                uri,
+                server_root,
+                mods_dir,
+                restart_cmd,
+                port,
+                identity_file,
+                known_hosts_file,
+                upload_method,
+                rsync_opts,
+                scp_opts,
+                interactive,
+            } => {
+                let args = features_server::args::add_args::ServerAddArgs {
+                    name: name.clone().unwrap_or_default(),
+                    kind: kind.clone().unwrap_or_default(),
+                    uri: uri.clone().unwrap_or_default(),
+                    server_root: server_root.clone(),
+                    mods_dir: mods_dir.clone(),
+                    restart_cmd: restart_cmd.clone(),
+                    port: *port,
+                    identity_file: identity_file.clone(),
+                    known_hosts_file: known_hosts_file.clone(),
+                    upload_method: upload_method.clone(),
+                    rsync_opts: rsync_opts.clone(),
+                    scp_opts: scp_opts.clone(),
+                };
+                if *interactive {
+                    features_server::cmd::add::run_interactive(args, executor)?;
+                    return Ok(());
+                }
+                features_server::ServerCommand::Add(args)
+            }
             ServerCommand::Default { kind, name } => features_server::ServerCommand::Default(
                 features_server::args::default_args::ServerDefaultArgs {
                     kind: kind.clone(),
@@ -81,8 +176,21 @@ impl CliCommand for ServerCommand {
             ServerCommand::Get { name } => features_server::ServerCommand::Get(
                 features_server::args::get_args::ServerGetArgs { name: name.clone() },
             ),
+            ServerCommand::Validate { name } => features_server::ServerCommand::Validate(
+                features_server::args::validate_args::ServerValidateArgs { name: name.clone() },
+            ),
+            ServerCommand::Set { name, key, value } => features_server::ServerCommand::Set(
+                features_server::args::set_args::ServerSetArgs {
+                    name: name.clone(),
+                    key: key.clone(),
+                    value: value.clone(),
+                },
+            ),
+            ServerCommand::Edit { name } => features_server::ServerCommand::Edit(
+                features_server::args::edit_args::ServerEditArgs { name: name.clone() },
+            ),
         };
-        features_server::execute(cmd);
+        features_server::execute(cmd, executor)?;
         Ok(())
     }
 }
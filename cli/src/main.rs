@@ -1,8 +1,12 @@
 use crate::command::CliCommand;
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use std::ffi::OsString;
+use std::sync::Arc;
+use std::time::Duration;
 
 // Declare CLI command modules
+#[path = "../apply/mod.rs"]
+mod apply;
 #[path = "../build/mod.rs"]
 mod build;
 mod command;
@@ -14,8 +18,11 @@ mod deploy;
 mod dev;
 #[path = "../link/mod.rs"]
 mod link;
+#[path = "../logs/mod.rs"]
+mod logs;
 #[path = "../new/mod.rs"]
 mod new;
+mod plan_out;
 #[path = "../server/mod.rs"]
 mod server;
 
@@ -24,10 +31,54 @@ mod server;
 #[command(about = "Hytale Mod Development Kit", long_about = None)]
 #[command(version)]
 struct Cli {
+    /// Print the plan instead of executing it
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Like --dry-run, and also show how every write would change the files on disk
+    #[arg(long, global = true)]
+    diff: bool,
+
+    /// Echo every child command and its working directory
+    #[arg(long, short, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Suppress all output except errors
+    #[arg(long, short, global = true)]
+    quiet: bool,
+
+    /// Maximum number of parallel steps to run at once (default: number of CPUs)
+    #[arg(long, short, global = true)]
+    jobs: Option<usize>,
+
+    /// How to report plan progress: plain text, a live progress view, or JSON lines
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Progress,
+    Json,
+}
+
+impl Cli {
+    fn verbosity(&self) -> core_ops::Verbosity {
+        // JSON output must stay machine-readable, so human status lines are muted.
+        if self.quiet || self.output == OutputFormat::Json {
+            core_ops::Verbosity::Quiet
+        } else if self.verbose {
+            core_ops::Verbosity::Verbose
+        } else {
+            core_ops::Verbosity::Normal
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new mod project
@@ -51,6 +102,13 @@ enum Commands {
 
     /// Manage global configuration
     Config(config::ConfigCommand),
+
+    /// Execute a plan file written by --plan-out
+    Apply(apply::ApplyCommand),
+
+    /// Show logs of previous runs from ~/.hymod/logs
+    #[command(subcommand)]
+    Logs(logs::LogsCommand),
 }
 
 impl CliCommand for Commands {
@@ -63,6 +121,25 @@ impl CliCommand for Commands {
             Commands::Deploy(cmd) => cmd.run(executor),
             Commands::Server(cmd) => cmd.run(executor),
             Commands::Config(cmd) => cmd.run(executor),
+            Commands::Apply(cmd) => cmd.run(executor),
+            Commands::Logs(cmd) => cmd.run(executor),
+        }
+    }
+}
+
+impl Commands {
+    /// Name of the subcommand, used to name its run log.
+    fn name(&self) -> &'static str {
+        match self {
+            Commands::New(_) => "new",
+            Commands::Build(_) => "build",
+            Commands::Link(_) => "link",
+            Commands::Dev(_) => "dev",
+            Commands::Deploy(_) => "deploy",
+            Commands::Server(_) => "server",
+            Commands::Config(_) => "config",
+            Commands::Apply(_) => "apply",
+            Commands::Logs(_) => "logs",
         }
     }
 }
@@ -87,6 +164,18 @@ fn should_show_logo(args: &[OsString]) -> bool {
         .any(|arg| matches!(arg.to_str(), Some("-h") | Some("--help")))
 }
 
+/// First lines of a run log: the command line, when and where it ran.
+fn log_header(args: &[OsString]) -> String {
+    let command = std::iter::once("hymod".into())
+        .chain(args.iter().skip(1).map(|arg| arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let cwd = std::env::current_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    format!("$ {}\ncwd: {}", command, cwd)
+}
+
 fn main() {
     let args: Vec<OsString> = std::env::args_os().collect();
     if should_show_logo(&args) {
@@ -104,14 +193,50 @@ fn main() {
     }
 
     // Parse command-line arguments
-    let cli = Cli::parse_from(args);
+    let cli = Cli::parse_from(args.clone());
 
-    // Initialize Executor (defaulting dry_run to false for now, or TODO: add global flag)
-    let executor = core_ops::Executor::new(false);
+    let cancel = core_ops::CancelToken::new();
+    if let Err(e) = cancel.install_interrupt_handler() {
+        eprintln!("Warning: could not install Ctrl-C handler: {}", e);
+    }
+
+    // Execution settings have no project layer, so a broken hymod.yaml
+    // cannot keep commands like `hymod config` from running.
+    let config = core_config::ConfigResolver::global();
+    let mut executor = core_ops::Executor::new(cli.dry_run || cli.diff)
+        .with_diff(cli.diff)
+        .with_verbosity(cli.verbosity())
+        .with_cancel_token(cancel)
+        .with_fsync(config.fsync());
+    for (op, secs) in config.timeouts() {
+        executor = executor.with_default_timeout(op, Duration::from_secs(secs));
+    }
+    if let Some(jobs) = cli.jobs {
+        executor = executor.with_jobs(jobs);
+    }
+    let run_log = Arc::new(core_ops::observers::RunLogWriter::new(
+        core_config::logs::new_log_path(cli.command.name()),
+        log_header(&args),
+    ));
+    executor = executor.with_observer(run_log.clone());
+    executor = match cli.output {
+        OutputFormat::Text => executor,
+        OutputFormat::Progress => {
+            executor.with_observer(Arc::new(core_ops::observers::ProgressRenderer))
+        }
+        OutputFormat::Json => executor.with_observer(Arc::new(core_ops::observers::JsonRenderer)),
+    };
 
     // Route to appropriate feature module
     if let Err(e) = cli.command.run(&executor) {
         eprintln!("Error executing command: {}", e);
-        std::process::exit(1);
+        let typed = e.downcast_ref::<core_error::HymodError>();
+        if let Some(hint) = typed.and_then(|err| err.hint()) {
+            eprintln!("hint: {}", hint);
+        }
+        if run_log.is_written() {
+            eprintln!("log: {}", run_log.path().display());
+        }
+        std::process::exit(typed.map_or(1, |err| err.exit_code()));
     }
 }
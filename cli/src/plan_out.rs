@@ -0,0 +1,10 @@
+use anyhow::Result;
+use core_plan::Plan;
+use std::path::Path;
+
+/// Write `plan` to `path` instead of executing it, for later review and `hymod apply`.
+pub fn write_plan(plan: &Plan, path: &Path) -> Result<()> {
+    core_plan::save_plan(plan, path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    println!("Plan written to {}", path.display());
+    Ok(())
+}
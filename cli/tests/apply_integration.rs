@@ -0,0 +1,7 @@
+mod support;
+
+#[path = "../apply/tests"]
+mod apply_tests {
+    mod test_apply_diff;
+    mod test_apply_plan_file;
+}
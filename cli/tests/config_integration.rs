@@ -1,15 +1,16 @@
 //! Integration tests for the `hymod config` command.
 
-use assert_cmd::Command;
-use std::env;
+mod support;
+
+use predicates::prelude::*;
 use std::fs;
+use support::hymod;
 use tempfile::TempDir;
 
 #[test]
 fn config_set_get_and_list() {
     // Create a temporary home directory to avoid polluting the real user config.
     let temp_home = TempDir::new().expect("failed to create temp dir");
-    env::set_var("HOME", temp_home.path());
 
     // Ensure the config file does not exist initially.
     let config_path = temp_home.path().join(".hymod").join("config.yaml");
@@ -18,26 +19,206 @@ fn config_set_get_and_list() {
     }
 
     // Set a config value.
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(temp_home.path())
         .args(["config", "set", "author", "TestAuthor"])
         .assert()
         .success()
         .stdout(predicates::str::contains("Set author = TestAuthor"));
 
     // Get the config value.
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(temp_home.path())
         .args(["config", "get", "author"])
         .assert()
         .success()
         .stdout(predicates::str::contains("TestAuthor"));
 
     // List all config values and ensure the author appears.
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
+    hymod(temp_home.path())
         .args(["config", "list"])
         .assert()
         .success()
         .stdout(predicates::str::contains("author: TestAuthor"));
 }
+
+#[test]
+fn config_explain_shows_every_layer() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let project = TempDir::new().expect("failed to create project dir");
+    fs::write(
+        project.path().join("hymod.yaml"),
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\ndefaults:\n  server: local-dev\n",
+    )
+    .unwrap();
+
+    hymod(temp_home.path())
+        .args(["config", "set", "author", "GlobalAuthor"])
+        .assert()
+        .success();
+
+    hymod(temp_home.path())
+        .current_dir(project.path())
+        .env("HYMOD_AUTHOR", "EnvAuthor")
+        .args(["config", "explain", "author"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("author = EnvAuthor"))
+        .stdout(
+            predicates::str::is_match(r"environment \(HYMOD_AUTHOR\) +EnvAuthor  <- used").unwrap(),
+        )
+        .stdout(predicates::str::contains("GlobalAuthor"))
+        .stdout(predicates::str::contains("built-in default"));
+
+    hymod(temp_home.path())
+        .current_dir(project.path())
+        .args(["config", "explain", "server"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("server = local-dev"))
+        .stdout(predicates::str::contains("hymod.yaml)"));
+
+    hymod(temp_home.path())
+        .args(["config", "explain", "colour"])
+        .assert()
+        .code(2)
+        .stderr(predicates::str::contains("Unknown config key: colour"));
+}
+
+#[test]
+fn config_get_and_list_resolve_every_layer() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let project = TempDir::new().expect("failed to create project dir");
+    fs::write(
+        project.path().join("hymod.yaml"),
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\ndefaults:\n  server: local-dev\n",
+    )
+    .unwrap();
+
+    hymod(temp_home.path())
+        .args(["config", "set", "author", "GlobalAuthor"])
+        .assert()
+        .success();
+
+    hymod(temp_home.path())
+        .current_dir(project.path())
+        .env("HYMOD_AUTHOR", "EnvAuthor")
+        .args(["config", "get", "author"])
+        .assert()
+        .success()
+        .stdout("EnvAuthor\n");
+    hymod(temp_home.path())
+        .current_dir(project.path())
+        .args(["config", "get", "server"])
+        .assert()
+        .success()
+        .stdout("local-dev\n");
+
+    hymod(temp_home.path())
+        .current_dir(project.path())
+        .env("HYMOD_TIMEOUTS_SSH_RUN", "90")
+        .args(["config", "list"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("author: GlobalAuthor  (global ("))
+        .stdout(predicates::str::contains("server: local-dev  (project ("))
+        .stdout(predicates::str::contains(
+            "timeouts.ssh_run: 90  (environment (HYMOD_TIMEOUTS_SSH_RUN))",
+        ))
+        .stdout(predicates::str::contains(
+            "group: com.example  (built-in default)",
+        ))
+        .stdout(predicates::str::contains("username: <unset>"));
+}
+
+#[test]
+fn config_migrate_upgrades_old_files_with_backups() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let hymod_dir = temp_home.path().join(".hymod");
+    let servers = hymod_dir.join("servers.d");
+    fs::create_dir_all(&servers).unwrap();
+    let old_server = "server:\n  name: prod\n  kind: ssh\n  server_root: /opt/hytale\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n  ssh:\n    host: example.com\n    user: deploy\n";
+    fs::write(servers.join("prod.yaml"), old_server).unwrap();
+    fs::write(servers.join("default"), "prod").unwrap();
+    fs::write(hymod_dir.join("config.yaml"), "desc: My mods\n").unwrap();
+
+    hymod(temp_home.path())
+        .args(["config", "migrate", "--check"])
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("server.ssh -> server.remote"))
+        .stdout(predicates::str::contains("desc -> description"))
+        .stdout(predicates::str::contains("renamed to default.remote"));
+    // --check changes nothing.
+    assert_eq!(
+        fs::read_to_string(servers.join("prod.yaml")).unwrap(),
+        old_server
+    );
+
+    // Reading the default server does not migrate the legacy file either.
+    hymod(temp_home.path())
+        .args(["config", "explain", "server"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("prod"));
+    assert!(servers.join("default").is_file());
+    assert!(!servers.join("default.remote").exists());
+
+    hymod(temp_home.path())
+        .args(["config", "migrate"])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(servers.join("prod.yaml.v1.bak")).unwrap(),
+        old_server
+    );
+    assert!(fs::read_to_string(servers.join("prod.yaml"))
+        .unwrap()
+        .starts_with("schema_version: 2\n"));
+    assert_eq!(
+        fs::read_to_string(servers.join("default.remote")).unwrap(),
+        "prod"
+    );
+    assert_eq!(
+        fs::read_to_string(servers.join("default.v1.bak")).unwrap(),
+        "prod"
+    );
+    assert!(!servers.join("default").exists());
+
+    hymod(temp_home.path())
+        .args(["config", "get", "description"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("My mods"));
+    hymod(temp_home.path())
+        .args(["config", "migrate", "--check"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "All config files are up to date.",
+        ));
+}
+
+#[test]
+fn config_set_diff_shows_change_without_writing() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let config_path = temp_home.path().join(".hymod").join("config.yaml");
+
+    hymod(temp_home.path())
+        .args(["config", "set", "author", "Old"])
+        .assert()
+        .success();
+    let before = fs::read_to_string(&config_path).unwrap();
+
+    hymod(temp_home.path())
+        .args(["--diff", "config", "set", "author", "New"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "--- {}",
+            config_path.display()
+        )))
+        .stdout(predicates::str::contains("-author: Old\n"))
+        .stdout(predicates::str::contains("+author: New\n"))
+        .stdout(predicates::str::contains("Set author").not());
+
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), before);
+}
@@ -1,3 +1,5 @@
+mod support;
+
 #[path = "../deploy/tests"]
 mod deploy_tests {
 
@@ -5,13 +7,28 @@ mod deploy_tests {
     mod test_deploy_dry_run_with_transport;
     mod test_deploy_force_scp;
     mod test_deploy_full_pipeline;
+    mod test_deploy_gradle_config;
+    mod test_deploy_link_mode;
+    mod test_deploy_multiple_servers;
 
     mod test_deploy_path_arg;
+    mod test_deploy_path_project_registry;
+    mod test_deploy_plan_out;
+    mod test_deploy_preflight;
     mod test_deploy_remote_path_jar;
+    mod test_deploy_retry_policy;
+    mod test_deploy_ssh_options;
 
     mod test_deploy_with_default_server;
     mod test_deploy_with_server_name;
 
+    mod test_error_invalid_transport;
+    mod test_error_missing_server_no_default;
+    mod test_error_server_not_found;
+    mod test_error_transport_unavailable;
+
+    mod test_golden_plan_deploy;
+
     mod test_transport_override_rsync;
     mod test_transport_override_scp;
 }
@@ -1,3 +1,5 @@
+mod support;
+
 #[path = "../dev/tests"]
 mod dev_tests {
     mod test_default_server_dev_local;
@@ -5,6 +7,7 @@ mod dev_tests {
     mod test_deploy_to_target;
     mod test_dev_default_server_from_config;
     mod test_dev_full_pipeline;
+    mod test_dev_gradle_config;
     mod test_dev_manifest_naming;
     mod test_dev_no_default_server_error;
     mod test_dev_oneshot_flow;
@@ -13,6 +16,7 @@ mod dev_tests {
     mod test_dev_runs_build;
     mod test_dev_runs_link;
     mod test_dev_runs_restart;
+    mod test_dev_skips_unchanged_artifact;
     mod test_dev_watch_mode_init;
     mod test_dev_with_default_server;
     mod test_dev_with_server_name;
@@ -1,3 +1,5 @@
+mod support;
+
 #[path = "../link/tests"]
 mod link_tests {
     mod test_default_server_link_local;
@@ -15,6 +17,7 @@ mod link_tests {
     mod test_link_creates_mods_dir;
     mod test_link_creates_symlink;
     mod test_link_local_server;
+    mod test_link_mode;
     mod test_link_multiple_mods_same_server;
     mod test_link_overwrites_existing_symlink;
     mod test_link_server_not_found;
@@ -1,3 +1,5 @@
+mod support;
+
 #[path = "../new/tests"]
 mod new_tests {
     mod test_error_empty_name;
@@ -1,15 +1,18 @@
-use assert_cmd::Command;
+mod support;
+
 use predicates::prelude::*;
 use std::fs;
+use support::hymod;
 use tempfile::TempDir;
 
+const PROJECT_YAML: &str =
+    "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\n";
+
 #[test]
 fn test_server_add_local() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "local", "myserver", "/tmp/myserver"])
         .assert()
         .success()
@@ -34,9 +37,7 @@ fn test_server_add_local() {
 fn test_server_add_remote() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "remote", "myremote", "user@example.com"])
         .assert()
         .success()
@@ -62,9 +63,7 @@ fn test_server_add_remote() {
 fn test_server_add_remote_with_path_parses_server_root() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args([
             "server",
             "add",
@@ -97,17 +96,13 @@ fn test_server_already_exists() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
     // Add first time
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "local", "myserver", "/tmp/path"])
         .assert()
         .success();
 
     // Add second time
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "local", "myserver", "/tmp/path"])
         .assert()
         .failure()
@@ -118,23 +113,17 @@ fn test_server_already_exists() {
 fn test_server_list() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "local", "s1", "/tmp/s1"])
         .assert()
         .success();
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "remote", "s2", "u@h"])
         .assert()
         .success();
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "list"])
         .assert()
         .success()
@@ -146,26 +135,20 @@ fn test_server_list() {
 fn test_server_default() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "local", "s1", "/tmp/s1"])
         .assert()
         .success();
 
     // Set default
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "default", "local", "s1"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Default local server set to 's1'"));
 
     // Check list highlight
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "list"])
         .assert()
         .success()
@@ -176,30 +159,22 @@ fn test_server_default() {
 fn test_server_default_separate_local_and_remote() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "local", "local1", "/tmp/local1"])
         .assert()
         .success();
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "remote", "remote1", "user@example.com"])
         .assert()
         .success();
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "default", "local", "local1"])
         .assert()
         .success();
 
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "default", "remote", "remote1"])
         .assert()
         .success();
@@ -216,7 +191,10 @@ fn test_server_default_separate_local_and_remote() {
         .join("default.remote");
 
     assert_eq!(fs::read_to_string(default_local).unwrap().trim(), "local1");
-    assert_eq!(fs::read_to_string(default_remote).unwrap().trim(), "remote1");
+    assert_eq!(
+        fs::read_to_string(default_remote).unwrap().trim(),
+        "remote1"
+    );
 }
 
 #[test]
@@ -224,9 +202,7 @@ fn test_server_remove() {
     let temp_home = TempDir::new().expect("failed to create temp dir");
 
     // Add server
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "add", "local", "removetest", "/tmp/removetest"])
         .assert()
         .success();
@@ -239,9 +215,7 @@ fn test_server_remove() {
     assert!(config_path.exists());
 
     // Remove server
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "remove", "removetest"])
         .assert()
         .success()
@@ -253,9 +227,7 @@ fn test_server_remove() {
     assert!(!config_path.exists());
 
     // Remove non-existent
-    Command::cargo_bin("hymod")
-        .expect("binary not found")
-        .env("HOME", temp_home.path())
+    hymod(temp_home.path())
         .args(["server", "remove", "nonexistent"])
         .assert()
         .failure()
@@ -263,3 +235,433 @@ fn test_server_remove() {
             "Server configuration 'nonexistent' not found",
         ));
 }
+
+#[test]
+fn test_hymod_home_relocates_config() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let hymod_home = TempDir::new().expect("failed to create hymod home");
+
+    hymod(temp_home.path())
+        .env("HYMOD_HOME", hymod_home.path())
+        .args(["server", "add", "local", "ci", "/tmp/ci-server"])
+        .assert()
+        .success();
+    hymod(temp_home.path())
+        .env("HYMOD_HOME", hymod_home.path())
+        .args(["config", "set", "author", "CI"])
+        .assert()
+        .success();
+
+    assert!(hymod_home.path().join("servers.d").join("ci.yaml").exists());
+    assert!(hymod_home.path().join("config.yaml").exists());
+    assert!(!temp_home.path().join(".hymod").exists());
+
+    hymod(temp_home.path())
+        .args(["server", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No servers configured."));
+}
+
+#[test]
+fn test_project_server_registry_merged_over_user() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let project = TempDir::new().expect("failed to create project dir");
+
+    hymod(temp_home.path())
+        .args(["server", "add", "local", "mine", "/tmp/mine"])
+        .assert()
+        .success();
+    hymod(temp_home.path())
+        .args(["server", "add", "local", "staging", "/tmp/personal-staging"])
+        .assert()
+        .success();
+
+    fs::write(project.path().join("hymod.yaml"), PROJECT_YAML).unwrap();
+    let registry = project.path().join(".hymod").join("servers.d");
+    fs::create_dir_all(&registry).unwrap();
+    fs::write(
+        registry.join("staging.yaml"),
+        "server:\n  name: staging\n  kind: local\n  server_root: /srv/team-staging\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n",
+    )
+    .unwrap();
+    let nested = project.path().join("src");
+    fs::create_dir(&nested).unwrap();
+
+    hymod(temp_home.path())
+        .current_dir(&nested)
+        .args(["server", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("mine"))
+        .stdout(predicate::str::contains("/srv/team-staging"))
+        .stdout(predicate::str::contains("/tmp/personal-staging").not());
+
+    hymod(temp_home.path())
+        .current_dir(&nested)
+        .args(["server", "remove", "staging"])
+        .assert()
+        .success();
+
+    // The personal copy is gone; the project's definition stays.
+    assert!(registry.join("staging.yaml").exists());
+    hymod(temp_home.path())
+        .current_dir(&nested)
+        .args(["server", "remove", "staging"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("defined by the project"));
+}
+
+#[test]
+fn test_server_validate_reports_every_problem() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+
+    hymod(temp_home.path())
+        .args(["server", "add", "local", "good", "/tmp/good"])
+        .assert()
+        .success();
+
+    let registry = temp_home.path().join(".hymod").join("servers.d");
+    fs::write(
+        registry.join("broken.yaml"),
+        "server:\n  name: other\n  kind: local\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n",
+    )
+    .unwrap();
+    fs::write(
+        registry.join("remote.yaml"),
+        "server:\n  name: remote\n  kind: remote\n  server_root: /opt/hytale\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n  remote:\n    user: deploy\n  upload:\n    method: ftp\n",
+    )
+    .unwrap();
+
+    hymod(temp_home.path())
+        .args(["server", "validate"])
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("good.yaml"))
+        .stdout(predicate::str::contains(
+            "server.name: 'other' does not match the file name 'broken.yaml'",
+        ))
+        .stdout(predicate::str::contains("server.server_root: missing"))
+        .stdout(predicate::str::contains("server.remote.host: missing"))
+        .stdout(predicate::str::contains(
+            "server.upload.method: 'ftp' is not one of rsync, scp",
+        ))
+        .stderr(predicate::str::contains("4 problem(s) in 2 files"));
+
+    hymod(temp_home.path())
+        .args(["server", "validate", "good"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_server_set_changes_one_field() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    hymod(temp_home.path())
+        .args(["server", "add", "remote", "prod", "deploy@example.com"])
+        .assert()
+        .success();
+    hymod(temp_home.path())
+        .args(["server", "set", "prod", "restart.cmd", "./restart.sh"])
+        .assert()
+        .success();
+    hymod(temp_home.path())
+        .args(["server", "set", "prod", "server.remote.port", "2222"])
+        .assert()
+        .success();
+
+    let servers = temp_home.path().join(".hymod").join("servers.d");
+    let content = fs::read_to_string(servers.join("prod.yaml")).unwrap();
+    assert!(content.contains("cmd: ./restart.sh"));
+    assert!(content.contains("port: 2222"));
+
+    // Typed and whole-file validation failures leave the file alone.
+    hymod(temp_home.path())
+        .args(["server", "set", "prod", "remote.port", "0"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("is not a port number"));
+    hymod(temp_home.path())
+        .args(["server", "set", "prod", "remote.host", ""])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "server.remote.host: must not be empty",
+        ));
+    assert_eq!(
+        fs::read_to_string(servers.join("prod.yaml")).unwrap(),
+        content
+    );
+
+    hymod(temp_home.path())
+        .args(["server", "default", "remote", "prod"])
+        .assert()
+        .success();
+    hymod(temp_home.path())
+        .args(["server", "set", "prod", "name", "live"])
+        .assert()
+        .success();
+    assert!(!servers.join("prod.yaml").exists());
+    assert!(servers.join("live.yaml").exists());
+    assert_eq!(
+        fs::read_to_string(servers.join("default.remote")).unwrap(),
+        "live"
+    );
+}
+
+#[test]
+fn test_server_edit_validates_before_saving() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let with_editor = |editor: &str| {
+        let mut cmd = hymod(temp_home.path());
+        cmd.env_remove("VISUAL").env("EDITOR", editor);
+        cmd
+    };
+
+    with_editor("true")
+        .args(["server", "add", "local", "dev", "/srv/dev"])
+        .assert()
+        .success();
+    let path = temp_home
+        .path()
+        .join(".hymod")
+        .join("servers.d")
+        .join("dev.yaml");
+
+    with_editor("sed -i s/mods_dir:.*/mods_dir:\\ plugins/")
+        .args(["server", "edit", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Server 'dev' updated."));
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("mods_dir: plugins"));
+
+    with_editor("sed -i /server_root:/d")
+        .args(["server", "edit", "dev"])
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("server.server_root: missing"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), content);
+
+    with_editor("true")
+        .args(["server", "edit", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No changes"));
+}
+
+#[test]
+fn test_server_add_with_flags() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    hymod(temp_home.path())
+        .args([
+            "server",
+            "add",
+            "remote",
+            "prod",
+            "deploy@example.com:/srv/hytale",
+            "--port",
+            "2222",
+            "--identity-file",
+            "~/.ssh/prod",
+            "--known-hosts",
+            "~/.ssh/known_hosts.prod",
+            "--mods-dir",
+            "plugins",
+            "--restart-cmd",
+            "./restart.sh",
+            "--scp-opts",
+            "-C",
+        ])
+        .assert()
+        .success();
+
+    let servers = temp_home.path().join(".hymod").join("servers.d");
+    let content = fs::read_to_string(servers.join("prod.yaml")).unwrap();
+    for expected in [
+        "server_root: /srv/hytale",
+        "mods_dir: plugins",
+        "cmd: ./restart.sh",
+        "port: 2222",
+        "identity_file: ~/.ssh/prod",
+        "known_hosts_file: ~/.ssh/known_hosts.prod",
+        "method: scp",
+        "opts: -C",
+    ] {
+        assert!(
+            content.contains(expected),
+            "{} not in:\n{}",
+            expected,
+            content
+        );
+    }
+
+    hymod(temp_home.path())
+        .args([
+            "server", "add", "local", "dev", "/srv/dev", "--port", "2222",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "--port only applies to remote servers",
+        ));
+    hymod(temp_home.path())
+        .args([
+            "server",
+            "add",
+            "remote",
+            "staging",
+            "deploy@example.com",
+            "--upload-method",
+            "ftp",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "server.upload.method: 'ftp' is not one of rsync, scp",
+        ));
+    assert!(!servers.join("dev.yaml").exists());
+    assert!(!servers.join("staging.yaml").exists());
+
+    hymod(temp_home.path())
+        .args([
+            "server",
+            "add",
+            "remote",
+            "backup",
+            "deploy@example.com",
+            "--server-root",
+            "/data/hytale",
+        ])
+        .assert()
+        .success();
+    let content = fs::read_to_string(servers.join("backup.yaml")).unwrap();
+    assert!(content.contains("server_root: /data/hytale"));
+}
+
+#[test]
+fn test_server_add_interactive_without_terminal_uses_defaults() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+
+    // Without a terminal every prompt takes its default and the connectivity
+    // check is skipped.
+    hymod(temp_home.path())
+        .args([
+            "server",
+            "add",
+            "-i",
+            "local",
+            "dev",
+            "/srv/dev",
+            "--mods-dir",
+            "plugins",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Server 'dev' added successfully."));
+
+    let content = fs::read_to_string(
+        temp_home
+            .path()
+            .join(".hymod")
+            .join("servers.d")
+            .join("dev.yaml"),
+    )
+    .unwrap();
+    assert!(content.contains("server_root: /srv/dev"));
+    assert!(content.contains("mods_dir: plugins"));
+}
+
+#[test]
+fn test_server_add_interactive_dry_run_skips_connectivity_check() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+
+    // A dry run only lists the checks, so they are reported as skipped
+    // rather than as passed.
+    hymod(temp_home.path())
+        .args([
+            "--dry-run",
+            "server",
+            "add",
+            "-i",
+            "local",
+            "dev",
+            "/srv/dev",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipped the connectivity check (dry run).",
+        ))
+        .stdout(predicate::str::contains("PREFLIGHT").not());
+}
+
+#[test]
+fn test_hymod_home_ignores_user_registry_above_cwd() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let hymod_home = TempDir::new().expect("failed to create hymod home");
+
+    // A decoy registry in ~/.hymod, above the working directory, even with a
+    // hymod.yaml next to it.
+    let decoy = temp_home.path().join(".hymod").join("servers.d");
+    fs::create_dir_all(&decoy).unwrap();
+    fs::write(
+        decoy.join("decoy.yaml"),
+        "server:\n  name: decoy\n  kind: local\n  server_root: /srv/decoy\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n",
+    )
+    .unwrap();
+    fs::write(temp_home.path().join("hymod.yaml"), PROJECT_YAML).unwrap();
+    let work = temp_home.path().join("work").join("mod");
+    fs::create_dir_all(&work).unwrap();
+
+    let run = || {
+        let mut cmd = hymod(temp_home.path());
+        cmd.current_dir(&work).env("HYMOD_HOME", hymod_home.path());
+        cmd
+    };
+    run()
+        .args(["server", "add", "local", "ci", "/srv/ci"])
+        .assert()
+        .success();
+
+    run()
+        .args(["server", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ci"))
+        .stdout(predicate::str::contains("decoy").not());
+    run().args(["server", "get", "decoy"]).assert().code(5);
+}
+
+#[test]
+fn test_server_set_and_remove_diff_without_writing() {
+    let temp_home = TempDir::new().expect("failed to create temp dir");
+    let server_file = temp_home
+        .path()
+        .join(".hymod")
+        .join("servers.d")
+        .join("prod.yaml");
+
+    hymod(temp_home.path())
+        .args(["server", "add", "local", "prod", "/srv/prod"])
+        .assert()
+        .success();
+    let before = fs::read_to_string(&server_file).unwrap();
+
+    hymod(temp_home.path())
+        .args(["--diff", "server", "set", "prod", "mods_dir", "plugins"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("-  mods_dir: mods\n"))
+        .stdout(predicate::str::contains("+  mods_dir: plugins\n"));
+
+    hymod(temp_home.path())
+        .args(["--diff", "server", "remove", "prod"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+++ /dev/null"))
+        .stdout(predicate::str::contains("-  name: prod\n"));
+
+    assert_eq!(fs::read_to_string(&server_file).unwrap(), before);
+}
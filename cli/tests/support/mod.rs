@@ -0,0 +1,38 @@
+//! Helpers shared by the CLI integration tests.
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+/// The `hymod` binary with `HOME` set to `home` and `HYMOD_HOME` to
+/// `home/.hymod`, so a test never reads or writes the real user's config,
+/// servers or logs, whatever `HYMOD_HOME` the test run inherited.
+pub fn hymod(home: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("hymod").expect("binary not found");
+    cmd.env("HOME", home).env("HYMOD_HOME", home.join(".hymod"));
+    cmd
+}
+
+/// Write a jar that passes the checks deploy, dev and link run on a built
+/// mod: a `manifest.json` naming `com.example.Main`, and that class.
+pub fn write_mod_jar(path: &Path) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    let mut writer = ZipWriter::new(fs::File::create(path).unwrap());
+    writer
+        .start_file("manifest.json", FileOptions::default())
+        .unwrap();
+    writer
+        .write_all(br#"{ "Name": "test-mod", "Version": "1.0.0", "Main": "com.example.Main" }"#)
+        .unwrap();
+    writer
+        .start_file("com/example/Main.class", FileOptions::default())
+        .unwrap();
+    writer.write_all(b"\xca\xfe\xba\xbe").unwrap();
+    writer.finish().unwrap();
+}
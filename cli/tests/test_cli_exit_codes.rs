@@ -0,0 +1,135 @@
+mod support;
+
+use predicates::prelude::*;
+use std::fs;
+use support::hymod;
+use tempfile::TempDir;
+
+#[test]
+fn test_cli_exit_code_server_not_found() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["server", "get", "ghost"])
+        .assert()
+        .code(5)
+        .stderr(predicate::str::contains("hint: run: hymod server list"));
+}
+
+#[test]
+fn test_cli_exit_code_server_kind_mismatch() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["server", "add", "remote", "prod", "user@host:/srv/hytale"])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .args(["server", "default", "local", "prod"])
+        .assert()
+        .code(7)
+        .stderr(predicate::str::contains(
+            "Server 'prod' is a remote server, expected a local server",
+        ));
+}
+
+#[test]
+fn test_cli_exit_code_build_failed() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let mod_dir = TempDir::new().expect("failed to create mod dir");
+    let gradlew = mod_dir.path().join("gradlew");
+    fs::write(&gradlew, "#!/bin/sh\nexit 1\n").expect("failed to write mock gradlew");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&gradlew, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    hymod(home_dir.path())
+        .args(["dev", "--path", mod_dir.path().to_str().unwrap()])
+        .assert()
+        .code(11)
+        .stderr(predicate::str::contains("Gradle build failed"));
+}
+
+#[test]
+fn test_cli_exit_code_deploy_build_failed() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let mod_dir = TempDir::new().expect("failed to create mod dir");
+    let server_dir = TempDir::new().expect("failed to create server dir");
+    fs::create_dir(server_dir.path().join("mods")).unwrap();
+    let gradlew = mod_dir.path().join("gradlew");
+    fs::write(&gradlew, "#!/bin/sh\nexit 1\n").expect("failed to write mock gradlew");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&gradlew, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    hymod(home_dir.path())
+        .args([
+            "server",
+            "add",
+            "local",
+            "dev",
+            server_dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .args(["deploy", "dev", "--path", mod_dir.path().to_str().unwrap()])
+        .assert()
+        .code(11)
+        .stderr(predicate::str::contains("Gradle build failed"));
+}
+
+#[test]
+fn test_cli_exit_code_not_a_mod_directory() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let mod_dir = TempDir::new().expect("failed to create mod dir");
+
+    hymod(home_dir.path())
+        .args(["dev", "--path", mod_dir.path().to_str().unwrap()])
+        .assert()
+        .code(10)
+        .stderr(predicate::str::contains("Is this a mod directory?"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cli_exit_code_interrupted() {
+    use std::process::Stdio;
+    use std::time::Duration;
+
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let plan_dir = TempDir::new().expect("failed to create plan dir");
+    let plan = plan_dir.path().join("plan.yaml");
+    fs::write(
+        &plan,
+        "version: 1\nsteps:\n  - op: run_process\n    cmd: sleep\n    args: ['30']\n    cwd: null\n",
+    )
+    .expect("failed to write plan");
+
+    // `hymod` cannot be spawned and signalled, so set up its homes by hand.
+    let child = std::process::Command::new(assert_cmd::cargo::cargo_bin("hymod"))
+        .env("HOME", home_dir.path())
+        .env("HYMOD_HOME", home_dir.path().join(".hymod"))
+        .arg("apply")
+        .arg(&plan)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start hymod");
+    std::thread::sleep(Duration::from_millis(500));
+    std::process::Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .expect("failed to send SIGINT");
+
+    let output = child.wait_with_output().expect("hymod did not exit");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(130), "stderr: {}", stderr);
+    assert!(stderr.contains("Interrupted during step '1. RUN sleep 30"));
+}
@@ -1,4 +1,102 @@
+mod support;
+
+use predicates::prelude::*;
+use support::hymod;
+use tempfile::TempDir;
+
 #[test]
 fn test_cli_global_dry_run_flag() {
-    panic!("--dry-run flag must work for all commands: new, build, link, dev, deploy.");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    // new: plan is printed, nothing is created
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "new", "drymod"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PLAN"));
+    assert!(!temp_dir.path().join("drymod").exists());
+
+    // build: plan is printed, the build step is not run
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["build", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PLAN"))
+        .stdout(predicate::str::contains("Building project...").count(1));
+
+    // dev and deploy need a real project and a server
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["new", "testmod"])
+        .assert()
+        .success();
+    let project_dir = temp_dir.path().join("testmod");
+    let server_root = temp_dir.path().join("server");
+
+    hymod(home_dir.path())
+        .args([
+            "server",
+            "add",
+            "local",
+            "dev",
+            server_root.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["--dry-run", "dev", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PLAN"))
+        .stdout(predicate::str::contains("MKDIR"))
+        .stdout(predicate::str::contains("COPY"));
+
+    hymod(home_dir.path())
+        .current_dir(&project_dir)
+        .args(["--dry-run", "deploy", "dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PLAN"))
+        .stdout(predicate::str::contains("COPY"));
+
+    assert!(!server_root.exists());
+    assert!(!project_dir.join("build").exists());
+
+    // server and config writes: the change is listed, nothing is written
+    let hymod_home = home_dir.path().join(".hymod");
+    let servers_dir = hymod_home.join("servers.d");
+    let dev_file = servers_dir.join("dev.yaml");
+    let dev_content = std::fs::read_to_string(&dev_file).unwrap();
+    for args in [
+        vec!["--dry-run", "server", "add", "local", "other", "/srv/other"],
+        vec!["--dry-run", "server", "default", "local", "dev"],
+        vec!["--dry-run", "server", "set", "dev", "mods_dir", "plugins"],
+        vec!["--dry-run", "server", "remove", "dev"],
+        vec!["--dry-run", "config", "set", "author", "Someone"],
+    ] {
+        hymod(home_dir.path())
+            .args(&args)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("PLAN"))
+            .stdout(predicate::str::contains("successfully").not())
+            .stdout(predicate::str::contains("Set ").not());
+    }
+    assert!(!servers_dir.join("other.yaml").exists());
+    assert!(!servers_dir.join("default.local").exists());
+    assert_eq!(std::fs::read_to_string(&dev_file).unwrap(), dev_content);
+    assert!(!hymod_home.join("config.yaml").exists());
+
+    // -q: the write happens without a success line
+    hymod(home_dir.path())
+        .args(["-q", "server", "add", "local", "quiet", "/srv/quiet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+    assert!(servers_dir.join("quiet.yaml").exists());
 }
@@ -0,0 +1,60 @@
+mod support;
+
+use predicates::prelude::*;
+use std::fs;
+use support::hymod;
+use tempfile::TempDir;
+
+#[test]
+fn test_cli_logs_record_failed_apply() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let plan_dir = TempDir::new().expect("failed to create plan dir");
+    let plan = plan_dir.path().join("plan.yaml");
+    fs::write(
+        &plan,
+        "version: 1\nsteps:\n  - op: run_process\n    cmd: sh\n    args: ['-c', 'echo uploading; echo denied >&2; exit 4']\n    cwd: null\n",
+    )
+    .expect("failed to write plan");
+
+    hymod(home_dir.path())
+        .arg("apply")
+        .arg(&plan)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("uploading"))
+        .stderr(predicate::str::contains("log: "));
+
+    hymod(home_dir.path())
+        .args(["logs", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("apply"))
+        .stdout(predicate::str::contains("failed"));
+
+    hymod(home_dir.path())
+        .args(["logs", "last"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("apply"))
+        .stdout(predicate::str::contains("[1] uploading"))
+        .stdout(predicate::str::contains("[1 stderr] denied"))
+        .stdout(predicate::str::contains("exit 4"))
+        .stdout(predicate::str::contains("== plan failed"));
+}
+
+#[test]
+fn test_cli_logs_skip_commands_without_a_plan() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["server", "list"])
+        .assert()
+        .success();
+
+    hymod(home_dir.path())
+        .args(["logs", "last"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No run logs"));
+    assert!(!home_dir.path().join(".hymod").join("logs").exists());
+}
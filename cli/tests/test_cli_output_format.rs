@@ -0,0 +1,66 @@
+mod support;
+
+use predicates::prelude::*;
+use support::hymod;
+use tempfile::TempDir;
+
+#[test]
+fn test_cli_output_json_emits_one_event_per_line() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+
+    let output = hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["--output", "json", "build"])
+        .output()
+        .expect("failed to run hymod");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let events: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("every stdout line should be JSON"))
+        .collect();
+
+    let kinds: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds.first(), Some(&"plan_started"));
+    assert_eq!(kinds.last(), Some(&"plan_finished"));
+    assert!(kinds.contains(&"step_started"));
+    assert!(kinds.contains(&"step_finished"));
+
+    let output_line = events
+        .iter()
+        .find(|event| event["event"] == "output")
+        .expect("child output should be reported as an event");
+    assert_eq!(output_line["step"], "1");
+    assert_eq!(output_line["stream"], "stdout");
+    assert_eq!(output_line["line"], "Building project...");
+}
+
+#[test]
+fn test_cli_output_progress_reports_step_status() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["build", "--output", "progress"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1. RUN"))
+        .stdout(predicate::str::contains("1 | Building project..."))
+        .stdout(predicate::str::contains("1. done in"));
+}
+
+#[test]
+fn test_cli_output_rejects_unknown_format() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["--output", "xml", "build"])
+        .assert()
+        .failure();
+}
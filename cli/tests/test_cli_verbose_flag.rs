@@ -1,4 +1,47 @@
+mod support;
+
+use predicates::prelude::*;
+use support::hymod;
+use tempfile::TempDir;
+
 #[test]
 fn test_cli_verbose_flag() {
-    panic!("--verbose flag must increase log output verbosity.");
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["--verbose", "build"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "EXEC      echo Building project...",
+        ))
+        .stdout(predicate::str::contains(format!(
+            "(cwd: {})",
+            temp_dir.path().display()
+        )));
+}
+
+#[test]
+fn test_cli_quiet_flag() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+
+    hymod(home_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["build", "--quiet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_cli_verbose_and_quiet_conflict() {
+    let home_dir = TempDir::new().expect("failed to create home dir");
+
+    hymod(home_dir.path())
+        .args(["--verbose", "--quiet", "build"])
+        .assert()
+        .failure();
 }
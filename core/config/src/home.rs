@@ -0,0 +1,25 @@
+use std::path::{Path, PathBuf};
+
+/// Environment variable that moves hymod's configuration out of `~/.hymod`,
+/// e.g. to keep CI, containers or several identities apart.
+pub const HYMOD_HOME_ENV: &str = "HYMOD_HOME";
+
+/// Directory holding `config.yaml`, `servers.d/` and `logs/`: `$HYMOD_HOME`
+/// when set, otherwise `~/.hymod`.
+pub fn hymod_home() -> Result<PathBuf, String> {
+    if let Some(dir) = std::env::var_os(HYMOD_HOME_ENV).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home.join(".hymod"))
+}
+
+/// Whether `dir` is a hymod home, the current one or `~/.hymod`, rather than
+/// a project's `.hymod` directory.
+pub(crate) fn is_hymod_home(dir: &Path) -> bool {
+    let same = |home: PathBuf| match (home.canonicalize(), dir.canonicalize()) {
+        (Ok(home), Ok(dir)) => home == dir,
+        _ => home == dir,
+    };
+    hymod_home().is_ok_and(same) || dirs::home_dir().is_some_and(|home| same(home.join(".hymod")))
+}
@@ -1,4 +1,15 @@
+pub mod home;
+pub use home::*;
+pub mod logs;
+pub mod migrate;
+pub use migrate::*;
+pub mod project;
+pub use project::*;
+pub mod resolve;
+pub use resolve::*;
 pub mod server;
 pub use server::*;
 pub mod user;
 pub use user::*;
+pub mod validate;
+pub use validate::*;
@@ -0,0 +1,75 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+/// How many run logs are kept; older ones are removed when a new one is named.
+pub const MAX_RUN_LOGS: usize = 50;
+
+/// A run log in `$HYMOD_HOME/logs/` (`~/.hymod/logs/` by default), named `<command>-<timestamp>.log`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunLog {
+    pub path: PathBuf,
+    pub command: String,
+    /// UTC start time as written in the file name, e.g. `20261018T142233.120Z`.
+    pub timestamp: String,
+}
+
+impl RunLog {
+    fn from_path(path: &Path) -> Option<Self> {
+        if path.extension()? != "log" {
+            return None;
+        }
+        let stem = path.file_stem()?.to_str()?;
+        let (command, timestamp) = stem.rsplit_once('-')?;
+        Some(Self {
+            path: path.to_path_buf(),
+            command: command.to_string(),
+            timestamp: timestamp.to_string(),
+        })
+    }
+}
+
+pub fn logs_dir() -> PathBuf {
+    crate::home::hymod_home()
+        .unwrap_or_else(|_| PathBuf::from(".hymod"))
+        .join("logs")
+}
+
+/// Path for the log of a `command` run starting now. Makes room for it by
+/// pruning the oldest logs beyond `MAX_RUN_LOGS`; the file itself is not created.
+pub fn new_log_path(command: &str) -> PathBuf {
+    let _ = prune_logs(MAX_RUN_LOGS.saturating_sub(1));
+    let format =
+        format_description!("[year][month][day]T[hour][minute][second].[subsecond digits:3]Z");
+    let timestamp = OffsetDateTime::now_utc()
+        .format(&format)
+        .unwrap_or_else(|_| "unknown".to_string());
+    logs_dir().join(format!("{}-{}.log", command, timestamp))
+}
+
+/// Every run log, newest first.
+pub fn list_logs() -> io::Result<Vec<RunLog>> {
+    let dir = logs_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut logs: Vec<RunLog> = fs::read_dir(dir)?
+        .filter_map(|entry| RunLog::from_path(&entry.ok()?.path()))
+        .collect();
+    logs.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(logs)
+}
+
+pub fn latest_log() -> io::Result<Option<RunLog>> {
+    Ok(list_logs()?.into_iter().next())
+}
+
+/// Remove all but the `keep` newest run logs.
+pub fn prune_logs(keep: usize) -> io::Result<()> {
+    for log in list_logs()?.into_iter().skip(keep) {
+        fs::remove_file(&log.path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,312 @@
+use crate::server::{server_config_dirs, server_files};
+use crate::user::config_path;
+use crate::validate::describe;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The `schema_version` written to server files in `servers.d/`.
+pub const SERVER_SCHEMA_VERSION: u32 = 2;
+
+/// The `schema_version` written to the global `config.yaml`.
+pub const USER_SCHEMA_VERSION: u32 = 2;
+
+/// Files written before config files were versioned have no
+/// `schema_version` and are treated as version 1.
+const UNVERSIONED: u32 = 1;
+
+/// Name of the remote default server file in `servers.d/` from before
+/// local and remote servers had a default each.
+pub(crate) const LEGACY_DEFAULT_FILE: &str = "default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFileKind {
+    Server,
+    User,
+}
+
+impl ConfigFileKind {
+    pub fn current_version(self) -> u32 {
+        match self {
+            ConfigFileKind::Server => SERVER_SCHEMA_VERSION,
+            ConfigFileKind::User => USER_SCHEMA_VERSION,
+        }
+    }
+
+    fn migrations(self) -> &'static [Migration] {
+        match self {
+            ConfigFileKind::Server => SERVER_MIGRATIONS,
+            ConfigFileKind::User => USER_MIGRATIONS,
+        }
+    }
+}
+
+/// Upgrade of a file from schema version `from` to `from + 1`.
+struct Migration {
+    from: u32,
+    apply: fn(&mut Mapping) -> Vec<String>,
+}
+
+const SERVER_MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    apply: server_v1_to_v2,
+}];
+
+const USER_MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    apply: user_v1_to_v2,
+}];
+
+/// What upgrading a config file to the current schema did, or would do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migrated {
+    pub from: u32,
+    pub to: u32,
+    /// One line per key that was renamed or rewritten.
+    pub changes: Vec<String>,
+}
+
+impl Migrated {
+    pub fn is_current(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+/// A config file on disk that uses an older schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMigration {
+    pub path: PathBuf,
+    pub migrated: Migrated,
+    /// Copy of the original file, once the migration has been written.
+    pub backup: Option<PathBuf>,
+}
+
+/// Upgrade the parsed content of a config file of `kind` to the current
+/// schema in memory. Files newer than this hymod understands are an error.
+pub fn migrate_value(value: &mut Value, kind: ConfigFileKind) -> Result<Migrated, String> {
+    let Some(config) = value.as_mapping_mut() else {
+        return Err(format!(
+            "expected a mapping at the top level, found {}",
+            describe(value)
+        ));
+    };
+    let from = match config.get("schema_version") {
+        None => UNVERSIONED,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|&version| version >= 1)
+            .ok_or_else(|| {
+                format!(
+                    "schema_version: {} is not a positive integer",
+                    describe(version)
+                )
+            })?,
+    };
+    let to = kind.current_version();
+    if from > to {
+        return Err(format!(
+            "schema_version: {} is newer than this hymod supports ({}); upgrade hymod",
+            from, to
+        ));
+    }
+
+    let mut changes = Vec::new();
+    for migration in kind.migrations().iter().filter(|m| m.from >= from) {
+        changes.extend((migration.apply)(config));
+    }
+    if from < to {
+        *value = with_schema_version(value.clone(), to);
+    }
+    Ok(Migrated { from, to, changes })
+}
+
+/// `value` with `schema_version: version` as its first key.
+pub fn with_schema_version(value: Value, version: u32) -> Value {
+    let Value::Mapping(config) = value else {
+        return value;
+    };
+    let mut versioned = Mapping::new();
+    versioned.insert("schema_version".into(), version.into());
+    versioned.extend(
+        config
+            .into_iter()
+            .filter(|(key, _)| key.as_str() != Some("schema_version")),
+    );
+    Value::Mapping(versioned)
+}
+
+/// What migrating the file at `path` would change, or `None` if it already
+/// uses the current schema.
+pub fn check_file(path: &Path, kind: ConfigFileKind) -> Result<Option<Migrated>, String> {
+    Ok(read_migrated(path, kind)?.map(|(migrated, _)| migrated))
+}
+
+/// Upgrade the file at `path` in place, keeping the original next to it as
+/// `<file>.v<N>.bak`. Returns `None` if it already uses the current schema.
+pub fn migrate_file(path: &Path, kind: ConfigFileKind) -> Result<Option<FileMigration>, String> {
+    let Some((migrated, value)) = read_migrated(path, kind)? else {
+        return Ok(None);
+    };
+    let backup = backup_path(path, migrated.from);
+    fs::copy(path, &backup).map_err(|e| format!("{}: {}", backup.display(), e))?;
+    let content = serde_yaml::to_string(&value).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(Some(FileMigration {
+        path: path.to_path_buf(),
+        migrated,
+        backup: Some(backup),
+    }))
+}
+
+/// Every config file hymod reads that uses an older schema: the global
+/// config, then the server files of each registry as seen from `start`.
+pub fn outdated_config_files(start: &Path) -> Result<Vec<FileMigration>, String> {
+    let mut outdated = Vec::new();
+    for (path, kind) in config_files(start)? {
+        if let Some(migrated) = check_file(&path, kind)? {
+            outdated.push(FileMigration {
+                path,
+                migrated,
+                backup: None,
+            });
+        }
+    }
+    for dir in server_config_dirs(start)? {
+        let legacy = dir.join(LEGACY_DEFAULT_FILE);
+        if legacy.is_file() {
+            let superseded = dir.join("default.remote").exists();
+            outdated.push(legacy_default_migration(legacy, superseded, None));
+        }
+    }
+    Ok(outdated)
+}
+
+/// Upgrade every config file hymod reads, see `outdated_config_files`.
+pub fn migrate_config_files(start: &Path) -> Result<Vec<FileMigration>, String> {
+    let mut migrated = Vec::new();
+    for (path, kind) in config_files(start)? {
+        migrated.extend(migrate_file(&path, kind)?);
+    }
+    for dir in server_config_dirs(start)? {
+        migrated.extend(migrate_legacy_default(&dir)?);
+    }
+    Ok(migrated)
+}
+
+/// Rename the legacy `default` file of the registry `dir` to
+/// `default.remote`, keeping a copy as `default.v1.bak`. If both exist, the
+/// legacy one was already ignored and is only moved to `default.v1.bak`.
+fn migrate_legacy_default(dir: &Path) -> Result<Option<FileMigration>, String> {
+    let legacy = dir.join(LEGACY_DEFAULT_FILE);
+    if !legacy.is_file() {
+        return Ok(None);
+    }
+    let target = dir.join("default.remote");
+    let superseded = target.exists();
+    let backup = backup_path(&legacy, UNVERSIONED);
+    if superseded {
+        fs::rename(&legacy, &backup).map_err(|e| format!("{}: {}", legacy.display(), e))?;
+    } else {
+        fs::copy(&legacy, &backup).map_err(|e| format!("{}: {}", backup.display(), e))?;
+        fs::rename(&legacy, &target).map_err(|e| format!("{}: {}", legacy.display(), e))?;
+    }
+    Ok(Some(legacy_default_migration(
+        legacy,
+        superseded,
+        Some(backup),
+    )))
+}
+
+fn legacy_default_migration(
+    legacy: PathBuf,
+    superseded: bool,
+    backup: Option<PathBuf>,
+) -> FileMigration {
+    let change = if superseded {
+        "superseded by default.remote; set aside".to_string()
+    } else {
+        "renamed to default.remote".to_string()
+    };
+    FileMigration {
+        path: legacy,
+        migrated: Migrated {
+            from: UNVERSIONED,
+            to: SERVER_SCHEMA_VERSION,
+            changes: vec![change],
+        },
+        backup,
+    }
+}
+
+fn config_files(start: &Path) -> Result<Vec<(PathBuf, ConfigFileKind)>, String> {
+    let mut files = Vec::new();
+    let user = config_path();
+    if user.is_file() {
+        files.push((user, ConfigFileKind::User));
+    }
+    for dir in server_config_dirs(start)? {
+        files.extend(
+            server_files(&dir)?
+                .into_iter()
+                .map(|path| (path, ConfigFileKind::Server)),
+        );
+    }
+    Ok(files)
+}
+
+fn read_migrated(path: &Path, kind: ConfigFileKind) -> Result<Option<(Migrated, Value)>, String> {
+    let in_file = |e: String| format!("{}: {}", path.display(), e);
+    let content = fs::read_to_string(path).map_err(|e| in_file(e.to_string()))?;
+    let mut value: Value = serde_yaml::from_str(&content).map_err(|e| in_file(e.to_string()))?;
+    let migrated = migrate_value(&mut value, kind).map_err(in_file)?;
+    Ok((!migrated.is_current()).then_some((migrated, value)))
+}
+
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{}.bak", version));
+    path.with_file_name(name)
+}
+
+/// Rename `from` to `to` in place, unless `to` is already set.
+fn rename_key(config: &mut Mapping, from: &str, to: &str) -> bool {
+    if !config.contains_key(from) || config.contains_key(to) {
+        return false;
+    }
+    *config = std::mem::take(config)
+        .into_iter()
+        .map(|(key, value)| match key.as_str() == Some(from) {
+            true => (to.into(), value),
+            false => (key, value),
+        })
+        .collect();
+    true
+}
+
+/// Version 1 server files could use the `ssh` kind and an `ssh:` block for
+/// what is now the `remote` kind and its `remote:` block.
+fn server_v1_to_v2(config: &mut Mapping) -> Vec<String> {
+    let mut changes = Vec::new();
+    let Some(server) = config.get_mut("server").and_then(Value::as_mapping_mut) else {
+        return changes;
+    };
+    if server.get("kind").and_then(Value::as_str) == Some("ssh") {
+        server.insert("kind".into(), "remote".into());
+        changes.push("server.kind: ssh -> remote".to_string());
+    }
+    if rename_key(server, "ssh", "remote") {
+        changes.push("server.ssh -> server.remote".to_string());
+    }
+    changes
+}
+
+/// Version 1 global configs could name the description `desc`, as
+/// `hymod config set desc` still accepts.
+fn user_v1_to_v2(config: &mut Mapping) -> Vec<String> {
+    let mut changes = Vec::new();
+    if rename_key(config, "desc", "description") {
+        changes.push("desc -> description".to_string());
+    }
+    changes
+}
@@ -0,0 +1,349 @@
+use core_error::HymodError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file at the root of a mod project.
+pub const PROJECT_FILE: &str = "hymod.yaml";
+
+/// The newest `schema_version` this hymod understands.
+pub const PROJECT_SCHEMA_VERSION: u32 = 1;
+
+/// Settings of one mod project, read from its `hymod.yaml`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    #[serde(rename = "mod")]
+    pub mod_info: ModBlock,
+    #[serde(default, skip_serializing_if = "DefaultsBlock::is_empty")]
+    pub defaults: DefaultsBlock,
+    #[serde(default)]
+    pub build: BuildBlock,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ModBlock {
+    pub id: String,
+    pub name: String,
+    /// SemVer version, e.g. `1.2.0` or `2.0.0-beta.1`.
+    pub version: String,
+    /// Fully qualified Java main class, e.g. `com.example.MyMod`.
+    pub entrypoint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DefaultsBlock {
+    /// Server used by `dev`, `deploy` and `link` when none is given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+}
+
+impl DefaultsBlock {
+    fn is_empty(&self) -> bool {
+        self.server.is_none()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BuildBlock {
+    #[serde(default)]
+    pub gradle: GradleBlock,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GradleBlock {
+    #[serde(default = "default_gradle_tasks")]
+    pub tasks: Vec<String>,
+    /// Build artifact relative to the project root, instead of the jar
+    /// found in `build/libs/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_path: Option<String>,
+}
+
+impl Default for GradleBlock {
+    fn default() -> Self {
+        Self {
+            tasks: default_gradle_tasks(),
+            artifact_path: None,
+        }
+    }
+}
+
+fn default_schema_version() -> u32 {
+    PROJECT_SCHEMA_VERSION
+}
+
+fn default_gradle_tasks() -> Vec<String> {
+    vec!["build".to_string()]
+}
+
+impl ProjectConfig {
+    /// Parse and validate the content of a `hymod.yaml` read from `path`.
+    /// Errors name the file and, where known, the line and column at fault.
+    pub fn parse(content: &str, path: &Path) -> Result<Self, HymodError> {
+        let config: ProjectConfig = serde_yaml::from_str(content).map_err(|e| {
+            let reason = match e.location() {
+                Some(location) => {
+                    let message = e.to_string();
+                    // serde_yaml appends " at line L column C"; it is reported up front instead.
+                    let message = match message.rfind(" at line ") {
+                        Some(at) => message[..at].to_string(),
+                        None => message,
+                    };
+                    at_location(Some((location.line(), location.column())), &message)
+                }
+                None => e.to_string(),
+            };
+            HymodError::InvalidConfig {
+                path: path.to_path_buf(),
+                reason,
+            }
+        })?;
+        config.validate(content, path)?;
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, HymodError> {
+        if !path.is_file() {
+            return Err(HymodError::ConfigNotFound {
+                path: path.to_path_buf(),
+            });
+        }
+        let content = fs::read_to_string(path).map_err(|e| HymodError::InvalidConfig {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+        Self::parse(&content, path)
+    }
+
+    fn validate(&self, content: &str, path: &Path) -> Result<(), HymodError> {
+        let invalid = |key: &[&str], message: String| HymodError::InvalidConfig {
+            path: path.to_path_buf(),
+            reason: at_location(locate_key(content, key), &message),
+        };
+
+        if self.schema_version == 0 || self.schema_version > PROJECT_SCHEMA_VERSION {
+            return Err(invalid(
+                &["schema_version"],
+                format!(
+                    "schema_version {} is not supported (this hymod reads up to {})",
+                    self.schema_version, PROJECT_SCHEMA_VERSION
+                ),
+            ));
+        }
+        for (key, value) in [
+            ("id", &self.mod_info.id),
+            ("name", &self.mod_info.name),
+            ("version", &self.mod_info.version),
+            ("entrypoint", &self.mod_info.entrypoint),
+        ] {
+            if value.trim().is_empty() {
+                return Err(invalid(
+                    &["mod", key],
+                    format!("mod.{} must not be empty", key),
+                ));
+            }
+        }
+        if !is_semver(&self.mod_info.version) {
+            return Err(invalid(
+                &["mod", "version"],
+                format!(
+                    "mod.version '{}' is not a SemVer version (expected MAJOR.MINOR.PATCH, e.g. 1.0.0)",
+                    self.mod_info.version
+                ),
+            ));
+        }
+        if !is_java_class_name(&self.mod_info.entrypoint) {
+            return Err(invalid(
+                &["mod", "entrypoint"],
+                format!(
+                    "mod.entrypoint '{}' is not a Java class name (expected e.g. com.example.MyMod)",
+                    self.mod_info.entrypoint
+                ),
+            ));
+        }
+        if self
+            .build
+            .gradle
+            .tasks
+            .iter()
+            .any(|task| task.trim().is_empty())
+        {
+            return Err(invalid(
+                &["build", "gradle", "tasks"],
+                "build.gradle.tasks must not contain empty task names".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The nearest `hymod.yaml` in `start` or one of its parent directories.
+pub fn find_project_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Find the project `start` is in and load its configuration, returning the
+/// project root along with it.
+pub fn discover_project_config(start: &Path) -> Result<(PathBuf, ProjectConfig), HymodError> {
+    let path = find_project_file(start).ok_or_else(|| HymodError::ConfigNotFound {
+        path: start.join(PROJECT_FILE),
+    })?;
+    let config = ProjectConfig::load(&path)?;
+    let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    Ok((root, config))
+}
+
+fn at_location(location: Option<(usize, usize)>, message: &str) -> String {
+    match location {
+        Some((line, column)) => format!("line {}, column {}: {}", line, column, message),
+        None => message.to_string(),
+    }
+}
+
+/// 1-based line and column of the value of the block-style mapping key at
+/// `key` (e.g. `["mod", "version"]`), or of the key itself if its value is
+/// on the following lines.
+fn locate_key(content: &str, key: &[&str]) -> Option<(usize, usize)> {
+    let mut parents: Vec<(usize, &str)> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            continue;
+        }
+        let Some((name, rest)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let indent = line.len() - trimmed.len();
+        while parents.last().is_some_and(|&(parent, _)| parent >= indent) {
+            parents.pop();
+        }
+        parents.push((indent, name.trim().trim_matches(|c| c == '"' || c == '\'')));
+
+        if parents.len() == key.len()
+            && parents
+                .iter()
+                .map(|&(_, name)| name)
+                .eq(key.iter().copied())
+        {
+            let value = rest.trim_start();
+            let column = if value.is_empty() || value.starts_with('#') {
+                indent + 1
+            } else {
+                line.len() - value.len() + 1
+            };
+            return Some((index + 1, column));
+        }
+    }
+    None
+}
+
+/// Whether `version` is `MAJOR.MINOR.PATCH` with optional `-pre.release`
+/// and `+build` parts, as defined by SemVer 2.0.0.
+fn is_semver(version: &str) -> bool {
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    let numeric = |part: &str| {
+        !part.is_empty()
+            && part.bytes().all(|b| b.is_ascii_digit())
+            && (part == "0" || !part.starts_with('0'))
+    };
+    let identifier = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts.iter().all(|part| numeric(part))
+        && pre.is_none_or(|pre| {
+            pre.split('.').all(|part| {
+                identifier(part) && (!part.bytes().all(|b| b.is_ascii_digit()) || numeric(part))
+            })
+        })
+        && build.is_none_or(|build| build.split('.').all(identifier))
+}
+
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "_",
+];
+
+/// Whether `name` is a (possibly package-qualified) Java class name.
+fn is_java_class_name(name: &str) -> bool {
+    name.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+            && !JAVA_KEYWORDS.contains(&part)
+    })
+}
@@ -0,0 +1,288 @@
+use crate::project::{discover_project_config, ProjectConfig};
+use crate::server::{default_server_file, ServerKind};
+use crate::user::{config_path, UserConfig};
+use core_error::HymodError;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the environment variables that override configuration keys,
+/// e.g. `HYMOD_AUTHOR` or `HYMOD_TIMEOUTS_SSH_RUN` for `timeouts.ssh_run`.
+pub const ENV_PREFIX: &str = "HYMOD_";
+
+/// Where a configuration value came from, highest precedence first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigLayer {
+    Cli { flag: String },
+    Project { path: PathBuf },
+    Env { var: String },
+    Global { path: PathBuf },
+    Default,
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigLayer::Cli { flag } => write!(f, "command line ({})", flag),
+            ConfigLayer::Project { path } => write!(f, "project ({})", path.display()),
+            ConfigLayer::Env { var } => write!(f, "environment ({})", var),
+            ConfigLayer::Global { path } => write!(f, "global ({})", path.display()),
+            ConfigLayer::Default => write!(f, "built-in default"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigValue {
+    pub value: String,
+    pub layer: ConfigLayer,
+}
+
+/// Merges command-line flags, the project's `hymod.yaml`, `HYMOD_*`
+/// environment variables and `~/.hymod/config.yaml`, in that order.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigResolver {
+    cli: BTreeMap<String, ConfigValue>,
+    project: Option<(PathBuf, ProjectConfig)>,
+    global: UserConfig,
+}
+
+impl ConfigResolver {
+    /// Resolver for the project `dir` is in, if any, and the global config.
+    /// A `hymod.yaml` that exists but is invalid is an error.
+    pub fn load(dir: &Path) -> Result<Self, HymodError> {
+        let project = match discover_project_config(dir) {
+            Ok((root, config)) => Some((root.join(crate::project::PROJECT_FILE), config)),
+            Err(HymodError::ConfigNotFound { .. }) => None,
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            project,
+            ..Self::global()
+        })
+    }
+
+    /// Resolver without a project layer, for settings a project cannot set.
+    pub fn global() -> Self {
+        Self {
+            cli: BTreeMap::new(),
+            project: None,
+            global: UserConfig::load(),
+        }
+    }
+
+    pub fn with_project(mut self, path: PathBuf, config: ProjectConfig) -> Self {
+        self.project = Some((path, config));
+        self
+    }
+
+    pub fn with_global(mut self, config: UserConfig) -> Self {
+        self.global = config;
+        self
+    }
+
+    /// Add the value of a command-line `flag` for `key`, if it was given.
+    pub fn with_cli(mut self, key: &str, flag: &str, value: Option<&str>) -> Self {
+        if let Some(value) = value {
+            self.cli.insert(
+                canonical_key(key).to_string(),
+                ConfigValue {
+                    value: value.to_string(),
+                    layer: ConfigLayer::Cli {
+                        flag: flag.to_string(),
+                    },
+                },
+            );
+        }
+        self
+    }
+
+    pub fn project(&self) -> Option<&ProjectConfig> {
+        self.project.as_ref().map(|(_, config)| config)
+    }
+
+    /// The value of `key` from the highest layer that sets it.
+    pub fn resolve(&self, key: &str) -> Option<ConfigValue> {
+        self.layers(key).into_iter().next()
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.resolve(key).map(|resolved| resolved.value)
+    }
+
+    /// Every layer that sets `key`, highest precedence first.
+    pub fn layers(&self, key: &str) -> Vec<ConfigValue> {
+        let key = canonical_key(key);
+        let mut layers: Vec<ConfigValue> = self.cli.get(key).cloned().into_iter().collect();
+        if let Some(value) = self.project_value(key) {
+            layers.push(value);
+        }
+        let var = env_var(key);
+        if let Ok(value) = std::env::var(&var) {
+            layers.push(ConfigValue {
+                value,
+                layer: ConfigLayer::Env { var },
+            });
+        }
+        if key == "server" {
+            for kind in [ServerKind::Remote, ServerKind::Local] {
+                layers.extend(global_default_server(&kind));
+            }
+        } else if let Some(value) = self.global.get(key) {
+            layers.push(ConfigValue {
+                value,
+                layer: ConfigLayer::Global {
+                    path: config_path(),
+                },
+            });
+        }
+        if let Some(value) = builtin_default(key) {
+            layers.push(ConfigValue {
+                value: value.to_string(),
+                layer: ConfigLayer::Default,
+            });
+        }
+        layers
+    }
+
+    /// Every key hymod knows, with the `timeouts.<op>` keys that are set.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = KEYS.iter().map(|key| key.to_string()).collect();
+        keys.extend(
+            self.timeouts()
+                .into_keys()
+                .map(|op| format!("timeouts.{}", op)),
+        );
+        keys
+    }
+
+    /// Like `layers`, but rejects keys hymod does not know.
+    pub fn explain(&self, key: &str) -> Result<Vec<ConfigValue>, HymodError> {
+        if !is_known_key(canonical_key(key)) {
+            return Err(HymodError::InvalidArgument(format!(
+                "Unknown config key: {}",
+                key
+            )));
+        }
+        Ok(self.layers(key))
+    }
+
+    /// The server to use when none was named: `--server`, the project's
+    /// `defaults.server`, `HYMOD_SERVER`, then the global default of `kind`.
+    pub fn default_server(&self, kind: &ServerKind) -> Result<String, HymodError> {
+        let layers = self.layers("server");
+        let global = |value: &ConfigValue| matches!(value.layer, ConfigLayer::Global { .. });
+        let chosen = match layers.iter().find(|value| !global(value)) {
+            Some(value) => Some(value.value.clone()),
+            None => crate::server::get_default_server_for_kind(kind).map_err(HymodError::Other)?,
+        };
+        chosen.ok_or_else(|| HymodError::NoDefaultServer {
+            kind: kind.as_str().to_string(),
+        })
+    }
+
+    /// Gradle tasks to build the mod with, `build` unless configured.
+    pub fn gradle_tasks(&self) -> Vec<String> {
+        self.get("build.gradle.tasks")
+            .map(|tasks| tasks.split_whitespace().map(str::to_string).collect())
+            .filter(|tasks: &Vec<String>| !tasks.is_empty())
+            .unwrap_or_else(|| vec!["build".to_string()])
+    }
+
+    /// The build artifact relative to the project root, if configured.
+    pub fn artifact_path(&self) -> Option<String> {
+        self.get("build.gradle.artifact_path")
+    }
+
+    pub fn fsync(&self) -> bool {
+        self.get("fsync").is_some_and(|value| value == "true")
+    }
+
+    /// Per-op default timeouts from the global config and `HYMOD_TIMEOUTS_*`
+    /// variables, the latter taking precedence.
+    pub fn timeouts(&self) -> BTreeMap<String, u64> {
+        let mut timeouts = self.global.timeouts.clone();
+        let prefix = format!("{}TIMEOUTS_", ENV_PREFIX);
+        for (var, value) in std::env::vars() {
+            if let Some(op) = var.strip_prefix(&prefix) {
+                if let Ok(secs) = value.parse() {
+                    timeouts.insert(op.to_lowercase(), secs);
+                }
+            }
+        }
+        timeouts
+    }
+
+    fn project_value(&self, key: &str) -> Option<ConfigValue> {
+        let (path, config) = self.project.as_ref()?;
+        let gradle = &config.build.gradle;
+        let value = match key {
+            "server" => config.defaults.server.clone(),
+            "build.gradle.tasks" => Some(gradle.tasks.join(" ")),
+            "build.gradle.artifact_path" => gradle.artifact_path.clone(),
+            _ => None,
+        }?;
+        Some(ConfigValue {
+            value,
+            layer: ConfigLayer::Project { path: path.clone() },
+        })
+    }
+}
+
+fn canonical_key(key: &str) -> &str {
+    match key {
+        "desc" => "description",
+        "defaults.server" => "server",
+        _ => key,
+    }
+}
+
+/// `HYMOD_` followed by the key in upper case, with `.` and `-` as `_`.
+fn env_var(key: &str) -> String {
+    format!(
+        "{}{}",
+        ENV_PREFIX,
+        key.to_uppercase().replace(['.', '-'], "_")
+    )
+}
+
+fn global_default_server(kind: &ServerKind) -> Option<ConfigValue> {
+    let path = default_server_file(kind).ok()??;
+    let value = std::fs::read_to_string(&path).ok()?.trim().to_string();
+    Some(ConfigValue {
+        value,
+        layer: ConfigLayer::Global { path },
+    })
+}
+
+fn builtin_default(key: &str) -> Option<&'static str> {
+    match key {
+        "author" => Some("Your Name"),
+        "group" => Some("com.example"),
+        "version" => Some("0.1.0"),
+        "description" => Some("A Hytale mod"),
+        "fsync" => Some("false"),
+        "build.gradle.tasks" => Some("build"),
+        _ => None,
+    }
+}
+
+/// The keys besides `timeouts.<op>`, in the order `config list` shows them.
+const KEYS: [&str; 9] = [
+    "author",
+    "username",
+    "group",
+    "version",
+    "description",
+    "fsync",
+    "server",
+    "build.gradle.tasks",
+    "build.gradle.artifact_path",
+];
+
+fn is_known_key(key: &str) -> bool {
+    KEYS.contains(&key)
+        || key
+            .strip_prefix("timeouts.")
+            .is_some_and(|op| !op.is_empty())
+}
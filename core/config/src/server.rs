@@ -1,6 +1,10 @@
+use crate::home::is_hymod_home;
+use crate::migrate::{with_schema_version, LEGACY_DEFAULT_FILE, SERVER_SCHEMA_VERSION};
+use crate::validate::parse_server_config;
+use core_error::HymodError;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ServerConfig {
@@ -18,6 +22,12 @@ pub struct ServerBlock {
     pub remote: Option<RemoteBlock>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub upload: Option<UploadBlock>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryBlock>,
+    /// How a local server's mods are put in place; `None` copies them on
+    /// deploy and symlinks them on `hymod link`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_mode: Option<LinkMode>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -27,6 +37,25 @@ pub enum ServerKind {
     Remote,
 }
 
+impl ServerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServerKind::Local => "local",
+            ServerKind::Remote => "remote",
+        }
+    }
+}
+
+/// Preferred way to link a mod into a local server. When it does not work
+/// on the system, the next one of symlink, hardlink and copy is used.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    Symlink,
+    Hardlink,
+    Copy,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RestartBlock {
     pub cmd: String,
@@ -57,6 +86,29 @@ pub struct UploadBlock {
     pub scp: Option<ScpBlock>,
 }
 
+/// Retry policy for the SSH and upload steps of a remote server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RetryBlock {
+    /// Total number of attempts, including the first one.
+    pub attempts: u32,
+    /// Delay before the second attempt; doubled after every further failure.
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Exit codes worth retrying; empty retries any failure.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retry_on: Vec<i32>,
+}
+
+fn default_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RsyncBlock {
     pub opts: String,
@@ -67,9 +119,201 @@ pub struct ScpBlock {
     pub opts: String,
 }
 
+impl ServerConfig {
+    /// Set the field at the dotted `key` (e.g. `remote.port`, with or without
+    /// a leading `server.`) from its command-line form. An empty value clears
+    /// optional fields.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let server = &mut self.server;
+        let key = key.strip_prefix("server.").unwrap_or(key);
+        let text = || value.to_string();
+        let optional = || (!value.is_empty()).then(text);
+        let number = |what: &str| {
+            value.parse::<u64>().map_err(|_| {
+                format!(
+                    "Invalid value for {}: expected {}, got '{}'",
+                    key, what, value
+                )
+            })
+        };
+
+        match key {
+            "name" => server.name = text(),
+            "kind" => server.kind = parse_choice(key, value, &["local", "remote"])?,
+            "server_root" => server.server_root = text(),
+            "mods_dir" => server.mods_dir = text(),
+            "restart.cmd" => server.restart.cmd = text(),
+            "link_mode" => {
+                server.link_mode = match value {
+                    "" => None,
+                    _ => Some(parse_choice(key, value, &["symlink", "hardlink", "copy"])?),
+                }
+            }
+            "remote.host"
+            | "remote.user"
+            | "remote.port"
+            | "remote.identity_file"
+            | "remote.known_hosts_file" => {
+                let remote = server.remote.get_or_insert_with(|| RemoteBlock {
+                    host: String::new(),
+                    user: String::new(),
+                    port: default_ssh_port(),
+                    identity_file: None,
+                    known_hosts_file: None,
+                });
+                match key {
+                    "remote.host" => remote.host = text(),
+                    "remote.user" => remote.user = text(),
+                    "remote.port" => {
+                        remote.port = number("a port number from 1 to 65535")?
+                            .try_into()
+                            .ok()
+                            .filter(|&port| port != 0)
+                            .ok_or_else(|| {
+                                format!("Invalid value for {}: {} is not a port number", key, value)
+                            })?
+                    }
+                    "remote.identity_file" => remote.identity_file = optional(),
+                    _ => remote.known_hosts_file = optional(),
+                }
+            }
+            "upload.method" => {
+                let method = parse_choice(key, value, &["rsync", "scp"])?;
+                match &mut server.upload {
+                    Some(upload) => upload.method = method,
+                    None => {
+                        server.upload = Some(UploadBlock {
+                            method,
+                            rsync: None,
+                            scp: None,
+                        })
+                    }
+                }
+            }
+            "upload.rsync.opts" | "upload.scp.opts" => {
+                let upload = server
+                    .upload
+                    .as_mut()
+                    .ok_or_else(|| format!("Set upload.method before {}", key))?;
+                if key == "upload.rsync.opts" {
+                    upload.rsync = optional().map(|opts| RsyncBlock { opts });
+                } else {
+                    upload.scp = optional().map(|opts| ScpBlock { opts });
+                }
+            }
+            "retry.attempts" | "retry.backoff_ms" | "retry.max_backoff_ms" | "retry.retry_on" => {
+                let retry = server.retry.get_or_insert_with(|| RetryBlock {
+                    attempts: 1,
+                    backoff_ms: default_backoff_ms(),
+                    max_backoff_ms: default_max_backoff_ms(),
+                    retry_on: Vec::new(),
+                });
+                match key {
+                    "retry.attempts" => {
+                        retry.attempts = number("a number of attempts")?
+                            .try_into()
+                            .map_err(|_| format!("Invalid value for {}: too large", key))?
+                    }
+                    "retry.backoff_ms" => retry.backoff_ms = number("milliseconds")?,
+                    "retry.max_backoff_ms" => retry.max_backoff_ms = number("milliseconds")?,
+                    _ => {
+                        retry.retry_on = value
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|code| !code.is_empty())
+                            .map(|code| {
+                                code.parse().map_err(|_| {
+                                    format!(
+                                        "Invalid value for {}: expected comma-separated exit codes, got '{}'",
+                                        key, value
+                                    )
+                                })
+                            })
+                            .collect::<Result<_, _>>()?
+                    }
+                }
+            }
+            _ => return Err(format!("Unknown server key: {}", key)),
+        }
+        Ok(())
+    }
+}
+
+/// Parse `value` as the serde form of an enum or one of the string `choices`.
+fn parse_choice<T: serde::de::DeserializeOwned>(
+    key: &str,
+    value: &str,
+    choices: &[&str],
+) -> Result<T, String> {
+    let invalid = || {
+        format!(
+            "Invalid value for {}: expected one of {}, got '{}'",
+            key,
+            choices.join(", "),
+            value
+        )
+    };
+    if !choices.contains(&value) {
+        return Err(invalid());
+    }
+    serde_yaml::from_value(serde_yaml::Value::String(value.to_string())).map_err(|_| invalid())
+}
+
+/// The user's server registry, `$HYMOD_HOME/servers.d`.
 pub fn get_server_config_dir() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    Ok(home.join(".hymod").join("servers.d"))
+    Ok(crate::home::hymod_home()?.join("servers.d"))
+}
+
+/// A project's own server registry: `.hymod/servers.d` next to the
+/// `hymod.yaml` of the project `start` is in. A hymod home is never taken
+/// for a project, so `~/.hymod` above a project cannot shadow `$HYMOD_HOME`.
+pub fn find_project_server_config_dir(start: &Path) -> Option<PathBuf> {
+    let root = crate::project::find_project_file(start)?
+        .parent()?
+        .join(".hymod");
+    if is_hymod_home(&root) {
+        return None;
+    }
+    Some(root.join("servers.d")).filter(|dir| dir.is_dir())
+}
+
+/// Registries servers are looked up in, highest precedence first: the
+/// registry of the project `start` is in, then the user's.
+pub fn server_config_dirs(start: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut dirs = Vec::new();
+    if let Some(project) = find_project_server_config_dir(start) {
+        dirs.push(project);
+    }
+    dirs.push(get_server_config_dir()?);
+    Ok(dirs)
+}
+
+/// The file defining server `name` in the first registry, as seen from
+/// `start`, that has one.
+pub fn find_server_config_path(name: &str, start: &Path) -> Result<Option<PathBuf>, String> {
+    Ok(server_config_dirs(start)?
+        .into_iter()
+        .map(|dir| dir.join(format!("{}.yaml", name)))
+        .find(|path| path.exists()))
+}
+
+/// Like `find_server_config_path`, but a missing server is a
+/// `ServerNotFound` naming the registries that were searched.
+pub fn server_config_path(name: &str, start: &Path) -> Result<PathBuf, HymodError> {
+    let searched = server_config_dirs(start).map_err(HymodError::Other)?;
+    searched
+        .iter()
+        .map(|dir| dir.join(format!("{}.yaml", name)))
+        .find(|path| path.exists())
+        .ok_or_else(|| HymodError::ServerNotFound {
+            name: name.to_string(),
+            searched,
+        })
+}
+
+/// Where server `name` is saved in the user's registry.
+pub fn user_server_config_path(name: &str) -> Result<PathBuf, String> {
+    Ok(get_server_config_dir()?.join(format!("{}.yaml", name)))
 }
 
 pub fn save_server_config(config: &ServerConfig) -> Result<(), String> {
@@ -78,64 +322,120 @@ pub fn save_server_config(config: &ServerConfig) -> Result<(), String> {
         fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     }
 
-    let filename = format!("{}.yaml", config.server.name);
-    let path = dir.join(filename);
+    write_server_config(&user_server_config_path(&config.server.name)?, config)
+}
+
+/// The content of a server file for `config`.
+pub fn server_config_to_string(config: &ServerConfig) -> Result<String, String> {
+    let value = serde_yaml::to_value(config).map_err(|e| e.to_string())?;
+    serde_yaml::to_string(&with_schema_version(value, SERVER_SCHEMA_VERSION))
+        .map_err(|e| e.to_string())
+}
 
-    let content = serde_yaml::to_string(config).map_err(|e| e.to_string())?;
+/// Write `config` to `path`, which may be in any registry.
+pub fn write_server_config(path: &Path, config: &ServerConfig) -> Result<(), String> {
+    let content = server_config_to_string(config)?;
     fs::write(path, content).map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
-pub fn load_server_config(name: &str) -> Result<ServerConfig, String> {
-    let dir = get_server_config_dir()?;
-    let path = dir.join(format!("{}.yaml", name));
-
-    if !path.exists() {
+pub fn load_server_config(name: &str, start: &Path) -> Result<ServerConfig, String> {
+    let Some(path) = find_server_config_path(name, start)? else {
         return Err(format!("Server configuration '{}' not found", name));
-    }
+    };
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    parse_server_config(&content, &path).map_err(|problems| problems.join("; "))
+}
 
-    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let config: ServerConfig = serde_yaml::from_str(&content).map_err(|e| e.to_string())?;
+/// Like `load_server_config`, but tells a missing server apart from a broken one.
+pub fn find_server_config(name: &str, start: &Path) -> Result<ServerConfig, HymodError> {
+    let path = server_config_path(name, start)?;
 
+    let invalid = |reason: String| HymodError::InvalidConfig {
+        path: path.clone(),
+        reason,
+    };
+    let content = fs::read_to_string(&path).map_err(|e| invalid(e.to_string()))?;
+    parse_server_config(&content, &path).map_err(|problems| invalid(problems.join("; ")))
+}
+
+/// Load a server and check that it is of the `expected` kind.
+pub fn find_server_config_of_kind(
+    name: &str,
+    expected: &ServerKind,
+    start: &Path,
+) -> Result<ServerConfig, HymodError> {
+    let config = find_server_config(name, start)?;
+    if config.server.kind != *expected {
+        return Err(HymodError::ServerKindMismatch {
+            name: name.to_string(),
+            actual: config.server.kind.as_str().to_string(),
+            expected: expected.as_str().to_string(),
+        });
+    }
     Ok(config)
 }
 
-pub fn remove_server_config(name: &str) -> Result<(), String> {
-    let dir = get_server_config_dir()?;
-    let path = dir.join(format!("{}.yaml", name));
+/// The file to delete to remove server `name` from the user's registry.
+/// Servers defined by the project registry belong to the project and are
+/// left alone.
+pub fn removable_server_config_path(name: &str, start: &Path) -> Result<PathBuf, String> {
+    let path = user_server_config_path(name)?;
 
     if !path.exists() {
-        return Err(format!("Server configuration '{}' not found", name));
+        return match find_server_config_path(name, start)? {
+            Some(project) => Err(format!(
+                "Server configuration '{}' is defined by the project in {}; remove it there",
+                name,
+                project.display()
+            )),
+            None => Err(format!("Server configuration '{}' not found", name)),
+        };
     }
+    Ok(path)
+}
 
+/// Remove server `name` from the user's registry; see `removable_server_config_path`.
+pub fn remove_server_config(name: &str, start: &Path) -> Result<(), String> {
+    let path = removable_server_config_path(name, start)?;
     fs::remove_file(path).map_err(|e| e.to_string())?;
     Ok(())
 }
 
-pub fn list_servers() -> Result<Vec<String>, String> {
-    let dir = get_server_config_dir()?;
-    if !dir.exists() {
-        return Ok(Vec::new());
-    }
-
+/// Names of the servers in every registry, as seen from `start`, each
+/// listed once.
+pub fn list_servers(start: &Path) -> Result<Vec<String>, String> {
     let mut servers = Vec::new();
-    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
+    for dir in server_config_dirs(start)? {
+        for path in server_files(&dir)? {
             if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                if stem != "default" {
-                    // Exclude "default" file if it ends up here (it shouldn't as it has no extension in my code)
-                    servers.push(stem.to_string());
-                }
+                servers.push(stem.to_string());
             }
         }
     }
     servers.sort();
+    servers.dedup();
     Ok(servers)
 }
 
+/// The server files of the registry `dir`, sorted by path.
+pub(crate) fn server_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut paths = Vec::new();
+    if !dir.exists() {
+        return Ok(paths);
+    }
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
 pub fn get_default_server() -> Result<Option<String>, String> {
     get_default_server_for_kind(&ServerKind::Remote)
 }
@@ -145,23 +445,31 @@ pub fn set_default_server(name: &str) -> Result<(), String> {
 }
 
 pub fn get_default_server_for_kind(kind: &ServerKind) -> Result<Option<String>, String> {
+    match default_server_file(kind)? {
+        Some(path) => {
+            let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+            Ok(Some(content.trim().to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// The file holding the default server of `kind`, if one is set. A legacy
+/// `default` file still names the remote default until `hymod config
+/// migrate` renames it.
+pub(crate) fn default_server_file(kind: &ServerKind) -> Result<Option<PathBuf>, String> {
     let dir = get_server_config_dir()?;
     let path = dir.join(default_filename_for_kind(kind));
     if path.exists() {
-        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
-        return Ok(Some(content.trim().to_string()));
-    }
-
-    // Backward compatibility: if only legacy default exists, use it as remote default.
-    if *kind == ServerKind::Remote {
-        let legacy_path = dir.join("default");
-        if legacy_path.exists() {
-            let content = fs::read_to_string(legacy_path).map_err(|e| e.to_string())?;
-            return Ok(Some(content.trim().to_string()));
-        }
+        return Ok(Some(path));
     }
+    let legacy = dir.join(LEGACY_DEFAULT_FILE);
+    Ok((*kind == ServerKind::Remote && legacy.is_file()).then_some(legacy))
+}
 
-    Ok(None)
+/// Where `set_default_server_for_kind` records the default server of `kind`.
+pub fn default_server_path(kind: &ServerKind) -> Result<PathBuf, String> {
+    Ok(get_server_config_dir()?.join(default_filename_for_kind(kind)))
 }
 
 pub fn set_default_server_for_kind(kind: &ServerKind, name: &str) -> Result<(), String> {
@@ -169,8 +477,7 @@ pub fn set_default_server_for_kind(kind: &ServerKind, name: &str) -> Result<(),
     if !dir.exists() {
         fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     }
-    let path = dir.join(default_filename_for_kind(kind));
-    fs::write(path, name).map_err(|e| e.to_string())?;
+    fs::write(default_server_path(kind)?, name).map_err(|e| e.to_string())?;
     Ok(())
 }
 
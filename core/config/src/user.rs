@@ -1,4 +1,6 @@
+use crate::migrate::{migrate_value, with_schema_version, ConfigFileKind, USER_SCHEMA_VERSION};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -10,6 +12,13 @@ pub struct UserConfig {
     #[serde(rename = "description")]
     pub desc: Option<String>,
     pub username: Option<String>,
+    /// Default timeout in seconds per step op (e.g. `ssh_run: 60`), for steps
+    /// that do not set their own `timeout_secs`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub timeouts: BTreeMap<String, u64>,
+    /// Flush copied and written files to disk before renaming them into place.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fsync: bool,
 }
 
 impl UserConfig {
@@ -17,8 +26,12 @@ impl UserConfig {
         let path = config_path();
         if path.exists() {
             if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(cfg) = serde_yaml::from_str(&content) {
-                    return cfg;
+                if let Ok(mut value) = serde_yaml::from_str(&content) {
+                    if migrate_value(&mut value, ConfigFileKind::User).is_ok() {
+                        if let Ok(cfg) = serde_yaml::from_value(value) {
+                            return cfg;
+                        }
+                    }
                 }
             }
         }
@@ -30,11 +43,17 @@ impl UserConfig {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).map_err(|e| e.to_string())?;
         }
-        let content = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
-        fs::write(path, content).map_err(|e| e.to_string())?;
+        fs::write(path, self.to_yaml()?).map_err(|e| e.to_string())?;
         Ok(())
     }
 
+    /// The content `save` writes.
+    pub fn to_yaml(&self) -> Result<String, String> {
+        let value = serde_yaml::to_value(self).map_err(|e| e.to_string())?;
+        serde_yaml::to_string(&with_schema_version(value, USER_SCHEMA_VERSION))
+            .map_err(|e| e.to_string())
+    }
+
     pub fn set(&mut self, key: &str, value: String) -> Result<(), String> {
         match key {
             "author" => self.author = Some(value),
@@ -42,7 +61,26 @@ impl UserConfig {
             "version" => self.version = Some(value),
             "desc" | "description" => self.desc = Some(value),
             "username" => self.username = Some(value),
-            _ => return Err(format!("Unknown config key: {}", key)),
+            "fsync" => {
+                self.fsync = value.parse().map_err(|_| {
+                    format!(
+                        "Invalid value for fsync: expected true or false, got '{}'",
+                        value
+                    )
+                })?
+            }
+            _ => match key.strip_prefix("timeouts.") {
+                Some(op) if !op.is_empty() => {
+                    let secs = value.parse().map_err(|_| {
+                        format!(
+                            "Invalid timeout for {}: expected seconds, got '{}'",
+                            op, value
+                        )
+                    })?;
+                    self.timeouts.insert(op.to_string(), secs);
+                }
+                _ => return Err(format!("Unknown config key: {}", key)),
+            },
         }
         Ok(())
     }
@@ -54,14 +92,17 @@ impl UserConfig {
             "version" => self.version.clone(),
             "desc" | "description" => self.desc.clone(),
             "username" => self.username.clone(),
-            _ => None,
+            "fsync" => Some(self.fsync.to_string()),
+            _ => key
+                .strip_prefix("timeouts.")
+                .and_then(|op| self.timeouts.get(op))
+                .map(|secs| secs.to_string()),
         }
     }
 }
 
 pub fn config_path() -> PathBuf {
-    let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    path.push(".hymod");
-    path.push("config.yaml");
-    path
+    crate::home::hymod_home()
+        .unwrap_or_else(|_| PathBuf::from(".hymod"))
+        .join("config.yaml")
 }
@@ -0,0 +1,208 @@
+use crate::migrate::{migrate_value, ConfigFileKind};
+use crate::server::{server_config_dirs, server_files, ServerConfig};
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const UPLOAD_METHODS: &[&str] = &["rsync", "scp"];
+const LINK_MODES: &[&str] = &["symlink", "hardlink", "copy"];
+
+/// Everything wrong with one server file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerFileReport {
+    pub path: PathBuf,
+    pub problems: Vec<String>,
+}
+
+impl ServerFileReport {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for ServerFileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the server file at `path`, upgrading it to the current schema in
+/// memory, and check it, returning every problem found rather than stopping
+/// at the first.
+pub fn parse_server_config(content: &str, path: &Path) -> Result<ServerConfig, Vec<String>> {
+    let mut value: Value = serde_yaml::from_str(content).map_err(|e| vec![e.to_string()])?;
+    migrate_value(&mut value, ConfigFileKind::Server).map_err(|e| vec![e])?;
+    let file_name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string());
+
+    let problems = check_server_value(&value, file_name.as_deref());
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+    // Anything the checks above do not cover, like a wrongly typed optional field.
+    serde_yaml::from_value(value).map_err(|e| vec![e.to_string()])
+}
+
+/// Check the server file at `path`.
+pub fn validate_server_file(path: &Path) -> ServerFileReport {
+    let problems = match fs::read_to_string(path) {
+        Ok(content) => parse_server_config(&content, path)
+            .err()
+            .unwrap_or_default(),
+        Err(e) => vec![e.to_string()],
+    };
+    ServerFileReport {
+        path: path.to_path_buf(),
+        problems,
+    }
+}
+
+/// Check every server file of every registry as seen from `start`,
+/// including ones shadowed by a same-named server in a registry of higher
+/// precedence.
+pub fn validate_all_servers(start: &Path) -> Result<Vec<ServerFileReport>, String> {
+    let mut reports = Vec::new();
+    for dir in server_config_dirs(start)? {
+        reports.extend(
+            server_files(&dir)?
+                .iter()
+                .map(|path| validate_server_file(path)),
+        );
+    }
+    Ok(reports)
+}
+
+fn check_server_value(value: &Value, file_name: Option<&str>) -> Vec<String> {
+    let mut problems = Vec::new();
+    let Some(server) = value.get("server").and_then(Value::as_mapping) else {
+        return vec!["server: missing `server` block".to_string()];
+    };
+
+    let name = required_str(server, "server", "name", &mut problems);
+    if let (Some(name), Some(file_name)) = (name, file_name) {
+        if name != file_name {
+            problems.push(format!(
+                "server.name: '{}' does not match the file name '{}.yaml'",
+                name, file_name
+            ));
+        }
+    }
+    let kind = required_str(server, "server", "kind", &mut problems);
+    if kind.is_some_and(|kind| !matches!(kind, "local" | "remote")) {
+        problems.push(format!(
+            "server.kind: '{}' is not one of local, remote",
+            kind.unwrap_or_default()
+        ));
+    }
+    required_str(server, "server", "server_root", &mut problems);
+    required_str(server, "server", "mods_dir", &mut problems);
+    match server.get("restart").and_then(Value::as_mapping) {
+        Some(restart) => {
+            required_str(restart, "server.restart", "cmd", &mut problems);
+        }
+        None => problems.push("server.restart: missing `restart` block".to_string()),
+    }
+
+    let remote = server.get("remote").and_then(Value::as_mapping);
+    if kind == Some("remote") {
+        match remote {
+            Some(remote) => {
+                required_str(remote, "server.remote", "host", &mut problems);
+                required_str(remote, "server.remote", "user", &mut problems);
+            }
+            None => problems.push(
+                "server.remote: remote servers need a `remote` block with host and user"
+                    .to_string(),
+            ),
+        }
+    }
+    if let Some(port) = remote.and_then(|remote| remote.get("port")) {
+        if !port
+            .as_u64()
+            .is_some_and(|port| (1..=65535).contains(&port))
+        {
+            problems.push(format!(
+                "server.remote.port: {} is not a port number",
+                describe(port)
+            ));
+        }
+    }
+
+    if let Some(upload) = server.get("upload").and_then(Value::as_mapping) {
+        if let Some(method) = required_str(upload, "server.upload", "method", &mut problems) {
+            if !UPLOAD_METHODS.contains(&method) {
+                problems.push(format!(
+                    "server.upload.method: '{}' is not one of {}",
+                    method,
+                    UPLOAD_METHODS.join(", ")
+                ));
+            }
+        }
+    }
+    if let Some(attempts) = server.get("retry").and_then(|retry| retry.get("attempts")) {
+        if !matches!(attempts.as_u64(), Some(1..)) {
+            problems.push(format!(
+                "server.retry.attempts: {} must be at least 1",
+                describe(attempts)
+            ));
+        }
+    }
+    if let Some(mode) = server.get("link_mode") {
+        if !mode.as_str().is_some_and(|mode| LINK_MODES.contains(&mode)) {
+            problems.push(format!(
+                "server.link_mode: {} is not one of {}",
+                describe(mode),
+                LINK_MODES.join(", ")
+            ));
+        }
+    }
+    problems
+}
+
+/// The non-empty string at `parent.key`, recording a problem if it is
+/// missing, empty or not a string.
+fn required_str<'a>(
+    parent: &'a Mapping,
+    prefix: &str,
+    key: &str,
+    problems: &mut Vec<String>,
+) -> Option<&'a str> {
+    match parent.get(key) {
+        None | Some(Value::Null) => {
+            problems.push(format!("{}.{}: missing", prefix, key));
+            None
+        }
+        Some(Value::String(value)) if value.trim().is_empty() => {
+            problems.push(format!("{}.{}: must not be empty", prefix, key));
+            None
+        }
+        Some(Value::String(value)) => Some(value),
+        Some(other) => {
+            problems.push(format!(
+                "{}.{}: expected a string, found {}",
+                prefix,
+                key,
+                describe(other)
+            ));
+            None
+        }
+    }
+}
+
+pub(crate) fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => format!("'{}'", s),
+        Value::Sequence(_) => "a list".to_string(),
+        Value::Mapping(_) => "a mapping".to_string(),
+        Value::Tagged(_) => "a tagged value".to_string(),
+    }
+}
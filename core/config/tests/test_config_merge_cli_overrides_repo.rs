@@ -1,6 +1,34 @@
+use core_config::{ConfigLayer, ConfigResolver, ProjectConfig, ServerKind};
+use std::path::{Path, PathBuf};
+
 #[test]
 fn test_config_merge_cli_overrides_repo() {
-    panic!(
-        "Must use CLI --server flag value when both CLI and hymod.yaml defaults.server are set."
+    let project = ProjectConfig::parse(
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\ndefaults:\n  server: repo-server\n",
+        Path::new("hymod.yaml"),
+    )
+    .unwrap();
+
+    let config = ConfigResolver::default()
+        .with_project(PathBuf::from("/project/hymod.yaml"), project)
+        .with_cli("server", "--server", Some("cli-server"));
+
+    assert_eq!(
+        config.default_server(&ServerKind::Remote).unwrap(),
+        "cli-server"
+    );
+    let layers = config.layers("server");
+    assert_eq!(
+        layers[0].layer,
+        ConfigLayer::Cli {
+            flag: "--server".to_string()
+        }
+    );
+    assert_eq!(layers[1].value, "repo-server");
+    assert_eq!(
+        layers[1].layer,
+        ConfigLayer::Project {
+            path: PathBuf::from("/project/hymod.yaml")
+        }
     );
 }
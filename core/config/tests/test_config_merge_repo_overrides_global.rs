@@ -1,4 +1,39 @@
+use core_config::{
+    set_default_server_for_kind, ConfigLayer, ConfigResolver, ProjectConfig, ServerKind, UserConfig,
+};
+use std::path::{Path, PathBuf};
+
 #[test]
 fn test_config_merge_repo_overrides_global() {
-    panic!("Must use hymod.yaml defaults.server when no CLI flag and global default exists.");
+    let home = tempfile::tempdir().expect("failed to create home dir");
+    std::env::set_var("HOME", home.path());
+    set_default_server_for_kind(&ServerKind::Remote, "global-server").unwrap();
+
+    let project = ProjectConfig::parse(
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\ndefaults:\n  server: repo-server\n",
+        Path::new("hymod.yaml"),
+    )
+    .unwrap();
+    let config =
+        ConfigResolver::default().with_project(PathBuf::from("/project/hymod.yaml"), project);
+
+    assert_eq!(
+        config.default_server(&ServerKind::Remote).unwrap(),
+        "repo-server"
+    );
+    let global = config
+        .layers("server")
+        .into_iter()
+        .find(|value| matches!(value.layer, ConfigLayer::Global { .. }))
+        .expect("global default is listed");
+    assert_eq!(global.value, "global-server");
+
+    // Keys a project cannot set come from the global config, then the built-in default.
+    let config = ConfigResolver::default().with_global(UserConfig {
+        author: Some("Global Author".to_string()),
+        ..UserConfig::default()
+    });
+    assert_eq!(config.get("author").as_deref(), Some("Global Author"));
+    assert_eq!(config.get("group").as_deref(), Some("com.example"));
+    assert_eq!(config.resolve("group").unwrap().layer, ConfigLayer::Default);
 }
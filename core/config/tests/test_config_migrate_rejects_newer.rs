@@ -0,0 +1,32 @@
+use core_config::{migrate_value, ConfigFileKind, USER_SCHEMA_VERSION};
+use serde_yaml::Value;
+
+#[test]
+fn test_config_migrate_rejects_newer() {
+    let mut value: Value =
+        serde_yaml::from_str(&format!("schema_version: {}\n", USER_SCHEMA_VERSION + 1)).unwrap();
+
+    let error = migrate_value(&mut value, ConfigFileKind::User).unwrap_err();
+
+    assert!(
+        error.contains("newer than this hymod supports"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_config_migrate_user_v1_renames_desc() {
+    let mut value: Value = serde_yaml::from_str("author: Me\ndesc: My mods\n").unwrap();
+
+    let migrated = migrate_value(&mut value, ConfigFileKind::User).unwrap();
+
+    assert_eq!(migrated.changes, vec!["desc -> description".to_string()]);
+    assert_eq!(
+        serde_yaml::to_string(&value).unwrap(),
+        format!(
+            "schema_version: {}\nauthor: Me\ndescription: My mods\n",
+            USER_SCHEMA_VERSION
+        )
+    );
+}
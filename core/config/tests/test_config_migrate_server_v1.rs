@@ -0,0 +1,55 @@
+use core_config::{migrate_file, parse_server_config, ConfigFileKind, SERVER_SCHEMA_VERSION};
+use std::fs;
+use tempfile::TempDir;
+
+const V1_SSH_SERVER: &str = "\
+server:
+  name: prod
+  kind: ssh
+  server_root: /opt/hytale
+  mods_dir: mods
+  restart:
+    cmd: systemctl restart hytale
+  ssh:
+    host: example.com
+    user: deploy
+";
+
+#[test]
+fn test_config_migrate_server_v1() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("prod.yaml");
+    fs::write(&path, V1_SSH_SERVER).unwrap();
+
+    let migration = migrate_file(&path, ConfigFileKind::Server)
+        .unwrap()
+        .expect("a v1 file needs migrating");
+
+    assert_eq!(migration.migrated.from, 1);
+    assert_eq!(migration.migrated.to, SERVER_SCHEMA_VERSION);
+    assert_eq!(
+        migration.migrated.changes,
+        vec![
+            "server.kind: ssh -> remote".to_string(),
+            "server.ssh -> server.remote".to_string(),
+        ]
+    );
+    let backup = dir.path().join("prod.yaml.v1.bak");
+    assert_eq!(migration.backup.as_deref(), Some(backup.as_path()));
+    assert_eq!(fs::read_to_string(&backup).unwrap(), V1_SSH_SERVER);
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("schema_version: 2\n"));
+    let config = parse_server_config(&content, &path).unwrap();
+    assert_eq!(config.server.remote.unwrap().host, "example.com");
+
+    // Migrating again is a no-op.
+    assert_eq!(migrate_file(&path, ConfigFileKind::Server).unwrap(), None);
+}
+
+#[test]
+fn test_config_migrate_server_v1_in_memory_on_load() {
+    let config = parse_server_config(V1_SSH_SERVER, "prod.yaml".as_ref()).unwrap();
+
+    assert_eq!(config.server.remote.unwrap().user, "deploy");
+}
@@ -1,6 +1,39 @@
+use core_config::{set_default_server_for_kind, ConfigResolver, ServerKind};
+use core_error::HymodError;
+use std::fs;
+
 #[test]
 fn test_default_server_resolution() {
-    panic!(
-        "Must resolve default server from hymod.yaml defaults.server when --server flag omitted."
+    let home = tempfile::tempdir().expect("failed to create home dir");
+    std::env::set_var("HOME", home.path());
+
+    let project = tempfile::tempdir().unwrap();
+    fs::write(
+        project.path().join("hymod.yaml"),
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\ndefaults:\n  server: local-dev\n",
+    )
+    .unwrap();
+    let nested = project.path().join("src");
+    fs::create_dir(&nested).unwrap();
+
+    let config = ConfigResolver::load(&nested).unwrap();
+    assert_eq!(
+        config.default_server(&ServerKind::Local).unwrap(),
+        "local-dev"
+    );
+
+    // Outside a project the global default of the requested kind is used.
+    let elsewhere = tempfile::tempdir().unwrap();
+    let config = ConfigResolver::load(elsewhere.path()).unwrap();
+    assert_eq!(
+        config.default_server(&ServerKind::Local).unwrap_err(),
+        HymodError::NoDefaultServer {
+            kind: "local".to_string()
+        }
+    );
+    set_default_server_for_kind(&ServerKind::Local, "global-local").unwrap();
+    assert_eq!(
+        config.default_server(&ServerKind::Local).unwrap(),
+        "global-local"
     );
 }
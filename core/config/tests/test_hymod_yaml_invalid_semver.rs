@@ -1,4 +1,41 @@
+use core_config::ProjectConfig;
+use core_error::HymodError;
+use std::path::Path;
+
+fn parse_version(version: &str) -> Result<ProjectConfig, HymodError> {
+    let content = format!(
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: {}\n  entrypoint: com.example.MyMod\n",
+        version
+    );
+    ProjectConfig::parse(&content, Path::new("/project/hymod.yaml"))
+}
+
 #[test]
 fn test_hymod_yaml_invalid_semver() {
-    panic!("Must return Error when version is not valid SemVer format (e.g., '1.0' or 'invalid').");
+    for version in ["'1.0'", "invalid", "01.0.0", "1.0.0-", "1.0.0-beta..1"] {
+        let err = parse_version(version).expect_err(version);
+        let HymodError::InvalidConfig { path, reason } = err else {
+            panic!("expected InvalidConfig for {}", version);
+        };
+        assert_eq!(path, Path::new("/project/hymod.yaml"));
+        assert!(reason.starts_with("line 4, column 12: "), "{}", reason);
+        assert!(reason.contains("not a SemVer version"), "{}", reason);
+    }
+
+    for version in ["0.1.0", "1.0.0-rc.1", "1.0.0+20240101", "10.20.30-alpha-1"] {
+        parse_version(version).expect(version);
+    }
+}
+
+#[test]
+fn test_hymod_yaml_invalid_entrypoint() {
+    let content =
+        "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.class.1Main\n";
+
+    let err = ProjectConfig::parse(content, Path::new("hymod.yaml")).unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("hymod.yaml"), "{}", message);
+    assert!(message.contains("line 5, column 15"), "{}", message);
+    assert!(message.contains("not a Java class name"), "{}", message);
 }
@@ -1,4 +1,44 @@
+use core_config::ProjectConfig;
+use core_error::HymodError;
+use std::path::Path;
+
 #[test]
 fn test_hymod_yaml_missing_required_field() {
-    panic!("Must return Error when mod.id, mod.version, or mod.entrypoint is missing.");
+    let fields = [
+        ("id", "  id: my-mod\n"),
+        ("version", "  version: 1.0.0\n"),
+        ("entrypoint", "  entrypoint: com.example.MyMod\n"),
+    ];
+    for (missing, _) in fields {
+        let mut content = "mod:\n  name: My Mod\n".to_string();
+        for (field, line) in fields {
+            if field != missing {
+                content.push_str(line);
+            }
+        }
+
+        let err = ProjectConfig::parse(&content, Path::new("hymod.yaml")).unwrap_err();
+
+        let HymodError::InvalidConfig { reason, .. } = err else {
+            panic!("expected InvalidConfig when {} is missing", missing);
+        };
+        assert!(reason.starts_with("line "), "{}", reason);
+        assert!(
+            reason.contains(&format!("missing field `{}`", missing)),
+            "{}",
+            reason
+        );
+    }
+}
+
+#[test]
+fn test_hymod_yaml_empty_required_field() {
+    let content =
+        "mod:\n  id: ''\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\n";
+
+    let err = ProjectConfig::parse(content, Path::new("hymod.yaml")).unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains("line 2, column 7: mod.id must not be empty"));
 }
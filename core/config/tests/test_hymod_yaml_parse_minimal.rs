@@ -1,4 +1,24 @@
+use core_config::{ProjectConfig, PROJECT_SCHEMA_VERSION};
+use std::path::Path;
+
 #[test]
 fn test_hymod_yaml_parse_minimal() {
-    panic!("Must parse hymod.yaml with only required fields: id, name, version, entrypoint.");
+    let content = "\
+mod:
+  id: my-mod
+  name: My Mod
+  version: 1.0.0
+  entrypoint: com.example.MyMod
+";
+
+    let config = ProjectConfig::parse(content, Path::new("hymod.yaml")).unwrap();
+
+    assert_eq!(config.schema_version, PROJECT_SCHEMA_VERSION);
+    assert_eq!(config.mod_info.id, "my-mod");
+    assert_eq!(config.mod_info.name, "My Mod");
+    assert_eq!(config.mod_info.version, "1.0.0");
+    assert_eq!(config.mod_info.entrypoint, "com.example.MyMod");
+    assert_eq!(config.defaults.server, None);
+    assert_eq!(config.build.gradle.tasks, vec!["build".to_string()]);
+    assert_eq!(config.build.gradle.artifact_path, None);
 }
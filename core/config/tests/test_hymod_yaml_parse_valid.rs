@@ -1,4 +1,52 @@
+use core_config::{discover_project_config, ProjectConfig};
+use std::fs;
+
+const FULL: &str = "\
+schema_version: 1
+mod:
+  id: my-mod
+  name: My Mod
+  version: 2.1.0-beta.1+build.7
+  entrypoint: com.example.my_mod.MyMod
+defaults:
+  server: local-dev
+build:
+  gradle:
+    tasks: [clean, shadowJar]
+    artifact_path: build/libs/my-mod-all.jar
+";
+
 #[test]
 fn test_hymod_yaml_parse_valid() {
-    panic!("Must parse valid hymod.yaml with all fields: id, name, version, entrypoint, defaults.server, build config.");
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hymod.yaml");
+    fs::write(&path, FULL).unwrap();
+
+    let config = ProjectConfig::load(&path).unwrap();
+
+    assert_eq!(config.schema_version, 1);
+    assert_eq!(config.mod_info.version, "2.1.0-beta.1+build.7");
+    assert_eq!(config.mod_info.entrypoint, "com.example.my_mod.MyMod");
+    assert_eq!(config.defaults.server.as_deref(), Some("local-dev"));
+    assert_eq!(
+        config.build.gradle.tasks,
+        vec!["clean".to_string(), "shadowJar".to_string()]
+    );
+    assert_eq!(
+        config.build.gradle.artifact_path.as_deref(),
+        Some("build/libs/my-mod-all.jar")
+    );
+}
+
+#[test]
+fn test_hymod_yaml_discovered_from_subdirectory() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("hymod.yaml"), FULL).unwrap();
+    let nested = dir.path().join("src").join("main").join("java");
+    fs::create_dir_all(&nested).unwrap();
+
+    let (root, config) = discover_project_config(&nested).unwrap();
+
+    assert_eq!(root, dir.path());
+    assert_eq!(config.mod_info.id, "my-mod");
 }
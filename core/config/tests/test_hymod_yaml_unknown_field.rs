@@ -0,0 +1,17 @@
+use core_config::ProjectConfig;
+use core_error::HymodError;
+use std::path::Path;
+
+#[test]
+fn test_hymod_yaml_unknown_field() {
+    // A misspelled key would otherwise be ignored and its setting silently lost.
+    let content = "mod:\n  id: my-mod\n  name: My Mod\n  version: 1.0.0\n  entrypoint: com.example.MyMod\nbuild:\n  gradle:\n    task: [shadowJar]\n";
+
+    let err = ProjectConfig::parse(content, Path::new("hymod.yaml")).unwrap_err();
+
+    let HymodError::InvalidConfig { reason, .. } = err else {
+        panic!("expected InvalidConfig for an unknown key");
+    };
+    assert!(reason.starts_with("line 8, column 5: "), "{}", reason);
+    assert!(reason.contains("unknown field `task`"), "{}", reason);
+}
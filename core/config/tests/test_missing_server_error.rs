@@ -1,4 +1,20 @@
+use core_config::find_server_config;
+use core_error::HymodError;
+
 #[test]
 fn test_missing_server_error() {
-    panic!("Must return Error with message suggesting 'hymod server list' when server not found.");
+    let home = tempfile::tempdir().expect("failed to create home dir");
+    std::env::set_var("HOME", home.path());
+
+    let err = find_server_config("ghost", home.path()).expect_err("server should not exist");
+
+    assert_eq!(
+        err,
+        HymodError::ServerNotFound {
+            name: "ghost".to_string(),
+            searched: vec![home.path().join(".hymod").join("servers.d")],
+        }
+    );
+    assert!(err.to_string().contains("'ghost' not found in"));
+    assert!(err.hint().unwrap().contains("hymod server list"));
 }
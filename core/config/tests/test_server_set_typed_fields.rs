@@ -0,0 +1,82 @@
+use core_config::{parse_server_config, LinkMode, ServerConfig};
+use std::path::Path;
+
+fn remote_server() -> ServerConfig {
+    let content = "\
+server:
+  name: prod
+  kind: remote
+  server_root: /opt/hytale
+  mods_dir: mods
+  restart:
+    cmd: systemctl restart hytale
+  remote:
+    host: example.com
+    user: deploy
+";
+    parse_server_config(content, Path::new("prod.yaml")).unwrap()
+}
+
+#[test]
+fn test_server_set_typed_fields() {
+    let mut config = remote_server();
+
+    config.set("restart.cmd", "./restart.sh").unwrap();
+    config.set("server.mods_dir", "plugins").unwrap();
+    config.set("remote.port", "2222").unwrap();
+    config.set("remote.identity_file", "~/.ssh/prod").unwrap();
+    config.set("upload.method", "scp").unwrap();
+    config.set("upload.scp.opts", "-C").unwrap();
+    config.set("retry.retry_on", "255, 1").unwrap();
+    config.set("link_mode", "copy").unwrap();
+
+    let server = &config.server;
+    assert_eq!(server.restart.cmd, "./restart.sh");
+    assert_eq!(server.mods_dir, "plugins");
+    let remote = server.remote.as_ref().unwrap();
+    assert_eq!(remote.port, 2222);
+    assert_eq!(remote.identity_file.as_deref(), Some("~/.ssh/prod"));
+    let upload = server.upload.as_ref().unwrap();
+    assert_eq!(upload.method, "scp");
+    assert_eq!(upload.scp.as_ref().unwrap().opts, "-C");
+    assert_eq!(server.retry.as_ref().unwrap().retry_on, vec![255, 1]);
+    assert_eq!(server.link_mode, Some(LinkMode::Copy));
+
+    config.set("remote.identity_file", "").unwrap();
+    assert_eq!(config.server.remote.unwrap().identity_file, None);
+}
+
+#[test]
+fn test_server_set_rejects_bad_values() {
+    let mut config = remote_server();
+
+    for (key, value, expected) in [
+        ("remote.port", "70000", "is not a port number"),
+        ("remote.port", "ssh", "expected a port number"),
+        (
+            "upload.method",
+            "ftp",
+            "expected one of rsync, scp, got 'ftp'",
+        ),
+        ("kind", "ssh", "expected one of local, remote"),
+        ("retry.attempts", "-1", "expected a number of attempts"),
+        (
+            "link_mode",
+            "junction",
+            "expected one of symlink, hardlink, copy",
+        ),
+        (
+            "upload.rsync.opts",
+            "-az",
+            "Set upload.method before upload.rsync.opts",
+        ),
+        (
+            "restart.timeout",
+            "5",
+            "Unknown server key: restart.timeout",
+        ),
+    ] {
+        let error = config.set(key, value).unwrap_err();
+        assert!(error.contains(expected), "{} = {}: {}", key, value, error);
+    }
+}
@@ -1,4 +1,31 @@
+use core_config::parse_server_config;
+use std::path::Path;
+
 #[test]
 fn test_server_yaml_missing_ssh_host() {
-    panic!("Must return Error when kind=ssh but ssh.host or ssh.user is missing.");
+    let content = "\
+server:
+  name: prod
+  kind: ssh
+  mods_dir: mods
+  restart:
+    cmd: systemctl restart hytale
+  remote:
+    port: 22
+  upload:
+    method: ftp
+";
+
+    let problems = parse_server_config(content, Path::new("servers.d/prod.yaml")).unwrap_err();
+
+    // Every problem is reported, not just the first.
+    assert_eq!(
+        problems,
+        vec![
+            "server.server_root: missing".to_string(),
+            "server.remote.host: missing".to_string(),
+            "server.remote.user: missing".to_string(),
+            "server.upload.method: 'ftp' is not one of rsync, scp".to_string(),
+        ]
+    );
 }
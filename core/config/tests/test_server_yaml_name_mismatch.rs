@@ -1,4 +1,22 @@
+use core_config::parse_server_config;
+use std::path::Path;
+
 #[test]
 fn test_server_yaml_name_mismatch() {
-    panic!("Must return Error when server.name in YAML does not match filename base.");
+    let content = "\
+server:
+  name: staging
+  kind: local
+  server_root: /srv/hytale
+  mods_dir: mods
+  restart:
+    cmd: ./restart.sh
+";
+
+    let problems = parse_server_config(content, Path::new("servers.d/prod.yaml")).unwrap_err();
+
+    assert_eq!(
+        problems,
+        vec!["server.name: 'staging' does not match the file name 'prod.yaml'".to_string()]
+    );
 }
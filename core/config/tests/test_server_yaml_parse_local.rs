@@ -1,4 +1,24 @@
+use core_config::{parse_server_config, ServerKind};
+use std::path::Path;
+
 #[test]
 fn test_server_yaml_parse_local() {
-    panic!("Must parse local server config with kind=local, server_root, mods_dir, restart.cmd.");
+    let content = "\
+server:
+  name: dev
+  kind: local
+  server_root: /srv/hytale
+  mods_dir: mods
+  restart:
+    cmd: ./restart.sh
+";
+
+    let config = parse_server_config(content, Path::new("servers.d/dev.yaml")).unwrap();
+
+    assert_eq!(config.server.name, "dev");
+    assert_eq!(config.server.kind, ServerKind::Local);
+    assert_eq!(config.server.server_root, "/srv/hytale");
+    assert_eq!(config.server.mods_dir, "mods");
+    assert_eq!(config.server.restart.cmd, "./restart.sh");
+    assert!(config.server.remote.is_none());
 }
@@ -1,6 +1,29 @@
+use core_config::{parse_server_config, ServerKind};
+use std::path::Path;
+
 #[test]
 fn test_server_yaml_parse_ssh() {
-    panic!(
-        "Must parse SSH server config with kind=ssh, ssh.host, ssh.user, ssh.port, identity_file."
-    );
+    let content = "\
+server:
+  name: prod
+  kind: ssh
+  server_root: /opt/hytale
+  mods_dir: mods
+  restart:
+    cmd: systemctl restart hytale
+  remote:
+    host: example.com
+    user: deploy
+    port: 2222
+    identity_file: ~/.ssh/deploy
+";
+
+    let config = parse_server_config(content, Path::new("servers.d/prod.yaml")).unwrap();
+
+    assert_eq!(config.server.kind, ServerKind::Remote);
+    let remote = config.server.remote.unwrap();
+    assert_eq!(remote.host, "example.com");
+    assert_eq!(remote.user, "deploy");
+    assert_eq!(remote.port, 2222);
+    assert_eq!(remote.identity_file.as_deref(), Some("~/.ssh/deploy"));
 }
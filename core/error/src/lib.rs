@@ -0,0 +1,268 @@
+//! Errors hymod reports to the user, each with a stable exit code and a hint.
+//!
+//! | Code | Kind                    |
+//! |------|-------------------------|
+//! | 1    | other                   |
+//! | 2    | invalid-argument        |
+//! | 3    | config-not-found        |
+//! | 4    | invalid-config          |
+//! | 5    | server-not-found        |
+//! | 6    | no-default-server       |
+//! | 7    | server-kind-mismatch    |
+//! | 8    | server-exists           |
+//! | 10   | not-a-mod-directory     |
+//! | 11   | build-failed            |
+//! | 12   | artifact-missing        |
+//! | 20   | transport-unavailable   |
+//! | 21   | remote-unreachable      |
+//! | 30   | step-timed-out          |
+//! | 31   | preflight-failed        |
+//! | 130  | interrupted             |
+//!
+//! Exit codes are part of the CLI contract: scripts may branch on them, so
+//! existing codes must never be renumbered.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HymodError {
+    /// Anything without a more specific kind.
+    Other(String),
+    /// A command-line value hymod cannot use.
+    InvalidArgument(String),
+    /// A configuration file hymod needs does not exist.
+    ConfigNotFound {
+        path: PathBuf,
+    },
+    /// A configuration file exists but cannot be used.
+    InvalidConfig {
+        path: PathBuf,
+        reason: String,
+    },
+    ServerNotFound {
+        name: String,
+        /// Registries that were searched, highest precedence first.
+        searched: Vec<PathBuf>,
+    },
+    /// No server was named and no default of this kind is configured.
+    NoDefaultServer {
+        kind: String,
+    },
+    ServerKindMismatch {
+        name: String,
+        actual: String,
+        expected: String,
+    },
+    ServerExists {
+        name: String,
+    },
+    /// The directory has no Gradle wrapper.
+    NotAModDirectory {
+        path: PathBuf,
+    },
+    BuildFailed {
+        reason: String,
+    },
+    /// The build finished but produced no jar to deploy.
+    ArtifactMissing {
+        path: PathBuf,
+    },
+    /// The requested upload transport is not installed.
+    TransportUnavailable {
+        transport: String,
+    },
+    RemoteUnreachable {
+        host: String,
+        reason: String,
+    },
+    /// A step's child process ran past its timeout and was killed.
+    StepTimedOut {
+        step: String,
+        op: String,
+        timeout_secs: u64,
+    },
+    /// Preflight checks found `problems` before the plan changed anything.
+    PreflightFailed {
+        problems: Vec<String>,
+    },
+    /// The run was cancelled (Ctrl-C) while `step` was running.
+    Interrupted {
+        step: String,
+    },
+}
+
+impl HymodError {
+    /// Process exit code for this error; see the table in the crate docs.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            HymodError::Other(_) => 1,
+            HymodError::InvalidArgument(_) => 2,
+            HymodError::ConfigNotFound { .. } => 3,
+            HymodError::InvalidConfig { .. } => 4,
+            HymodError::ServerNotFound { .. } => 5,
+            HymodError::NoDefaultServer { .. } => 6,
+            HymodError::ServerKindMismatch { .. } => 7,
+            HymodError::ServerExists { .. } => 8,
+            HymodError::NotAModDirectory { .. } => 10,
+            HymodError::BuildFailed { .. } => 11,
+            HymodError::ArtifactMissing { .. } => 12,
+            HymodError::TransportUnavailable { .. } => 20,
+            HymodError::RemoteUnreachable { .. } => 21,
+            HymodError::StepTimedOut { .. } => 30,
+            HymodError::PreflightFailed { .. } => 31,
+            // Matches the shell convention for a process ended by SIGINT.
+            HymodError::Interrupted { .. } => 130,
+        }
+    }
+
+    /// Short machine-friendly name of the error kind.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HymodError::Other(_) => "other",
+            HymodError::InvalidArgument(_) => "invalid-argument",
+            HymodError::ConfigNotFound { .. } => "config-not-found",
+            HymodError::InvalidConfig { .. } => "invalid-config",
+            HymodError::ServerNotFound { .. } => "server-not-found",
+            HymodError::NoDefaultServer { .. } => "no-default-server",
+            HymodError::ServerKindMismatch { .. } => "server-kind-mismatch",
+            HymodError::ServerExists { .. } => "server-exists",
+            HymodError::NotAModDirectory { .. } => "not-a-mod-directory",
+            HymodError::BuildFailed { .. } => "build-failed",
+            HymodError::ArtifactMissing { .. } => "artifact-missing",
+            HymodError::TransportUnavailable { .. } => "transport-unavailable",
+            HymodError::RemoteUnreachable { .. } => "remote-unreachable",
+            HymodError::StepTimedOut { .. } => "step-timed-out",
+            HymodError::PreflightFailed { .. } => "preflight-failed",
+            HymodError::Interrupted { .. } => "interrupted",
+        }
+    }
+
+    /// What the user can try next, if there is anything obvious.
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            HymodError::Other(_) | HymodError::InvalidArgument(_) => return None,
+            HymodError::ConfigNotFound { .. } => "run: hymod config set <key> <value>".to_string(),
+            HymodError::InvalidConfig { path, .. } => {
+                format!("fix or remove {}", path.display())
+            }
+            HymodError::ServerNotFound { .. } => "run: hymod server list".to_string(),
+            HymodError::NoDefaultServer { kind } => {
+                format!("run: hymod server default {} <name>", kind)
+            }
+            HymodError::ServerKindMismatch { expected, .. } => {
+                format!("run: hymod server list {}", expected)
+            }
+            HymodError::ServerExists { name } => format!("run: hymod server remove {}", name),
+            HymodError::NotAModDirectory { .. } => {
+                "run from a mod directory or pass --path <mod_dir>".to_string()
+            }
+            HymodError::BuildFailed { .. } => {
+                "run: ./gradlew build to see the full output".to_string()
+            }
+            HymodError::ArtifactMissing { .. } => {
+                "check that the Gradle build writes a jar to build/libs/".to_string()
+            }
+            HymodError::TransportUnavailable { transport } => {
+                let other = if transport == "rsync" { "scp" } else { "rsync" };
+                format!("install {} or pass --transport {}", transport, other)
+            }
+            HymodError::RemoteUnreachable { host, .. } => {
+                format!("check that ssh {} works from this machine", host)
+            }
+            HymodError::StepTimedOut { op, .. } => format!(
+                "raise timeout_secs on the step or timeouts.{} in {}",
+                op,
+                config_file()
+            ),
+            HymodError::PreflightFailed { .. } => {
+                "nothing was changed; fix the problems above and re-run".to_string()
+            }
+            HymodError::Interrupted { .. } => {
+                "changes made by finished steps were rolled back; re-run to try again".to_string()
+            }
+        };
+        Some(hint)
+    }
+}
+
+/// The global `config.yaml` as hints name it: under `$HYMOD_HOME` when set,
+/// like `core_config::config_path`, which this crate cannot depend on.
+fn config_file() -> String {
+    match std::env::var_os("HYMOD_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => Path::new(&dir).join("config.yaml").display().to_string(),
+        None => "~/.hymod/config.yaml".to_string(),
+    }
+}
+
+impl fmt::Display for HymodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HymodError::Other(message) | HymodError::InvalidArgument(message) => {
+                write!(f, "{}", message)
+            }
+            HymodError::ConfigNotFound { path } => {
+                write!(f, "Configuration not found: {}", path.display())
+            }
+            HymodError::InvalidConfig { path, reason } => {
+                write!(f, "Invalid config {}: {}", path.display(), reason)
+            }
+            HymodError::ServerNotFound { name, searched } => {
+                write!(f, "Server configuration '{}' not found", name)?;
+                if !searched.is_empty() {
+                    let dirs: Vec<_> = searched
+                        .iter()
+                        .map(|dir| dir.display().to_string())
+                        .collect();
+                    write!(f, " in {}", dirs.join(", "))?;
+                }
+                Ok(())
+            }
+            HymodError::NoDefaultServer { kind } => write!(
+                f,
+                "No server specified and no default {} server configured",
+                kind
+            ),
+            HymodError::ServerKindMismatch {
+                name,
+                actual,
+                expected,
+            } => write!(
+                f,
+                "Server '{}' is a {} server, expected a {} server",
+                name, actual, expected
+            ),
+            HymodError::ServerExists { name } => write!(f, "Server '{}' already exists", name),
+            HymodError::NotAModDirectory { path } => write!(
+                f,
+                "No gradle wrapper found at {}. Is this a mod directory?",
+                path.display()
+            ),
+            HymodError::BuildFailed { reason } => write!(f, "Gradle build failed: {}", reason),
+            HymodError::ArtifactMissing { path } => write!(
+                f,
+                "Could not find a suitable .jar artifact in build/libs/ (expected {})",
+                path.display()
+            ),
+            HymodError::TransportUnavailable { transport } => {
+                write!(f, "Transport {} not available", transport)
+            }
+            HymodError::RemoteUnreachable { host, reason } => {
+                write!(f, "SSH connection to {} failed: {}", host, reason)
+            }
+            HymodError::StepTimedOut {
+                step, timeout_secs, ..
+            } => write!(f, "Step '{}' timed out after {}s", step, timeout_secs),
+            HymodError::PreflightFailed { problems } => {
+                write!(f, "Preflight failed with {} problem(s):", problems.len())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            HymodError::Interrupted { step } => write!(f, "Interrupted during step '{}'", step),
+        }
+    }
+}
+
+impl std::error::Error for HymodError {}
@@ -0,0 +1,136 @@
+use core_error::HymodError;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+fn all_kinds() -> Vec<HymodError> {
+    vec![
+        HymodError::Other("boom".to_string()),
+        HymodError::InvalidArgument("bad".to_string()),
+        HymodError::ConfigNotFound {
+            path: PathBuf::from("hymod.yaml"),
+        },
+        HymodError::InvalidConfig {
+            path: PathBuf::from("prod.yaml"),
+            reason: "parse error".to_string(),
+        },
+        HymodError::ServerNotFound {
+            name: "prod".to_string(),
+            searched: vec![PathBuf::from("servers.d")],
+        },
+        HymodError::NoDefaultServer {
+            kind: "remote".to_string(),
+        },
+        HymodError::ServerKindMismatch {
+            name: "dev".to_string(),
+            actual: "remote".to_string(),
+            expected: "local".to_string(),
+        },
+        HymodError::ServerExists {
+            name: "prod".to_string(),
+        },
+        HymodError::NotAModDirectory {
+            path: PathBuf::from("gradlew"),
+        },
+        HymodError::BuildFailed {
+            reason: "exit 1".to_string(),
+        },
+        HymodError::ArtifactMissing {
+            path: PathBuf::from("build/libs/mod.jar"),
+        },
+        HymodError::TransportUnavailable {
+            transport: "rsync".to_string(),
+        },
+        HymodError::RemoteUnreachable {
+            host: "example.com".to_string(),
+            reason: "timed out".to_string(),
+        },
+        HymodError::StepTimedOut {
+            step: "2. RUN       systemctl restart hytale".to_string(),
+            op: "ssh_run".to_string(),
+            timeout_secs: 60,
+        },
+        HymodError::PreflightFailed {
+            problems: vec!["scp not found on PATH".to_string()],
+        },
+        HymodError::Interrupted {
+            step: "1. RUN      ./gradlew build".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn test_error_exit_codes_are_unique_and_non_zero() {
+    let mut codes = HashSet::new();
+    let mut kinds = HashSet::new();
+    for error in all_kinds() {
+        assert_ne!(error.exit_code(), 0);
+        assert!(
+            codes.insert(error.exit_code()),
+            "duplicate code for {:?}",
+            error
+        );
+        assert!(kinds.insert(error.kind()), "duplicate kind for {:?}", error);
+    }
+}
+
+#[test]
+fn test_error_kinds_with_remedies_have_hints() {
+    for error in all_kinds() {
+        match error {
+            HymodError::Other(_) | HymodError::InvalidArgument(_) => {
+                assert!(error.hint().is_none())
+            }
+            _ => assert!(error.hint().is_some(), "missing hint for {:?}", error),
+        }
+    }
+}
+
+#[test]
+fn test_error_transport_unavailable_suggests_other_transport() {
+    let error = HymodError::TransportUnavailable {
+        transport: "rsync".to_string(),
+    };
+    assert_eq!(error.to_string(), "Transport rsync not available");
+    assert_eq!(
+        error.hint().unwrap(),
+        "install rsync or pass --transport scp"
+    );
+}
+
+#[test]
+fn test_error_timeout_hint_names_config_under_hymod_home() {
+    std::env::set_var("HYMOD_HOME", "/srv/ci/hymod");
+    let error = HymodError::StepTimedOut {
+        step: "2".to_string(),
+        op: "ssh_run".to_string(),
+        timeout_secs: 60,
+    };
+    assert_eq!(
+        error.hint().unwrap(),
+        "raise timeout_secs on the step or timeouts.ssh_run in /srv/ci/hymod/config.yaml"
+    );
+}
+
+#[test]
+fn test_error_interrupted_uses_sigint_exit_code() {
+    let error = HymodError::Interrupted {
+        step: "3".to_string(),
+    };
+    assert_eq!(error.exit_code(), 130);
+    assert_eq!(error.to_string(), "Interrupted during step '3'");
+}
+
+#[test]
+fn test_error_preflight_lists_every_problem() {
+    let error = HymodError::PreflightFailed {
+        problems: vec![
+            "scp not found on PATH".to_string(),
+            "cannot reach deploy@example.com over SSH".to_string(),
+        ],
+    };
+    assert_eq!(error.exit_code(), 31);
+    assert_eq!(
+        error.to_string(),
+        "Preflight failed with 2 problem(s):\n  - scp not found on PATH\n  - cannot reach deploy@example.com over SSH"
+    );
+}
@@ -0,0 +1,35 @@
+use crate::context::ExecContext;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Hidden sibling of `path` that a write goes to before it is renamed into
+/// place. It does not end in `.jar`, so a server scanning the directory
+/// skips it while it is incomplete.
+pub(crate) fn temp_sibling(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.hymod-tmp", name))
+}
+
+/// Let `write` produce the new file at a temporary sibling of `path`, then
+/// rename it over `path` so readers only ever see the old or the new file.
+/// The temporary file is removed again if anything fails.
+pub(crate) fn write_atomically(
+    ctx: &ExecContext,
+    path: &Path,
+    write: impl FnOnce(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let temp = temp_sibling(path);
+    let result = write(&temp)
+        .and_then(|()| match ctx.fsync() {
+            true => ctx.fs().sync_file(&temp),
+            false => Ok(()),
+        })
+        .and_then(|()| ctx.fs().rename(&temp, path));
+    if result.is_err() && ctx.fs().entry_kind(&temp).is_some() {
+        let _ = ctx.fs().remove_file(&temp);
+    }
+    result
+}
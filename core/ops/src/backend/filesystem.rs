@@ -0,0 +1,418 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// What a path points at, without following a final symlink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+}
+
+/// Filesystem operations used by the built-in handlers and the undo journal.
+pub trait FileSystem: Send + Sync {
+    /// Kind of the entry at `path` itself, like `fs::symlink_metadata`.
+    fn entry_kind(&self, path: &Path) -> Option<EntryKind>;
+    /// Whether `path` exists, following symlinks.
+    fn exists(&self, path: &Path) -> bool;
+    /// Whether `path` is a regular file, following symlinks.
+    fn is_file(&self, path: &Path) -> bool;
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>>;
+    fn write(&self, path: &Path, content: &[u8]) -> io::Result<()>;
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()>;
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+    /// Remove an empty directory.
+    fn remove_dir(&self, path: &Path) -> io::Result<()>;
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf>;
+    /// Create a symlink at `link` pointing to `target`.
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()>;
+    /// Create a hard link at `link` to the existing file `original`.
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()>;
+    /// Move `from` to `to`, replacing a file at `to` in a single step.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    /// Flush the content of the file at `path` to disk.
+    fn sync_file(&self, path: &Path) -> io::Result<()>;
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        self.open(path)?.read_to_end(&mut content)?;
+        Ok(content)
+    }
+}
+
+/// The real filesystem, through `std::fs`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn entry_kind(&self, path: &Path) -> Option<EntryKind> {
+        let meta = fs::symlink_metadata(path).ok()?;
+        Some(if meta.file_type().is_symlink() {
+            EntryKind::Symlink
+        } else if meta.is_dir() {
+            EntryKind::Dir
+        } else {
+            EntryKind::File
+        })
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(fs::File::open(path)?))
+    }
+
+    fn write(&self, path: &Path, content: &[u8]) -> io::Result<()> {
+        fs::write(path, content)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::copy(from, to).map(|_| ())
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir(path)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::read_link(path)
+    }
+
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(target, link)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (target, link);
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Symlinks only supported on Unix for now",
+            ))
+        }
+    }
+
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        fs::hard_link(original, link)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn sync_file(&self, path: &Path) -> io::Result<()> {
+        fs::File::open(path)?.sync_all()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    File(Vec<u8>),
+    Dir,
+    Symlink(PathBuf),
+}
+
+/// A filesystem held entirely in memory, for hermetic tests. The root and
+/// relative-path base always exist; only a final symlink component is followed.
+/// Hard links are stored as independent copies of the original's content.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    nodes: Mutex<BTreeMap<PathBuf, Node>>,
+    no_symlinks: bool,
+    no_hard_links: bool,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, creating its parent directories.
+    pub fn with_file(self, path: impl AsRef<Path>, content: impl Into<Vec<u8>>) -> Self {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            self.create_dir_all(parent).unwrap();
+        }
+        self.insert(path, Node::File(content.into()));
+        self
+    }
+
+    pub fn with_dir(self, path: impl AsRef<Path>) -> Self {
+        self.create_dir_all(path.as_ref()).unwrap();
+        self
+    }
+
+    /// Fail every `symlink` call as unsupported, like a system without symlinks.
+    pub fn without_symlinks(mut self) -> Self {
+        self.no_symlinks = true;
+        self
+    }
+
+    /// Fail every `hard_link` call as unsupported.
+    pub fn without_hard_links(mut self) -> Self {
+        self.no_hard_links = true;
+        self
+    }
+
+    /// Content of the file at `path`, following symlinks.
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        let nodes = self.nodes.lock().unwrap();
+        match resolve(&nodes, path.as_ref()).and_then(|path| nodes.get(&path)) {
+            Some(Node::File(content)) => Some(content.clone()),
+            _ => None,
+        }
+    }
+
+    /// Every path currently present, in sorted order.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.nodes.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn insert(&self, path: &Path, node: Node) {
+        self.nodes.lock().unwrap().insert(path.to_path_buf(), node);
+    }
+}
+
+fn is_root(path: &Path) -> bool {
+    path.as_os_str().is_empty() || path.parent().is_none()
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{}: no such file or directory", path.display()),
+    )
+}
+
+fn unsupported(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{} not supported", what),
+    )
+}
+
+fn invalid(path: &Path, what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{}: {}", path.display(), what),
+    )
+}
+
+/// Follow symlinks at `path` until a non-link entry; `None` if it dangles.
+fn resolve(nodes: &BTreeMap<PathBuf, Node>, path: &Path) -> Option<PathBuf> {
+    let mut current = path.to_path_buf();
+    for _ in 0..40 {
+        if is_root(&current) {
+            return Some(current);
+        }
+        match nodes.get(&current)? {
+            Node::Symlink(target) if target.is_absolute() => current = target.clone(),
+            Node::Symlink(target) => {
+                current = current.parent().unwrap_or(Path::new("")).join(target)
+            }
+            _ => return Some(current),
+        }
+    }
+    None
+}
+
+fn is_dir(nodes: &BTreeMap<PathBuf, Node>, path: &Path) -> bool {
+    match resolve(nodes, path) {
+        Some(path) if is_root(&path) => true,
+        Some(path) => matches!(nodes.get(&path), Some(Node::Dir)),
+        None => false,
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn entry_kind(&self, path: &Path) -> Option<EntryKind> {
+        if is_root(path) {
+            return Some(EntryKind::Dir);
+        }
+        match self.nodes.lock().unwrap().get(path)? {
+            Node::File(_) => Some(EntryKind::File),
+            Node::Dir => Some(EntryKind::Dir),
+            Node::Symlink(_) => Some(EntryKind::Symlink),
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        resolve(&self.nodes.lock().unwrap(), path).is_some()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.file(path).is_some()
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        let nodes = self.nodes.lock().unwrap();
+        match resolve(&nodes, path).and_then(|path| nodes.get(&path)) {
+            Some(Node::File(content)) => Ok(Box::new(Cursor::new(content.clone()))),
+            Some(_) => Err(invalid(path, "is a directory")),
+            None if is_root(path) => Err(invalid(path, "is a directory")),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn write(&self, path: &Path, content: &[u8]) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let target = resolve(&nodes, path).unwrap_or_else(|| path.to_path_buf());
+        if is_root(&target) || matches!(nodes.get(&target), Some(Node::Dir)) {
+            return Err(invalid(path, "is a directory"));
+        }
+        if !is_dir(&nodes, target.parent().unwrap_or(Path::new(""))) {
+            return Err(not_found(path));
+        }
+        nodes.insert(target, Node::File(content.to_vec()));
+        Ok(())
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let content = self.read(from)?;
+        self.write(to, &content)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let mut missing = Vec::new();
+        for dir in path.ancestors() {
+            if is_root(dir) || is_dir(&nodes, dir) {
+                break;
+            }
+            if nodes.contains_key(dir) {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("{}: not a directory", dir.display()),
+                ));
+            }
+            missing.push(dir.to_path_buf());
+        }
+        for dir in missing {
+            nodes.insert(dir, Node::Dir);
+        }
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        match nodes.get(path) {
+            Some(Node::File(_)) | Some(Node::Symlink(_)) => {
+                nodes.remove(path);
+                Ok(())
+            }
+            Some(Node::Dir) => Err(invalid(path, "is a directory")),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        match nodes.get(path) {
+            Some(Node::Dir) => {}
+            Some(_) => return Err(invalid(path, "not a directory")),
+            None => return Err(not_found(path)),
+        }
+        if nodes
+            .keys()
+            .any(|other| other != path && other.starts_with(path))
+        {
+            return Err(io::Error::other(format!(
+                "{}: directory not empty",
+                path.display()
+            )));
+        }
+        nodes.remove(path);
+        Ok(())
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.nodes.lock().unwrap().get(path) {
+            Some(Node::Symlink(target)) => Ok(target.clone()),
+            Some(_) => Err(invalid(path, "not a symlink")),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        if self.no_symlinks {
+            return Err(unsupported("symlinks"));
+        }
+        let mut nodes = self.nodes.lock().unwrap();
+        if is_root(link) || nodes.contains_key(link) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{}: file exists", link.display()),
+            ));
+        }
+        if !is_dir(&nodes, link.parent().unwrap_or(Path::new(""))) {
+            return Err(not_found(link));
+        }
+        nodes.insert(link.to_path_buf(), Node::Symlink(target.to_path_buf()));
+        Ok(())
+    }
+
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        if self.no_hard_links {
+            return Err(unsupported("hard links"));
+        }
+        let mut nodes = self.nodes.lock().unwrap();
+        let content = match nodes.get(original) {
+            Some(Node::File(content)) => content.clone(),
+            Some(_) => return Err(invalid(original, "not a regular file")),
+            None => return Err(not_found(original)),
+        };
+        if is_root(link) || nodes.contains_key(link) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{}: file exists", link.display()),
+            ));
+        }
+        if !is_dir(&nodes, link.parent().unwrap_or(Path::new(""))) {
+            return Err(not_found(link));
+        }
+        nodes.insert(link.to_path_buf(), Node::File(content));
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let Some(node) = nodes.get(from).cloned() else {
+            return Err(not_found(from));
+        };
+        if is_root(to) || matches!(nodes.get(to), Some(Node::Dir)) {
+            return Err(invalid(to, "is a directory"));
+        }
+        if !is_dir(&nodes, to.parent().unwrap_or(Path::new(""))) {
+            return Err(not_found(to));
+        }
+        nodes.remove(from);
+        nodes.insert(to.to_path_buf(), node);
+        Ok(())
+    }
+
+    fn sync_file(&self, path: &Path) -> io::Result<()> {
+        match self.file(path) {
+            Some(_) => Ok(()),
+            None => Err(not_found(path)),
+        }
+    }
+}
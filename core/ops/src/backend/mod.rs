@@ -0,0 +1,12 @@
+//! Seams between handlers and the outside world. The executor runs against
+//! the real filesystem and real child processes by default; tests and
+//! embedders can swap in the in-memory and recording implementations.
+
+pub mod filesystem;
+pub mod runner;
+
+pub use filesystem::{EntryKind, FileSystem, MemoryFileSystem, OsFileSystem};
+pub use runner::{
+    CannedResult, ProcessOutput, ProcessRunner, ProcessSpec, ProcessStatus, RecordingProcessRunner,
+    SystemProcessRunner,
+};
@@ -0,0 +1,225 @@
+use crate::context::ExecContext;
+use crate::process::{capture_command, report_exit, report_output, run_command};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// A child process to run: program, arguments, working directory and any
+/// environment variables set on top of the inherited environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessSpec {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: Vec<(String, String)>,
+}
+
+impl ProcessSpec {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            cwd: None,
+            env: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    fn to_command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args);
+        command.envs(self.env.iter().map(|(key, value)| (key, value)));
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+        command
+    }
+}
+
+impl fmt::Display for ProcessSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a child process ended. `code` is `None` if it was killed by a signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessStatus {
+    pub code: Option<i32>,
+}
+
+impl ProcessStatus {
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+/// Result of a captured run: exit status and everything written to stdout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessOutput {
+    pub status: ProcessStatus,
+    pub stdout: String,
+}
+
+/// Starts the child processes behind `RunProcess`, `SshRun` and the uploads.
+/// Implementations should honour `ctx.step_timeout()` and `ctx.is_cancelled()`.
+pub trait ProcessRunner: Send + Sync {
+    /// Run to completion, reporting output through the context like any step.
+    fn run(&self, spec: &ProcessSpec, ctx: &ExecContext) -> io::Result<ProcessStatus>;
+    /// Run without reporting anything and hand back stdout, for checks that
+    /// inspect a command's result rather than show it.
+    fn capture(&self, spec: &ProcessSpec, ctx: &ExecContext) -> io::Result<ProcessOutput>;
+}
+
+/// Real child processes through `std::process::Command`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemProcessRunner;
+
+impl ProcessRunner for SystemProcessRunner {
+    fn run(&self, spec: &ProcessSpec, ctx: &ExecContext) -> io::Result<ProcessStatus> {
+        let started = Instant::now();
+        let result = run_command(&mut spec.to_command(), ctx);
+        match &result {
+            Ok(status) => report_exit(ctx, &spec.to_string(), status.code(), started),
+            // Killed for running too long or on cancellation.
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::TimedOut | io::ErrorKind::Interrupted
+                ) =>
+            {
+                report_exit(ctx, &spec.to_string(), None, started)
+            }
+            Err(_) => {}
+        }
+        Ok(ProcessStatus {
+            code: result?.code(),
+        })
+    }
+
+    fn capture(&self, spec: &ProcessSpec, ctx: &ExecContext) -> io::Result<ProcessOutput> {
+        let (status, stdout) = capture_command(&mut spec.to_command(), ctx)?;
+        Ok(ProcessOutput {
+            status: ProcessStatus {
+                code: status.code(),
+            },
+            stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        })
+    }
+}
+
+/// Canned result for a `RecordingProcessRunner`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CannedResult {
+    pub code: i32,
+    pub stdout: String,
+}
+
+impl CannedResult {
+    pub fn success(stdout: impl Into<String>) -> Self {
+        Self {
+            code: 0,
+            stdout: stdout.into(),
+        }
+    }
+
+    pub fn exit(code: i32) -> Self {
+        Self {
+            code,
+            stdout: String::new(),
+        }
+    }
+}
+
+/// Records every process it is asked to run instead of starting it.
+///
+/// Programs succeed silently unless scripted with `with_response`. Responses
+/// for one program are used in order and the last one keeps repeating.
+#[derive(Debug, Default)]
+pub struct RecordingProcessRunner {
+    calls: Mutex<Vec<ProcessSpec>>,
+    responses: Mutex<HashMap<String, Vec<CannedResult>>>,
+}
+
+impl RecordingProcessRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_response(self, program: impl Into<String>, result: CannedResult) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(program.into())
+            .or_default()
+            .push(result);
+        self
+    }
+
+    /// Every process run or captured so far, in order.
+    pub fn calls(&self) -> Vec<ProcessSpec> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn respond(&self, spec: &ProcessSpec) -> CannedResult {
+        self.calls.lock().unwrap().push(spec.clone());
+        let mut responses = self.responses.lock().unwrap();
+        match responses.get_mut(&spec.program) {
+            Some(queue) if queue.len() > 1 => queue.remove(0),
+            Some(queue) if !queue.is_empty() => queue[0].clone(),
+            _ => CannedResult::success(""),
+        }
+    }
+}
+
+impl ProcessRunner for RecordingProcessRunner {
+    fn run(&self, spec: &ProcessSpec, ctx: &ExecContext) -> io::Result<ProcessStatus> {
+        let result = self.respond(spec);
+        report_output(ctx, &result.stdout);
+        report_exit(ctx, &spec.to_string(), Some(result.code), Instant::now());
+        Ok(ProcessStatus {
+            code: Some(result.code),
+        })
+    }
+
+    fn capture(&self, spec: &ProcessSpec, _ctx: &ExecContext) -> io::Result<ProcessOutput> {
+        let result = self.respond(spec);
+        Ok(ProcessOutput {
+            status: ProcessStatus {
+                code: Some(result.code),
+            },
+            stdout: result.stdout,
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared flag that asks a running plan to stop. Running children are killed
+/// together with their process group, no further steps start, and the
+/// executor rolls back what the plan changed so far.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Cancel this token on Ctrl-C. A second Ctrl-C exits immediately with
+    /// status 130 instead of waiting for cleanup. Can only be installed once
+    /// per process.
+    pub fn install_interrupt_handler(&self) -> Result<(), ctrlc::Error> {
+        let token = self.clone();
+        ctrlc::set_handler(move || {
+            if token.is_cancelled() {
+                std::process::exit(130);
+            }
+            eprintln!("Interrupted, stopping the running step...");
+            token.cancel();
+        })
+    }
+}
@@ -0,0 +1,179 @@
+use crate::backend::{FileSystem, OsFileSystem, ProcessRunner, SystemProcessRunner};
+use crate::cancel::CancelToken;
+use crate::events::{ExecEvent, ExecObserver};
+use crate::journal::Journal;
+use hymod_core_plan::SECRET_MASK;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How much output the executor and its handlers produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only errors are shown; child process stdout is discarded.
+    Quiet,
+    #[default]
+    Normal,
+    /// Every child command is echoed together with its resolved working directory.
+    Verbose,
+}
+
+/// State shared with every handler during a single plan run.
+pub struct ExecContext {
+    pub verbosity: Verbosity,
+    /// Compensating actions for the changes made so far in this run.
+    pub journal: Journal,
+    /// Set for steps running inside a `Step::Parallel` branch. Child output is
+    /// then buffered and printed as one block prefixed with this label.
+    pub output_label: Option<String>,
+    observers: Vec<Arc<dyn ExecObserver>>,
+    /// Label of the step currently running in this context, used to attribute
+    /// child output events.
+    current_step: Mutex<String>,
+    /// Timeout for child processes started by the current step, if any.
+    step_timeout: Mutex<Option<Duration>>,
+    /// Secret values of the current step, masked in everything it reports.
+    secrets: Mutex<Vec<String>>,
+    cancel: CancelToken,
+    fsync: bool,
+    fs: Arc<dyn FileSystem>,
+    runner: Arc<dyn ProcessRunner>,
+}
+
+impl Default for ExecContext {
+    fn default() -> Self {
+        Self {
+            verbosity: Verbosity::default(),
+            journal: Journal::default(),
+            output_label: None,
+            observers: Vec::new(),
+            current_step: Mutex::new(String::new()),
+            step_timeout: Mutex::new(None),
+            secrets: Mutex::new(Vec::new()),
+            cancel: CancelToken::default(),
+            fsync: false,
+            fs: Arc::new(OsFileSystem),
+            runner: Arc::new(SystemProcessRunner),
+        }
+    }
+}
+
+impl ExecContext {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Self {
+            verbosity,
+            ..Self::default()
+        }
+    }
+
+    /// Run handlers against the given filesystem and process backends.
+    pub fn with_backends(
+        mut self,
+        fs: Arc<dyn FileSystem>,
+        runner: Arc<dyn ProcessRunner>,
+    ) -> Self {
+        self.fs = fs;
+        self.runner = runner;
+        self
+    }
+
+    pub fn with_cancel_token(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    /// Whether files are flushed to disk before they are renamed into place.
+    pub fn fsync(&self) -> bool {
+        self.fsync
+    }
+
+    /// True once the run has been asked to stop, e.g. by Ctrl-C.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Filesystem every built-in handler reads and writes through.
+    pub fn fs(&self) -> &dyn FileSystem {
+        self.fs.as_ref()
+    }
+
+    /// Runner every built-in handler starts child processes through.
+    pub fn runner(&self) -> &dyn ProcessRunner {
+        self.runner.as_ref()
+    }
+
+    pub fn with_observers(mut self, observers: Vec<Arc<dyn ExecObserver>>) -> Self {
+        self.observers = observers;
+        self
+    }
+
+    /// Context for one branch of a parallel stage, with its own journal.
+    pub(crate) fn branch(&self, label: &str) -> Self {
+        Self {
+            verbosity: self.verbosity,
+            journal: Journal::default(),
+            output_label: Some(label.to_string()),
+            observers: self.observers.clone(),
+            current_step: Mutex::new(String::new()),
+            step_timeout: Mutex::new(None),
+            secrets: Mutex::new(Vec::new()),
+            cancel: self.cancel.clone(),
+            fsync: self.fsync,
+            fs: self.fs.clone(),
+            runner: self.runner.clone(),
+        }
+    }
+
+    /// True when an observer renders progress instead of plain output.
+    pub fn is_observed(&self) -> bool {
+        self.observers
+            .iter()
+            .any(|observer| observer.renders_output())
+    }
+
+    /// True when any observer, rendering or recording, receives events.
+    pub fn has_observers(&self) -> bool {
+        !self.observers.is_empty()
+    }
+
+    pub fn emit(&self, event: ExecEvent) {
+        for observer in &self.observers {
+            observer.on_event(&event);
+        }
+    }
+
+    pub fn current_step(&self) -> String {
+        self.current_step.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_current_step(&self, label: &str) {
+        *self.current_step.lock().unwrap() = label.to_string();
+    }
+
+    /// How long a child process of the current step may run before it is killed.
+    pub fn step_timeout(&self) -> Option<Duration> {
+        *self.step_timeout.lock().unwrap()
+    }
+
+    pub(crate) fn set_step_timeout(&self, timeout: Option<Duration>) {
+        *self.step_timeout.lock().unwrap() = timeout;
+    }
+
+    /// Replace every secret value of the current step in `text` with a mask.
+    pub fn redact(&self, text: &str) -> String {
+        let secrets = self.secrets.lock().unwrap();
+        let mut text = text.to_string();
+        for secret in secrets.iter().filter(|secret| !secret.is_empty()) {
+            text = text.replace(secret.as_str(), SECRET_MASK);
+        }
+        text
+    }
+
+    pub(crate) fn set_secrets(&self, secrets: Vec<String>) {
+        *self.secrets.lock().unwrap() = secrets;
+    }
+}
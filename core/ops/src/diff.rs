@@ -0,0 +1,115 @@
+use crate::context::ExecContext;
+use crate::digest::sha256_bytes;
+use hymod_core_plan::Step;
+use similar::TextDiff;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use zip::ZipArchive;
+
+/// What `step` would change on disk, for `--diff` dry runs: a unified diff
+/// for text writes and removals, sizes and hashes for binary ones, and the
+/// versions involved when a copy replaces an existing jar. `None` when there
+/// is nothing to show.
+pub(crate) fn describe_change(step: &Step, ctx: &ExecContext) -> Option<String> {
+    match step {
+        Step::WriteFile { path, content } => text_change(ctx, path, content),
+        Step::WriteBytes { path, content } => binary_change(ctx, path, content),
+        Step::CopyFile { from, to } => copy_change(ctx, from, to),
+        Step::RemoveFile { path } => removal(ctx, path),
+        _ => None,
+    }
+}
+
+fn text_change(ctx: &ExecContext, path: &str, content: &str) -> Option<String> {
+    let (old_header, old) = match ctx.fs().read(Path::new(path)) {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(text) => (path, text),
+            Err(e) => {
+                return Some(format!(
+                    "replaces binary file ({}) with text ({})",
+                    summary(e.as_bytes()),
+                    summary(content.as_bytes())
+                ))
+            }
+        },
+        Err(_) => ("/dev/null", String::new()),
+    };
+    if old == content && old_header == path {
+        return None;
+    }
+    let diff = TextDiff::from_lines(old.as_str(), content)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_header, path)
+        .to_string();
+    Some(diff.trim_end().to_string())
+}
+
+fn removal(ctx: &ExecContext, path: &str) -> Option<String> {
+    let old = match String::from_utf8(ctx.fs().read(Path::new(path)).ok()?) {
+        Ok(text) => text,
+        Err(e) => return Some(format!("removes binary file ({})", summary(e.as_bytes()))),
+    };
+    let diff = TextDiff::from_lines(old.as_str(), "")
+        .unified_diff()
+        .context_radius(3)
+        .header(path, "/dev/null")
+        .to_string();
+    Some(diff.trim_end().to_string())
+}
+
+fn binary_change(ctx: &ExecContext, path: &str, content: &[u8]) -> Option<String> {
+    match ctx.fs().read(Path::new(path)) {
+        Ok(old) if old == content => None,
+        Ok(old) => Some(format!("binary: {} -> {}", summary(&old), summary(content))),
+        Err(_) => Some(format!("binary: new file, {}", summary(content))),
+    }
+}
+
+fn copy_change(ctx: &ExecContext, from: &str, to: &str) -> Option<String> {
+    let old = ctx.fs().read(Path::new(to)).ok()?;
+    // The source may not exist yet during a dry run, e.g. a jar the plan builds first.
+    let Ok(new) = ctx.fs().read(Path::new(from)) else {
+        return Some(format!("overwrites existing {} ({})", to, summary(&old)));
+    };
+    if old == new {
+        return None;
+    }
+    match (jar_version(&old), jar_version(&new)) {
+        (Some(old_version), Some(new_version)) if old_version != new_version => Some(format!(
+            "overwrites {} version {} with version {}",
+            to, old_version, new_version
+        )),
+        (Some(version), Some(_)) => Some(format!(
+            "overwrites {} with a different build of the same version {}",
+            to, version
+        )),
+        _ => Some(format!(
+            "overwrites {}: {} -> {}",
+            to,
+            summary(&old),
+            summary(&new)
+        )),
+    }
+}
+
+/// `Version` from the `manifest.json` inside a mod jar.
+fn jar_version(bytes: &[u8]) -> Option<String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).ok()?;
+    let mut content = String::new();
+    archive
+        .by_name("manifest.json")
+        .ok()?
+        .read_to_string(&mut content)
+        .ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json.get("Version")?.as_str().map(String::from)
+}
+
+fn summary(bytes: &[u8]) -> String {
+    format!(
+        "{} bytes, sha256 {}",
+        bytes.len(),
+        &sha256_bytes(bytes)[..12]
+    )
+}
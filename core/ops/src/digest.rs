@@ -0,0 +1,60 @@
+use crate::backend::{FileSystem, ProcessSpec};
+use crate::context::ExecContext;
+use crate::handlers::net::split_remote;
+use crate::process::shell_quote;
+use hymod_core_plan::SshOptions;
+use sha2::{Digest, Sha256};
+use std::io;
+use std::path::Path;
+
+/// Hex-encoded SHA-256 of `bytes`.
+pub(crate) fn sha256_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Hex-encoded SHA-256 of the file at `path`, streamed rather than read whole.
+pub(crate) fn sha256_file(fs: &dyn FileSystem, path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs.open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// True if `path` is a file whose SHA-256 equals `expected`.
+pub(crate) fn file_has_digest(fs: &dyn FileSystem, path: &Path, expected: &str) -> bool {
+    fs.is_file(path) && sha256_file(fs, path).is_ok_and(|actual| actual == expected)
+}
+
+/// True if `source` exists and `dest` already holds the same bytes.
+pub(crate) fn same_file_content(fs: &dyn FileSystem, source: &Path, dest: &Path) -> bool {
+    sha256_file(fs, source).is_ok_and(|digest| file_has_digest(fs, dest, &digest))
+}
+
+/// True if the remote file named by an scp/rsync destination (`user@host:/path`)
+/// has the same SHA-256 as the local file `source`. Any failure to connect or
+/// hash counts as "not up to date", so the upload simply goes ahead.
+pub(crate) fn remote_matches(
+    ctx: &ExecContext,
+    source: &Path,
+    remote: &str,
+    ssh: &SshOptions,
+) -> bool {
+    let Some((target, path)) = split_remote(remote) else {
+        return false;
+    };
+    let Ok(local) = sha256_file(ctx.fs(), source) else {
+        return false;
+    };
+
+    let spec = ProcessSpec::new("ssh")
+        .args(ssh.ssh_args())
+        .args(["-o", "BatchMode=yes", &target])
+        .arg(format!("sha256sum -- {}", shell_quote(path)));
+    match ctx.runner().capture(&spec, ctx) {
+        Ok(output) if output.status.success() => output
+            .stdout
+            .split_whitespace()
+            .next()
+            .is_some_and(|remote| remote.eq_ignore_ascii_case(&local)),
+        _ => false,
+    }
+}
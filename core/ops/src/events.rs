@@ -0,0 +1,96 @@
+use hymod_core_plan::LinkMode;
+use serde::Serialize;
+
+/// Which stream of a child process an output line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Progress of a plan run, as reported to every registered `ExecObserver`.
+///
+/// `step` is the step's position in the plan as shown by dry-run output,
+/// e.g. `"2"` or `"2.1.1"` for the first step of a parallel stage's first branch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecEvent {
+    /// The plan's preflight checks found problems, so none of its steps ran.
+    PreflightFailed {
+        problems: Vec<String>,
+    },
+    PlanStarted {
+        steps: usize,
+    },
+    /// One step of the plan about to run, sent for every step (including
+    /// those inside parallel branches) before the first one starts.
+    StepPlanned {
+        step: String,
+        description: String,
+    },
+    StepStarted {
+        step: String,
+        description: String,
+    },
+    Output {
+        step: String,
+        stream: OutputStream,
+        line: String,
+    },
+    /// A child process started by the step has ended. `exit_code` is `None`
+    /// if it was killed, by a signal or for running past its timeout.
+    ProcessExited {
+        step: String,
+        command: String,
+        exit_code: Option<i32>,
+        duration_ms: u64,
+    },
+    StepFinished {
+        step: String,
+        duration_ms: u64,
+    },
+    /// The step was not run because its result is already in place.
+    StepSkipped {
+        step: String,
+        reason: String,
+    },
+    /// A link step put its file in place, with `used` differing from
+    /// `requested` when that mode failed and a fallback was taken.
+    StepLinked {
+        step: String,
+        requested: LinkMode,
+        used: LinkMode,
+    },
+    /// A network step failed and will be attempted again after `delay_ms`.
+    StepRetrying {
+        step: String,
+        /// The attempt that just failed, starting at 1.
+        attempt: u32,
+        attempts: u32,
+        exit_code: Option<i32>,
+        delay_ms: u64,
+    },
+    StepFailed {
+        step: String,
+        duration_ms: u64,
+        error: String,
+    },
+    PlanFinished {
+        success: bool,
+        duration_ms: u64,
+    },
+}
+
+/// Receives events while the executor runs a plan. Events from parallel
+/// branches arrive on worker threads, so implementations must be thread-safe.
+pub trait ExecObserver: Send + Sync {
+    fn on_event(&self, event: &ExecEvent);
+
+    /// Whether this observer shows progress in place of the executor's plain
+    /// text output. Observers that only record events, like a log file,
+    /// return `false` so the terminal output stays as it is.
+    fn renders_output(&self) -> bool {
+        true
+    }
+}
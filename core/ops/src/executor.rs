@@ -1,27 +1,136 @@
 use anyhow::{bail, Result};
-use hymod_core_plan::{Plan, Step};
+use core_error::HymodError;
+use hymod_core_plan::{LinkMode, Plan, SshOptions, Step, StepEnv};
 use std::collections::HashMap;
+use std::io;
 use std::mem::discriminant;
 use std::mem::Discriminant;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use crate::backend::{FileSystem, OsFileSystem, ProcessRunner, SystemProcessRunner};
+use crate::cancel::CancelToken;
+use crate::context::{ExecContext, Verbosity};
+use crate::diff;
+use crate::events::{ExecEvent, ExecObserver};
 use crate::handlers;
+use crate::preflight;
 use crate::traits::OpHandler;
 
+/// Marker appended to steps whose result is already in place.
+const UP_TO_DATE: &str = "up-to-date";
+
+/// How a step that did not fail ended.
+enum StepOutcome {
+    Done,
+    UpToDate,
+}
+
+/// Result of one parallel branch, together with the context holding its journal.
+type BranchOutcome = Mutex<Option<(Result<()>, ExecContext)>>;
+
 pub struct Executor {
     dry_run: bool,
+    verbosity: Verbosity,
+    jobs: usize,
+    observers: Vec<Arc<dyn ExecObserver>>,
+    fs: Arc<dyn FileSystem>,
+    runner: Arc<dyn ProcessRunner>,
+    /// Timeouts for steps that do not set `timeout_secs`, keyed by step op.
+    default_timeouts: HashMap<String, Duration>,
+    cancel: CancelToken,
+    fsync: bool,
+    diff: bool,
     handlers: HashMap<Discriminant<Step>, Box<dyn OpHandler>>,
+    /// Handlers for `Step::Custom`, keyed by the step's `kind`.
+    custom_handlers: HashMap<String, Box<dyn OpHandler>>,
 }
 
 impl Executor {
     pub fn new(dry_run: bool) -> Self {
         let mut executor = Self {
             dry_run,
+            verbosity: Verbosity::default(),
+            jobs: default_jobs(),
+            observers: Vec::new(),
+            fs: Arc::new(OsFileSystem),
+            runner: Arc::new(SystemProcessRunner),
+            default_timeouts: HashMap::new(),
+            cancel: CancelToken::default(),
+            fsync: false,
+            diff: false,
             handlers: HashMap::new(),
+            custom_handlers: HashMap::new(),
         };
         executor.register_defaults();
         executor
     }
 
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Maximum number of `Step::Parallel` branches run at the same time.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Report step progress and child output to `observer` while plans run.
+    /// Observed child output is no longer printed by the executor itself.
+    pub fn with_observer(mut self, observer: Arc<dyn ExecObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Run the built-in handlers against `fs` instead of the real filesystem.
+    pub fn with_file_system(mut self, fs: Arc<dyn FileSystem>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// Start child processes through `runner` instead of spawning them directly.
+    pub fn with_process_runner(mut self, runner: Arc<dyn ProcessRunner>) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Kill child processes of `op` steps (e.g. `ssh_run`) that run longer
+    /// than `timeout`, unless the step sets its own `timeout_secs`.
+    pub fn with_default_timeout(mut self, op: impl Into<String>, timeout: Duration) -> Self {
+        self.default_timeouts.insert(op.into(), timeout);
+        self
+    }
+
+    /// Stop running plans once `cancel` is cancelled: the running child is
+    /// killed, no further steps start and the plan is rolled back.
+    pub fn with_cancel_token(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Flush files to disk before they are renamed into place.
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    /// In dry-run mode, also show how each write would change the files on disk.
+    pub fn with_diff(mut self, diff: bool) -> Self {
+        self.diff = diff;
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
     fn register_defaults(&mut self) {
         // Run
         self.register(
@@ -29,6 +138,8 @@ impl Executor {
                 cmd: String::new(),
                 args: vec![],
                 cwd: None,
+                env: StepEnv::new(),
+                timeout_secs: None,
             },
             Box::new(handlers::run_process::RunProcessHandler),
         );
@@ -62,11 +173,18 @@ impl Executor {
             Box::new(handlers::fs::copy_file::CopyFileHandler),
         );
         self.register(
-            Step::Symlink {
+            Step::RemoveFile {
+                path: String::new(),
+            },
+            Box::new(handlers::fs::remove_file::RemoveFileHandler),
+        );
+        self.register(
+            Step::Link {
                 from: String::new(),
                 to: String::new(),
+                mode: LinkMode::default(),
             },
-            Box::new(handlers::fs::symlink::SymlinkHandler),
+            Box::new(handlers::fs::link::LinkHandler),
         );
 
         // Net
@@ -75,6 +193,9 @@ impl Executor {
                 local: String::new(),
                 remote: String::new(),
                 opts: String::new(),
+                ssh: SshOptions::default(),
+                retry: None,
+                timeout_secs: None,
             },
             Box::new(handlers::net::upload_rsync::UploadRsyncHandler),
         );
@@ -82,6 +203,10 @@ impl Executor {
             Step::UploadScp {
                 local: String::new(),
                 remote: String::new(),
+                opts: String::new(),
+                ssh: SshOptions::default(),
+                retry: None,
+                timeout_secs: None,
             },
             Box::new(handlers::net::upload_scp::UploadScpHandler),
         );
@@ -90,6 +215,10 @@ impl Executor {
                 host: String::new(),
                 user: String::new(),
                 cmd: String::new(),
+                env: StepEnv::new(),
+                ssh: SshOptions::default(),
+                retry: None,
+                timeout_secs: None,
             },
             Box::new(handlers::net::ssh_run::SshRunHandler),
         );
@@ -104,36 +233,381 @@ impl Executor {
         self.register(
             Step::CheckZip {
                 path: String::new(),
+                require: Vec::new(),
+                require_main_class: false,
             },
             Box::new(handlers::check::check_zip::CheckZipHandler),
         );
     }
 
-    fn register(&mut self, step_variant: Step, handler: Box<dyn OpHandler>) {
-        self.handlers.insert(discriminant(&step_variant), handler);
+    /// Run every step of the same variant as `step_variant` with `handler`,
+    /// replacing any built-in handler. For `Step::Custom` the handler is bound
+    /// to the sample's `kind` only; the payload is ignored.
+    pub fn register(&mut self, step_variant: Step, handler: Box<dyn OpHandler>) {
+        match step_variant {
+            Step::Custom { kind, .. } => {
+                self.custom_handlers.insert(kind, handler);
+            }
+            _ => {
+                self.handlers.insert(discriminant(&step_variant), handler);
+            }
+        }
+    }
+
+    /// Run `Step::Custom` steps of the given `kind` with `handler`.
+    pub fn register_custom(&mut self, kind: impl Into<String>, handler: Box<dyn OpHandler>) {
+        self.custom_handlers.insert(kind.into(), handler);
+    }
+
+    /// Builder form of `register_custom`.
+    pub fn with_custom_handler(
+        mut self,
+        kind: impl Into<String>,
+        handler: Box<dyn OpHandler>,
+    ) -> Self {
+        self.register_custom(kind, handler);
+        self
+    }
+
+    fn handler_for(&self, step: &Step) -> Option<&dyn OpHandler> {
+        let handler = match step {
+            Step::Custom { kind, .. } => self.custom_handlers.get(kind),
+            _ => self.handlers.get(&discriminant(step)),
+        };
+        handler.map(|handler| handler.as_ref())
+    }
+
+    /// The step as shown in plans and events, as rendered by its handler.
+    fn describe(&self, step: &Step) -> String {
+        self.handler_for(step)
+            .and_then(|handler| handler.describe(step))
+            .unwrap_or_else(|| step.to_string())
+    }
+
+    /// What a dry run of `plan` prints: its preflight checks and its steps,
+    /// each as described by its handler.
+    pub fn dry_run_listing(&self, plan: &Plan) -> String {
+        let mut out = String::new();
+        if !plan.preflight.is_empty() {
+            out.push_str("PREFLIGHT\n");
+            for check in &plan.preflight {
+                out.push_str(&format!("  - {}\n", check));
+            }
+        }
+        out.push_str("PLAN\n");
+        let ctx = self.context();
+        self.write_steps(&mut out, &plan.steps, &ctx, 2, "");
+        out
     }
 
     pub fn execute(&self, plan: &Plan) -> Result<()> {
         if self.dry_run {
-            println!("PLAN");
-            for (i, step) in plan.steps.iter().enumerate() {
-                println!("  {}. {}", i + 1, step);
-            }
+            print!("{}", self.dry_run_listing(plan));
             return Ok(());
         }
 
-        for step in &plan.steps {
-            self.execute_step(step)?;
+        // Nothing has changed yet, so there is nothing to roll back.
+        let ctx = self.context().with_observers(self.observers.clone());
+        let problems = preflight::check_all(&plan.preflight, &ctx);
+        if !problems.is_empty() {
+            ctx.emit(ExecEvent::PreflightFailed {
+                problems: problems.clone(),
+            });
+            return Err(HymodError::PreflightFailed { problems }.into());
+        }
+
+        let started = Instant::now();
+        ctx.emit(ExecEvent::PlanStarted {
+            steps: plan.steps.len(),
+        });
+        if ctx.has_observers() {
+            self.emit_planned(&plan.steps, &ctx, "");
+        }
+
+        let result = self.execute_steps(&plan.steps, &ctx, "");
+        if result.is_err() {
+            self.rollback(&ctx);
+        }
+
+        ctx.emit(ExecEvent::PlanFinished {
+            success: result.is_ok(),
+            duration_ms: elapsed_ms(started),
+        });
+        result
+    }
+
+    fn emit_planned(&self, steps: &[Step], ctx: &ExecContext, prefix: &str) {
+        for (i, step) in steps.iter().enumerate() {
+            let label = format!("{}{}", prefix, i + 1);
+            ctx.emit(ExecEvent::StepPlanned {
+                step: label.clone(),
+                description: self.describe(step),
+            });
+            if let Step::Parallel { branches } = step {
+                for (b, branch) in branches.iter().enumerate() {
+                    self.emit_planned(branch, ctx, &format!("{}.{}.", label, b + 1));
+                }
+            }
+        }
+    }
+
+    fn context(&self) -> ExecContext {
+        ExecContext::new(self.verbosity)
+            .with_backends(self.fs.clone(), self.runner.clone())
+            .with_cancel_token(self.cancel.clone())
+            .with_fsync(self.fsync)
+    }
+
+    /// Undo the changes recorded so far, newest first, after a failed step.
+    fn rollback(&self, ctx: &ExecContext) {
+        if ctx.journal.is_empty() {
+            return;
+        }
+        if self.verbosity != Verbosity::Quiet {
+            eprintln!("Rolling back {} change(s)...", ctx.journal.len());
+        }
+        for failure in ctx.journal.rollback(ctx.fs()) {
+            eprintln!("{}", failure);
+        }
+    }
+
+    fn execute_steps(&self, steps: &[Step], ctx: &ExecContext, prefix: &str) -> Result<()> {
+        for (i, step) in steps.iter().enumerate() {
+            let label = format!("{}{}", prefix, i + 1);
+            self.execute_step(step, ctx, &label)?;
         }
         Ok(())
     }
 
-    fn execute_step(&self, step: &Step) -> Result<()> {
-        let key = discriminant(step);
-        if let Some(handler) = self.handlers.get(&key) {
-            handler.handle(step)
-        } else {
+    fn execute_step(&self, step: &Step, ctx: &ExecContext, label: &str) -> Result<()> {
+        ctx.set_current_step(label);
+        ctx.set_step_timeout(self.timeout_for(step));
+        ctx.set_secrets(step.secret_values().into_iter().map(String::from).collect());
+        ctx.emit(ExecEvent::StepStarted {
+            step: label.to_string(),
+            description: self.describe(step),
+        });
+        let started = Instant::now();
+
+        let result = self
+            .dispatch(step, ctx, label)
+            .map_err(|err| self.classify_failure(err, step, ctx, label));
+
+        let duration_ms = elapsed_ms(started);
+        match &result {
+            Ok(StepOutcome::Done) => ctx.emit(ExecEvent::StepFinished {
+                step: label.to_string(),
+                duration_ms,
+            }),
+            Ok(StepOutcome::UpToDate) => {
+                if !ctx.is_observed() && ctx.verbosity != Verbosity::Quiet {
+                    println!("  {}. {}    ({})", label, self.describe(step), UP_TO_DATE);
+                }
+                ctx.emit(ExecEvent::StepSkipped {
+                    step: label.to_string(),
+                    reason: UP_TO_DATE.to_string(),
+                });
+            }
+            Err(err) => ctx.emit(ExecEvent::StepFailed {
+                step: label.to_string(),
+                duration_ms,
+                error: ctx.redact(&format!("{:#}", err)),
+            }),
+        }
+        result.map(|_| ())
+    }
+
+    fn dispatch(&self, step: &Step, ctx: &ExecContext, label: &str) -> Result<StepOutcome> {
+        if ctx.is_cancelled() {
+            bail!(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+        if let Step::Parallel { branches } = step {
+            return self
+                .execute_parallel(branches, ctx, label)
+                .map(|()| StepOutcome::Done);
+        }
+
+        let Some(handler) = self.handler_for(step) else {
+            if let Step::Custom { kind, .. } = step {
+                bail!("No handler registered for custom step kind '{}'", kind);
+            }
             bail!("No handler registered for step: {:?}", step);
+        };
+        if handler.is_up_to_date(step, ctx) {
+            return Ok(StepOutcome::UpToDate);
         }
+        handler.handle(step, ctx)?;
+        Ok(StepOutcome::Done)
     }
+
+    fn timeout_for(&self, step: &Step) -> Option<Duration> {
+        step.timeout_secs()
+            .map(Duration::from_secs)
+            .or_else(|| self.default_timeouts.get(step.op()).copied())
+    }
+
+    /// Replace the bare io error left by a killed child with a typed error
+    /// naming the step. Errors from nested steps are already typed.
+    fn classify_failure(
+        &self,
+        err: anyhow::Error,
+        step: &Step,
+        ctx: &ExecContext,
+        label: &str,
+    ) -> anyhow::Error {
+        if matches!(
+            err.downcast_ref::<HymodError>(),
+            Some(HymodError::Interrupted { .. } | HymodError::StepTimedOut { .. })
+        ) {
+            return err;
+        }
+        let name = format!(
+            "{}. {}",
+            label,
+            self.describe(step)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        if ctx.is_cancelled() {
+            return HymodError::Interrupted { step: name }.into();
+        }
+        let timed_out = err.chain().any(|cause| {
+            cause
+                .downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == io::ErrorKind::TimedOut)
+        });
+        match ctx.step_timeout() {
+            Some(timeout) if timed_out => HymodError::StepTimedOut {
+                step: name,
+                op: step.op().to_string(),
+                timeout_secs: timeout.as_secs(),
+            }
+            .into(),
+            _ => err,
+        }
+    }
+
+    /// Dry-run check for steps that are already in place. Remote steps are
+    /// never checked here, since a dry run must not connect to any host.
+    fn is_up_to_date(&self, step: &Step, ctx: &ExecContext) -> bool {
+        if matches!(
+            step,
+            Step::UploadRsync { .. } | Step::UploadScp { .. } | Step::SshRun { .. }
+        ) {
+            return false;
+        }
+        self.handler_for(step)
+            .is_some_and(|handler| handler.is_up_to_date(step, ctx))
+    }
+
+    fn write_steps(
+        &self,
+        out: &mut String,
+        steps: &[Step],
+        ctx: &ExecContext,
+        indent: usize,
+        prefix: &str,
+    ) {
+        for (i, step) in steps.iter().enumerate() {
+            let label = format!("{}{}", prefix, i + 1);
+            let description = self.describe(step);
+            if self.is_up_to_date(step, ctx) {
+                out.push_str(&format!(
+                    "{}{}. {}    ({})\n",
+                    " ".repeat(indent),
+                    label,
+                    description,
+                    UP_TO_DATE
+                ));
+            } else {
+                out.push_str(&format!(
+                    "{}{}. {}\n",
+                    " ".repeat(indent),
+                    label,
+                    description
+                ));
+                if let Some(change) = self
+                    .diff
+                    .then(|| diff::describe_change(step, ctx))
+                    .flatten()
+                {
+                    for line in change.lines() {
+                        out.push_str(&format!("{}{}\n", " ".repeat(indent + 4), line));
+                    }
+                }
+            }
+            if let Step::Parallel { branches } = step {
+                for (b, branch) in branches.iter().enumerate() {
+                    self.write_steps(
+                        out,
+                        branch,
+                        ctx,
+                        indent + 3,
+                        &format!("{}.{}.", label, b + 1),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Run the branches of a parallel stage on at most `self.jobs` threads.
+    /// Once a branch fails no new branches are started; the first error (in
+    /// branch order) is returned after all running branches have finished.
+    fn execute_parallel(
+        &self,
+        branches: &[Vec<Step>],
+        ctx: &ExecContext,
+        label: &str,
+    ) -> Result<()> {
+        let next = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let outcomes: Vec<BranchOutcome> = branches.iter().map(|_| Mutex::new(None)).collect();
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.jobs.min(branches.len()) {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    if i >= branches.len() || failed.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let branch_label = format!("{}.{}", label, i + 1);
+                    let branch_ctx = ctx.branch(&branch_label);
+                    let result = self.execute_steps(
+                        &branches[i],
+                        &branch_ctx,
+                        &format!("{}.", branch_label),
+                    );
+                    if result.is_err() {
+                        failed.store(true, Ordering::SeqCst);
+                    }
+                    *outcomes[i].lock().unwrap() = Some((result, branch_ctx));
+                });
+            }
+        });
+
+        let mut first_err = None;
+        for outcome in outcomes {
+            if let Some((result, branch_ctx)) = outcome.into_inner().unwrap() {
+                ctx.journal.absorb(branch_ctx.journal);
+                if let Err(err) = result {
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        match first_err {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
 }
@@ -1,3 +1,4 @@
+use crate::context::ExecContext;
 use crate::traits::OpHandler;
 use anyhow::{bail, Result};
 use hymod_core_plan::Step;
@@ -6,9 +7,9 @@ use std::path::Path;
 pub struct CheckFileHandler;
 
 impl OpHandler for CheckFileHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
         if let Step::CheckFile { path } = step {
-            if !Path::new(path).exists() {
+            if !ctx.fs().exists(Path::new(path)) {
                 bail!("File not found: {}", path);
             }
             Ok(())
@@ -1,19 +1,92 @@
+use crate::context::ExecContext;
 use crate::traits::OpHandler;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use hymod_core_plan::Step;
+use std::io::{self, Cursor, Read};
 use std::path::Path;
+use zip::ZipArchive;
+
+const MANIFEST: &str = "manifest.json";
 
 pub struct CheckZipHandler;
 
 impl OpHandler for CheckZipHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
-        if let Step::CheckZip { path } = step {
-            if !Path::new(path).exists() {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
+        if let Step::CheckZip {
+            path,
+            require,
+            require_main_class,
+        } = step
+        {
+            if !ctx.fs().exists(Path::new(path)) {
                 bail!("Zip artifact not found: {}", path);
             }
+            let bytes = ctx
+                .fs()
+                .read(Path::new(path))
+                .with_context(|| format!("Failed to read zip artifact {}", path))?;
+            let mut archive = ZipArchive::new(Cursor::new(bytes))
+                .with_context(|| format!("Zip artifact is not a valid archive: {}", path))?;
+            verify_entries(&mut archive)
+                .with_context(|| format!("Zip artifact is corrupt: {}", path))?;
+
+            for entry in require {
+                if archive.by_name(entry).is_err() {
+                    bail!(
+                        "Zip artifact {} is missing required entry '{}'",
+                        path,
+                        entry
+                    );
+                }
+            }
+            if *require_main_class {
+                let class = main_class_entry(&mut archive, path)?;
+                if archive.by_name(&class).is_err() {
+                    bail!(
+                        "Zip artifact {} is missing main class entry '{}' named in {}",
+                        path,
+                        class,
+                        MANIFEST
+                    );
+                }
+            }
             Ok(())
         } else {
             panic!("CheckZipHandler called with non-CheckZip step");
         }
     }
 }
+
+/// Decompress every entry; the zip reader checks each entry's CRC once it
+/// has been read to the end.
+fn verify_entries(archive: &mut ZipArchive<Cursor<Vec<u8>>>) -> Result<()> {
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let name = entry.name().to_string();
+        io::copy(&mut entry, &mut io::sink())
+            .with_context(|| format!("entry '{}' failed its CRC check", name))?;
+    }
+    Ok(())
+}
+
+/// Entry name of the class `manifest.json` names as `Main`, e.g.
+/// `com/example/Main.class` for `com.example.Main`.
+fn main_class_entry(archive: &mut ZipArchive<Cursor<Vec<u8>>>, path: &str) -> Result<String> {
+    let Ok(mut manifest) = archive.by_name(MANIFEST) else {
+        bail!(
+            "Zip artifact {} is missing required entry '{}'",
+            path,
+            MANIFEST
+        );
+    };
+    let mut content = String::new();
+    manifest
+        .read_to_string(&mut content)
+        .with_context(|| format!("Failed to read {} in {}", MANIFEST, path))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid {} in {}", MANIFEST, path))?;
+    let Some(main) = json.get("Main").and_then(|value| value.as_str()) else {
+        bail!("{} in {} does not name a Main class", MANIFEST, path);
+    };
+    Ok(format!("{}.class", main.replace('.', "/")))
+}
@@ -1,21 +1,36 @@
+use crate::atomic::write_atomically;
+use crate::context::ExecContext;
+use crate::digest::same_file_content;
 use crate::traits::OpHandler;
 use anyhow::{Context, Result};
 use hymod_core_plan::Step;
-use std::fs;
 use std::path::Path;
 
 pub struct CopyFileHandler;
 
 impl OpHandler for CopyFileHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
         if let Step::CopyFile { from, to } = step {
             if let Some(parent) = Path::new(to).parent() {
-                fs::create_dir_all(parent)?;
+                ctx.journal.create_dir_all(ctx.fs(), parent)?;
             }
-            fs::copy(from, to).context(format!("Failed to copy {} to {}", from, to))?;
+            ctx.journal.prepare_write(ctx.fs(), Path::new(to))?;
+            write_atomically(ctx, Path::new(to), |temp| {
+                ctx.fs().copy(Path::new(from), temp)
+            })
+            .context(format!("Failed to copy {} to {}", from, to))?;
             Ok(())
         } else {
             panic!("CopyFileHandler called with non-CopyFile step");
         }
     }
+
+    fn is_up_to_date(&self, step: &Step, ctx: &ExecContext) -> bool {
+        match step {
+            Step::CopyFile { from, to } => {
+                same_file_content(ctx.fs(), Path::new(from), Path::new(to))
+            }
+            _ => false,
+        }
+    }
 }
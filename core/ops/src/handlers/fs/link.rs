@@ -0,0 +1,116 @@
+use crate::atomic::{temp_sibling, write_atomically};
+use crate::backend::{EntryKind, FileSystem};
+use crate::context::{ExecContext, Verbosity};
+use crate::digest::same_file_content;
+use crate::events::ExecEvent;
+use crate::traits::OpHandler;
+use anyhow::{anyhow, bail, Result};
+use hymod_core_plan::{LinkMode, Step};
+use std::io;
+use std::path::Path;
+
+pub struct LinkHandler;
+
+impl OpHandler for LinkHandler {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
+        if let Step::Link { from, to, mode } = step {
+            let (from, link) = (Path::new(from), Path::new(to));
+            if ctx.fs().entry_kind(link) == Some(EntryKind::Dir) {
+                bail!("Refusing to replace directory {} with a link", to);
+            }
+            if let Some(parent) = link.parent() {
+                ctx.journal.create_dir_all(ctx.fs(), parent)?;
+            }
+            ctx.journal.prepare_write(ctx.fs(), link)?;
+
+            let mut failures = Vec::new();
+            for &attempt in mode.fallback_chain() {
+                match link_as(ctx, attempt, from, link) {
+                    Ok(()) => {
+                        report_link(ctx, *mode, attempt, from, link);
+                        return Ok(());
+                    }
+                    Err(e) => failures.push(format!("{}: {}", attempt, e)),
+                }
+            }
+            Err(anyhow!(
+                "Failed to link {} -> {} ({})",
+                to,
+                from.display(),
+                failures.join("; ")
+            ))
+        } else {
+            panic!("LinkHandler called with non-Link step");
+        }
+    }
+
+    fn is_up_to_date(&self, step: &Step, ctx: &ExecContext) -> bool {
+        match step {
+            Step::Link { from, to, mode } => mode
+                .fallback_chain()
+                .iter()
+                .any(|&mode| is_linked_as(ctx.fs(), mode, Path::new(from), Path::new(to))),
+            _ => false,
+        }
+    }
+}
+
+/// Put `link` in place as `mode`, through a temporary sibling so an existing
+/// file is only replaced once the new entry has been created.
+fn link_as(ctx: &ExecContext, mode: LinkMode, from: &Path, link: &Path) -> io::Result<()> {
+    if mode == LinkMode::Copy {
+        return write_atomically(ctx, link, |temp| ctx.fs().copy(from, temp));
+    }
+
+    let fs = ctx.fs();
+    let temp = temp_sibling(link);
+    if fs.entry_kind(&temp).is_some() {
+        fs.remove_file(&temp)?;
+    }
+    let created = match mode {
+        LinkMode::Symlink => fs.symlink(from, &temp),
+        _ => fs.hard_link(from, &temp),
+    };
+    let result = created.and_then(|()| fs.rename(&temp, link));
+    if result.is_err() && fs.entry_kind(&temp).is_some() {
+        let _ = fs.remove_file(&temp);
+    }
+    result
+}
+
+fn is_linked_as(fs: &dyn FileSystem, mode: LinkMode, from: &Path, link: &Path) -> bool {
+    match mode {
+        LinkMode::Symlink => {
+            fs.entry_kind(link) == Some(EntryKind::Symlink)
+                && fs.read_link(link).is_ok_and(|target| target == from)
+        }
+        LinkMode::Hardlink | LinkMode::Copy => {
+            fs.entry_kind(link) == Some(EntryKind::File) && same_file_content(fs, from, link)
+        }
+    }
+}
+
+fn report_link(ctx: &ExecContext, requested: LinkMode, used: LinkMode, from: &Path, link: &Path) {
+    ctx.emit(ExecEvent::StepLinked {
+        step: ctx.current_step(),
+        requested,
+        used,
+    });
+    if requested == used || ctx.is_observed() || ctx.verbosity == Verbosity::Quiet {
+        return;
+    }
+
+    let prefix = ctx
+        .output_label
+        .as_deref()
+        .map(|label| format!("[{}] ", label))
+        .unwrap_or_default();
+    eprintln!(
+        "{}Could not {} {} to {}, used {} instead",
+        prefix,
+        requested,
+        link.display(),
+        from.display(),
+        used
+    );
+}
@@ -1,14 +1,17 @@
+use crate::context::ExecContext;
 use crate::traits::OpHandler;
 use anyhow::{Context, Result};
 use hymod_core_plan::Step;
-use std::fs;
+use std::path::Path;
 
 pub struct MkdirHandler;
 
 impl OpHandler for MkdirHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
         if let Step::Mkdir { path } = step {
-            fs::create_dir_all(path).context(format!("Failed to create directory {}", path))?;
+            ctx.journal
+                .create_dir_all(ctx.fs(), Path::new(path))
+                .context(format!("Failed to create directory {}", path))?;
             Ok(())
         } else {
             panic!("MkdirHandler called with non-Mkdir step");
@@ -1,5 +1,6 @@
 pub mod copy_file;
+pub mod link;
 pub mod mkdir;
-pub mod symlink;
+pub mod remove_file;
 pub mod write_bytes;
 pub mod write_file;
@@ -0,0 +1,29 @@
+use crate::context::ExecContext;
+use crate::traits::OpHandler;
+use anyhow::{Context, Result};
+use hymod_core_plan::Step;
+use std::path::Path;
+
+pub struct RemoveFileHandler;
+
+impl OpHandler for RemoveFileHandler {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
+        if let Step::RemoveFile { path } = step {
+            let path = Path::new(path);
+            ctx.journal.prepare_write(ctx.fs(), path)?;
+            ctx.fs()
+                .remove_file(path)
+                .context(format!("Failed to remove file {}", path.display()))?;
+            Ok(())
+        } else {
+            panic!("RemoveFileHandler called with non-RemoveFile step");
+        }
+    }
+
+    fn is_up_to_date(&self, step: &Step, ctx: &ExecContext) -> bool {
+        match step {
+            Step::RemoveFile { path } => ctx.fs().entry_kind(Path::new(path)).is_none(),
+            _ => false,
+        }
+    }
+}
@@ -1,21 +1,34 @@
+use crate::atomic::write_atomically;
+use crate::context::ExecContext;
+use crate::digest::{file_has_digest, sha256_bytes};
 use crate::traits::OpHandler;
 use anyhow::{Context, Result};
 use hymod_core_plan::Step;
-use std::fs;
 use std::path::Path;
 
 pub struct WriteBytesHandler;
 
 impl OpHandler for WriteBytesHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
         if let Step::WriteBytes { path, content } = step {
             if let Some(parent) = Path::new(path).parent() {
-                fs::create_dir_all(parent)?;
+                ctx.journal.create_dir_all(ctx.fs(), parent)?;
             }
-            fs::write(path, content).context(format!("Failed to write file {}", path))?;
+            ctx.journal.prepare_write(ctx.fs(), Path::new(path))?;
+            write_atomically(ctx, Path::new(path), |temp| ctx.fs().write(temp, content))
+                .context(format!("Failed to write file {}", path))?;
             Ok(())
         } else {
             panic!("WriteBytesHandler called with non-WriteBytes step");
         }
     }
+
+    fn is_up_to_date(&self, step: &Step, ctx: &ExecContext) -> bool {
+        match step {
+            Step::WriteBytes { path, content } => {
+                file_has_digest(ctx.fs(), Path::new(path), &sha256_bytes(content))
+            }
+            _ => false,
+        }
+    }
 }
@@ -1,3 +1,93 @@
 pub mod ssh_run;
 pub mod upload_rsync;
 pub mod upload_scp;
+
+use crate::atomic::temp_sibling;
+use crate::backend::ProcessSpec;
+use crate::context::ExecContext;
+use crate::process::{run_with_retry, shell_quote};
+use anyhow::{bail, Context, Result};
+use hymod_core_plan::{RetryPolicy, SshOptions};
+use std::path::Path;
+
+/// Split an scp/rsync destination `[user@]host:path` into the target for
+/// `ssh` and the path on that host. An IPv6 host is written in brackets, as
+/// in `user@[::1]:/srv`, which ssh takes without them. `None` for a local
+/// path, including a Windows one such as `C:\mods\mod.jar`.
+pub(crate) fn split_remote(remote: &str) -> Option<(String, &str)> {
+    let colon = match remote.find('[') {
+        Some(open) => open + remote[open..].find("]:")? + 1,
+        None => remote.find(':')?,
+    };
+    let (target, path) = (&remote[..colon], &remote[colon + 1..]);
+    let host = target.rsplit('@').next().unwrap_or(target);
+    let drive_letter = host.len() == 1 && host.chars().all(|c| c.is_ascii_alphabetic());
+    if host.is_empty() || drive_letter || target.contains(['/', '\\']) {
+        return None;
+    }
+    Some((target.replace(['[', ']'], ""), path))
+}
+
+/// Where an upload to `remote` (`user@host:path`) is written first: a
+/// temporary sibling of `path` on the same host. `None` for a local `remote`.
+pub(crate) fn staging_remote(remote: &str) -> Option<String> {
+    let (_, path) = split_remote(remote)?;
+    let target = &remote[..remote.len() - path.len()];
+    Some(format!("{}{}", target, staging_path(path)))
+}
+
+/// Copy the file at `remote` to its staging path on the same host, if it
+/// exists, so rsync can send only the changes against it. Copying is best
+/// effort: without a seed rsync sends the whole file.
+pub(crate) fn seed_staging(ctx: &ExecContext, remote: &str, ssh: &SshOptions) -> Result<()> {
+    let Some((target, path)) = split_remote(remote) else {
+        return Ok(());
+    };
+    let cmd = format!(
+        "test ! -f {path} || cp -f -- {path} {staging}",
+        path = shell_quote(path),
+        staging = shell_quote(&staging_path(path))
+    );
+    let spec = ProcessSpec::new("ssh")
+        .args(ssh.ssh_args())
+        .arg(target)
+        .arg(cmd);
+    ctx.runner()
+        .run(&spec, ctx)
+        .context("Failed to run ssh command")?;
+    Ok(())
+}
+
+/// Rename a finished upload from its staging path over `remote` with `mv`
+/// through SSH, so the server never sees a partly uploaded file.
+pub(crate) fn move_into_place(
+    ctx: &ExecContext,
+    remote: &str,
+    ssh: &SshOptions,
+    retry: Option<&RetryPolicy>,
+) -> Result<()> {
+    let Some((target, path)) = split_remote(remote) else {
+        return Ok(());
+    };
+    let mut cmd = format!(
+        "mv -f -- {} {}",
+        shell_quote(&staging_path(path)),
+        shell_quote(path)
+    );
+    if ctx.fsync() {
+        cmd = format!("sync && {}", cmd);
+    }
+    let spec = ProcessSpec::new("ssh")
+        .args(ssh.ssh_args())
+        .arg(target)
+        .arg(cmd);
+    let status = run_with_retry(&spec, retry, ctx).context("Failed to run ssh command")?;
+    if !status.success() {
+        bail!("Failed to move the upload into place at {}", remote);
+    }
+    Ok(())
+}
+
+fn staging_path(path: &str) -> String {
+    temp_sibling(Path::new(path)).to_string_lossy().to_string()
+}
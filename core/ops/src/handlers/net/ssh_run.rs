@@ -1,18 +1,39 @@
+use crate::backend::ProcessSpec;
+use crate::context::ExecContext;
+use crate::process::{run_with_retry, shell_quote};
 use crate::traits::OpHandler;
 use anyhow::{bail, Context, Result};
-use hymod_core_plan::Step;
-use std::process::Command;
+use core_error::HymodError;
+use hymod_core_plan::{Step, StepEnv};
 
 pub struct SshRunHandler;
 
 impl OpHandler for SshRunHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
-        if let Step::SshRun { host, user, cmd } = step {
-            let status = Command::new("ssh")
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
+        if let Step::SshRun {
+            host,
+            user,
+            cmd,
+            env,
+            ssh,
+            retry,
+            ..
+        } = step
+        {
+            let spec = ProcessSpec::new("ssh")
+                .args(ssh.ssh_args())
                 .arg(format!("{}@{}", user, host))
-                .arg(cmd)
-                .status()
-                .context("Failed to run ssh command")?;
+                .arg(remote_command(cmd, env)?);
+            let status =
+                run_with_retry(&spec, retry.as_ref(), ctx).context("Failed to run ssh command")?;
+            // ssh reserves 255 for its own failures, e.g. the host refusing the connection.
+            if status.code == Some(255) {
+                return Err(HymodError::RemoteUnreachable {
+                    host: host.clone(),
+                    reason: "ssh exited with status 255".to_string(),
+                }
+                .into());
+            }
             if !status.success() {
                 bail!("Ssh command failed");
             }
@@ -22,3 +43,27 @@ impl OpHandler for SshRunHandler {
         }
     }
 }
+
+/// `cmd` preceded by an `export` of every variable in `env`, since ssh does
+/// not forward the local environment to the remote shell.
+fn remote_command(cmd: &str, env: &StepEnv) -> Result<String> {
+    if env.is_empty() {
+        return Ok(cmd.to_string());
+    }
+    let mut exports = Vec::new();
+    for (name, value) in env {
+        if !is_env_name(name) {
+            bail!("Invalid environment variable name '{}'", name);
+        }
+        exports.push(format!("{}={}", name, shell_quote(value.value())));
+    }
+    Ok(format!("export {}; {}", exports.join(" "), cmd))
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
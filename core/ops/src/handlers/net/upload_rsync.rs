@@ -1,30 +1,58 @@
+use crate::backend::ProcessSpec;
+use crate::context::ExecContext;
+use crate::digest::remote_matches;
+use crate::handlers::net::{move_into_place, seed_staging, staging_remote};
+use crate::process::run_with_retry;
 use crate::traits::OpHandler;
 use anyhow::{bail, Context, Result};
 use hymod_core_plan::Step;
-use std::process::Command;
+use std::path::Path;
 
 pub struct UploadRsyncHandler;
 
 impl OpHandler for UploadRsyncHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
         if let Step::UploadRsync {
             local,
             remote,
             opts,
+            ssh,
+            retry,
+            ..
         } = step
         {
-            let status = Command::new("rsync")
-                .arg(opts)
-                .arg(local)
-                .arg(remote)
-                .status()
-                .context("Failed to run rsync")?;
+            let staging = staging_remote(remote);
+            let destination = staging.as_deref().unwrap_or(remote);
+            if staging.is_some() {
+                // Rsync compares against the file at its destination, and a
+                // fresh staging path has none.
+                seed_staging(ctx, remote, ssh)?;
+            }
+            let mut spec = ProcessSpec::new("rsync").args(opts.split_whitespace());
+            if let Some(shell) = ssh.rsync_shell() {
+                spec = spec.args(["-e".to_string(), shell]);
+            }
+            let spec = spec.arg(local).arg(destination);
+            let status =
+                run_with_retry(&spec, retry.as_ref(), ctx).context("Failed to run rsync")?;
             if !status.success() {
                 bail!("Rsync failed");
             }
+            if staging.is_some() {
+                move_into_place(ctx, remote, ssh, retry.as_ref())?;
+            }
             Ok(())
         } else {
             panic!("UploadRsyncHandler called with non-UploadRsync step");
         }
     }
+
+    fn is_up_to_date(&self, step: &Step, ctx: &ExecContext) -> bool {
+        match step {
+            Step::UploadRsync {
+                local, remote, ssh, ..
+            } => remote_matches(ctx, Path::new(local), remote, ssh),
+            _ => false,
+        }
+    }
 }
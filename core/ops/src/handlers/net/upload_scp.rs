@@ -1,24 +1,52 @@
+use crate::backend::ProcessSpec;
+use crate::context::ExecContext;
+use crate::digest::remote_matches;
+use crate::handlers::net::{move_into_place, staging_remote};
+use crate::process::run_with_retry;
 use crate::traits::OpHandler;
 use anyhow::{bail, Context, Result};
 use hymod_core_plan::Step;
-use std::process::Command;
+use std::path::Path;
 
 pub struct UploadScpHandler;
 
 impl OpHandler for UploadScpHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
-        if let Step::UploadScp { local, remote } = step {
-            let status = Command::new("scp")
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
+        if let Step::UploadScp {
+            local,
+            remote,
+            opts,
+            ssh,
+            retry,
+            ..
+        } = step
+        {
+            let staging = staging_remote(remote);
+            let destination = staging.as_deref().unwrap_or(remote);
+            let spec = ProcessSpec::new("scp")
+                .args(ssh.scp_args())
+                .args(opts.split_whitespace())
                 .arg(local)
-                .arg(remote)
-                .status()
-                .context("Failed to run scp")?;
+                .arg(destination);
+            let status = run_with_retry(&spec, retry.as_ref(), ctx).context("Failed to run scp")?;
             if !status.success() {
                 bail!("Scp failed");
             }
+            if staging.is_some() {
+                move_into_place(ctx, remote, ssh, retry.as_ref())?;
+            }
             Ok(())
         } else {
             panic!("UploadScpHandler called with non-UploadScp step");
         }
     }
+
+    fn is_up_to_date(&self, step: &Step, ctx: &ExecContext) -> bool {
+        match step {
+            Step::UploadScp {
+                local, remote, ssh, ..
+            } => remote_matches(ctx, Path::new(local), remote, ssh),
+            _ => false,
+        }
+    }
 }
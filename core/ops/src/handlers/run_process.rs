@@ -1,20 +1,31 @@
+use crate::backend::ProcessSpec;
+use crate::context::ExecContext;
 use crate::traits::OpHandler;
 use anyhow::{Context, Result};
 use hymod_core_plan::Step;
-use std::process::Command;
 
 pub struct RunProcessHandler;
 
 impl OpHandler for RunProcessHandler {
-    fn handle(&self, step: &Step) -> Result<()> {
-        if let Step::RunProcess { cmd, args, cwd } = step {
-            let mut command = Command::new(cmd);
-            command.args(args);
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()> {
+        if let Step::RunProcess {
+            cmd,
+            args,
+            cwd,
+            env,
+            ..
+        } = step
+        {
+            let mut spec = ProcessSpec::new(cmd).args(args);
             if let Some(dir) = cwd {
-                command.current_dir(dir);
+                spec = spec.cwd(dir);
             }
-            let status = command
-                .status()
+            for (name, value) in env {
+                spec = spec.env(name, value.value());
+            }
+            let status = ctx
+                .runner()
+                .run(&spec, ctx)
                 .context(format!("Failed to execute {}", cmd))?;
             if !status.success() {
                 anyhow::bail!("Command failed: {} {}", cmd, args.join(" "));
@@ -0,0 +1,136 @@
+use crate::backend::{EntryKind, FileSystem};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A compensating action that reverts one filesystem change made by a handler.
+#[derive(Debug)]
+pub enum UndoAction {
+    /// A file (or symlink) that did not exist before the step ran.
+    RemoveFile(PathBuf),
+    /// A directory that did not exist before the step ran.
+    RemoveDir(PathBuf),
+    /// A regular file that was overwritten; `content` is what it held before.
+    RestoreFile { path: PathBuf, content: Vec<u8> },
+    /// A symlink that was replaced; `target` is where it pointed before.
+    RestoreSymlink { path: PathBuf, target: PathBuf },
+}
+
+impl UndoAction {
+    fn undo(&self, fs: &dyn FileSystem) -> io::Result<()> {
+        match self {
+            UndoAction::RemoveFile(path) => remove_if_present(fs, path),
+            UndoAction::RemoveDir(path) => match fs.remove_dir(path) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                other => other,
+            },
+            UndoAction::RestoreFile { path, content } => {
+                remove_if_present(fs, path)?;
+                fs.write(path, content)
+            }
+            UndoAction::RestoreSymlink { path, target } => {
+                remove_if_present(fs, path)?;
+                fs.symlink(target, path)
+            }
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            UndoAction::RemoveFile(path)
+            | UndoAction::RemoveDir(path)
+            | UndoAction::RestoreFile { path, .. }
+            | UndoAction::RestoreSymlink { path, .. } => path,
+        }
+    }
+}
+
+fn remove_if_present(fs: &dyn FileSystem, path: &Path) -> io::Result<()> {
+    if fs.entry_kind(path).is_some() {
+        fs.remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Undo journal for a single plan run. Handlers record what they change so the
+/// executor can unwind a partially applied plan in reverse order.
+#[derive(Debug, Default)]
+pub struct Journal {
+    actions: Mutex<Vec<UndoAction>>,
+}
+
+impl Journal {
+    pub fn record(&self, action: UndoAction) {
+        self.actions.lock().unwrap().push(action);
+    }
+
+    pub fn len(&self) -> usize {
+        self.actions.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append another journal's actions, e.g. from a finished parallel branch.
+    pub(crate) fn absorb(&self, other: Journal) {
+        let other = other.actions.into_inner().unwrap();
+        self.actions.lock().unwrap().extend(other);
+    }
+
+    /// Record how to revert a write to `path`: restore the old file or symlink,
+    /// or remove the path if nothing was there.
+    pub fn prepare_write(&self, fs: &dyn FileSystem, path: &Path) -> io::Result<()> {
+        let action = match fs.entry_kind(path) {
+            Some(EntryKind::Symlink) => UndoAction::RestoreSymlink {
+                path: path.to_path_buf(),
+                target: fs.read_link(path)?,
+            },
+            Some(EntryKind::File) => UndoAction::RestoreFile {
+                path: path.to_path_buf(),
+                content: fs.read(path)?,
+            },
+            // Directories are never overwritten by a write; let the write itself fail.
+            Some(EntryKind::Dir) => return Ok(()),
+            None => UndoAction::RemoveFile(path.to_path_buf()),
+        };
+        self.record(action);
+        Ok(())
+    }
+
+    /// `create_dir_all` that records every directory it actually creates.
+    pub fn create_dir_all(&self, fs: &dyn FileSystem, path: &Path) -> io::Result<()> {
+        let mut missing = Vec::new();
+        let mut current = Some(path);
+        while let Some(dir) = current {
+            if dir.as_os_str().is_empty() || fs.exists(dir) {
+                break;
+            }
+            missing.push(dir.to_path_buf());
+            current = dir.parent();
+        }
+
+        fs.create_dir_all(path)?;
+        for dir in missing.into_iter().rev() {
+            self.record(UndoAction::RemoveDir(dir));
+        }
+        Ok(())
+    }
+
+    /// Revert every recorded action, newest first. Returns a message for each
+    /// action that could not be undone.
+    pub(crate) fn rollback(&self, fs: &dyn FileSystem) -> Vec<String> {
+        let mut actions = self.actions.lock().unwrap();
+        let mut failures = Vec::new();
+        while let Some(action) = actions.pop() {
+            if let Err(e) = action.undo(fs) {
+                failures.push(format!(
+                    "Failed to roll back {}: {}",
+                    action.path().display(),
+                    e
+                ));
+            }
+        }
+        failures
+    }
+}
@@ -1,6 +1,25 @@
+mod atomic;
+pub mod backend;
+pub mod cancel;
+pub mod context;
+mod diff;
+mod digest;
+pub mod events;
 pub mod executor;
 pub mod handlers;
+pub mod journal;
+pub mod observers;
+mod preflight;
+mod process;
 pub mod traits;
 
+pub use backend::{
+    CannedResult, EntryKind, FileSystem, MemoryFileSystem, OsFileSystem, ProcessOutput,
+    ProcessRunner, ProcessSpec, ProcessStatus, RecordingProcessRunner, SystemProcessRunner,
+};
+pub use cancel::CancelToken;
+pub use context::{ExecContext, Verbosity};
+pub use events::{ExecEvent, ExecObserver, OutputStream};
 pub use executor::Executor;
+pub use journal::{Journal, UndoAction};
 pub use traits::OpHandler;
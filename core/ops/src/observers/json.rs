@@ -0,0 +1,16 @@
+use crate::events::{ExecEvent, ExecObserver};
+use std::io::Write;
+
+/// Writes every event to stdout as one JSON object per line.
+pub struct JsonRenderer;
+
+impl ExecObserver for JsonRenderer {
+    fn on_event(&self, event: &ExecEvent) {
+        if let Ok(line) = serde_json::to_string(event) {
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            let _ = writeln!(out, "{}", line);
+            let _ = out.flush();
+        }
+    }
+}
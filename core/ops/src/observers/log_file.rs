@@ -0,0 +1,155 @@
+use super::seconds;
+use crate::events::{ExecEvent, ExecObserver, OutputStream};
+use crate::process::describe_exit;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Records every plan run to a plain-text log file: the plan, each step's
+/// output, child exit codes and timings. The file is only created once the
+/// first event arrives, so commands that run no plan leave no log behind.
+/// It does not replace the terminal output.
+pub struct RunLogWriter {
+    path: PathBuf,
+    header: String,
+    file: Mutex<Option<File>>,
+}
+
+impl RunLogWriter {
+    /// `header` is written once at the top of the file, e.g. the command line.
+    pub fn new(path: impl Into<PathBuf>, header: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            header: header.into(),
+            file: Mutex::new(None),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether anything has been logged, i.e. whether the file exists.
+    pub fn is_written(&self) -> bool {
+        self.file.lock().unwrap().is_some()
+    }
+
+    fn write(&self, text: &str) {
+        let mut file = self.file.lock().unwrap();
+        if file.is_none() {
+            *file = self.open();
+        }
+        // Logging must never fail the run it records.
+        if let Some(file) = file.as_mut() {
+            let _ = writeln!(file, "{}", text);
+        }
+    }
+
+    fn open(&self) -> Option<File> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).ok()?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .ok()?;
+        writeln!(file, "{}", self.header.trim_end()).ok()?;
+        Some(file)
+    }
+}
+
+impl ExecObserver for RunLogWriter {
+    fn on_event(&self, event: &ExecEvent) {
+        let text = match event {
+            ExecEvent::PreflightFailed { problems } => {
+                let mut text = String::from("\n== preflight failed");
+                for problem in problems {
+                    text.push_str(&format!("\n!! {}", problem));
+                }
+                text
+            }
+            ExecEvent::PlanStarted { steps } => format!("\n== plan ({} steps)", steps),
+            ExecEvent::StepPlanned { step, description } => {
+                format!("  {}. {}", step, description.trim_end())
+            }
+            ExecEvent::StepStarted { step, description } => {
+                format!("\n== {}. {}", step, description.trim_end())
+            }
+            ExecEvent::Output {
+                step,
+                stream: OutputStream::Stdout,
+                line,
+            } => format!("[{}] {}", step, line),
+            ExecEvent::Output {
+                step,
+                stream: OutputStream::Stderr,
+                line,
+            } => format!("[{} stderr] {}", step, line),
+            ExecEvent::ProcessExited {
+                step,
+                command,
+                exit_code,
+                duration_ms,
+            } => format!(
+                "-- {}: {} `{}` after {}",
+                step,
+                describe_exit(*exit_code),
+                command,
+                seconds(*duration_ms)
+            ),
+            ExecEvent::StepFinished { step, duration_ms } => {
+                format!("-- {}: finished in {}", step, seconds(*duration_ms))
+            }
+            ExecEvent::StepSkipped { step, reason } => format!("-- {}: skipped, {}", step, reason),
+            ExecEvent::StepLinked {
+                step,
+                requested,
+                used,
+            } if requested == used => format!("-- {}: linked as {}", step, used),
+            ExecEvent::StepLinked {
+                step,
+                requested,
+                used,
+            } => format!("-- {}: linked as {} ({} failed)", step, used, requested),
+            ExecEvent::StepRetrying {
+                step,
+                attempt,
+                attempts,
+                exit_code,
+                delay_ms,
+            } => format!(
+                "-- {}: attempt {}/{} failed ({}), retrying in {}",
+                step,
+                attempt,
+                attempts,
+                describe_exit(*exit_code),
+                seconds(*delay_ms)
+            ),
+            ExecEvent::StepFailed {
+                step,
+                duration_ms,
+                error,
+            } => format!(
+                "!! {}: failed after {}: {}",
+                step,
+                seconds(*duration_ms),
+                error
+            ),
+            ExecEvent::PlanFinished {
+                success: true,
+                duration_ms,
+            } => format!("\n== plan succeeded in {}", seconds(*duration_ms)),
+            ExecEvent::PlanFinished {
+                success: false,
+                duration_ms,
+            } => format!("\n== plan failed after {}", seconds(*duration_ms)),
+        };
+        self.write(&text);
+    }
+
+    fn renders_output(&self) -> bool {
+        false
+    }
+}
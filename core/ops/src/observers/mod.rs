@@ -0,0 +1,11 @@
+pub mod json;
+pub mod log_file;
+pub mod progress;
+
+pub use json::JsonRenderer;
+pub use log_file::RunLogWriter;
+pub use progress::ProgressRenderer;
+
+fn seconds(duration_ms: u64) -> String {
+    format!("{:.2}s", duration_ms as f64 / 1000.0)
+}
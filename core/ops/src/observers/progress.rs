@@ -0,0 +1,98 @@
+use super::seconds;
+use crate::events::{ExecEvent, ExecObserver, OutputStream};
+use colored::*;
+
+/// Human-readable live progress: one line per step start/finish, with child
+/// output indented underneath and tagged with the step it belongs to.
+pub struct ProgressRenderer;
+
+impl ExecObserver for ProgressRenderer {
+    fn on_event(&self, event: &ExecEvent) {
+        match event {
+            ExecEvent::PreflightFailed { .. }
+            | ExecEvent::PlanStarted { .. }
+            | ExecEvent::StepPlanned { .. }
+            | ExecEvent::ProcessExited { .. } => {}
+            ExecEvent::StepStarted { step, description } => {
+                println!("{} {}. {}", ">>".blue(), step, description);
+            }
+            ExecEvent::Output {
+                step,
+                stream: OutputStream::Stdout,
+                line,
+            } => println!("   {} {}", format!("{} |", step).dimmed(), line),
+            ExecEvent::Output {
+                step,
+                stream: OutputStream::Stderr,
+                line,
+            } => eprintln!("   {} {}", format!("{} |", step).dimmed(), line),
+            ExecEvent::StepFinished { step, duration_ms } => {
+                println!(
+                    "{} {}. done in {}",
+                    "OK".green(),
+                    step,
+                    seconds(*duration_ms)
+                );
+            }
+            ExecEvent::StepSkipped { step, reason } => {
+                println!("{} {}. {}", "OK".green(), step, reason);
+            }
+            ExecEvent::StepLinked {
+                step,
+                requested,
+                used,
+            } if requested != used => {
+                eprintln!(
+                    "{} {}. {} failed, linked as {} instead",
+                    "..".yellow(),
+                    step,
+                    requested,
+                    used
+                );
+            }
+            ExecEvent::StepLinked { .. } => {}
+            ExecEvent::StepRetrying {
+                step,
+                attempt,
+                attempts,
+                exit_code,
+                delay_ms,
+            } => {
+                eprintln!(
+                    "{} {}. attempt {}/{} failed ({}), retrying in {}",
+                    "..".yellow(),
+                    step,
+                    attempt,
+                    attempts,
+                    crate::process::describe_exit(*exit_code),
+                    seconds(*delay_ms)
+                );
+            }
+            ExecEvent::StepFailed {
+                step,
+                duration_ms,
+                error,
+            } => {
+                eprintln!(
+                    "{} {}. failed after {}: {}",
+                    "!!".red(),
+                    step,
+                    seconds(*duration_ms),
+                    error
+                );
+            }
+            ExecEvent::PlanFinished {
+                success,
+                duration_ms,
+            } => {
+                if *success {
+                    println!(
+                        "{} Plan finished in {}",
+                        "SUCCESS".green().bold(),
+                        seconds(*duration_ms)
+                    );
+                }
+            }
+        }
+    }
+}
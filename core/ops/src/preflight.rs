@@ -0,0 +1,73 @@
+use crate::backend::ProcessSpec;
+use crate::context::ExecContext;
+use crate::process::describe_exit;
+use hymod_core_plan::Preflight;
+use std::path::{Path, PathBuf};
+
+const PROBE_NAME: &str = ".hymod-preflight";
+
+/// Check every preflight condition and describe each one that does not hold.
+pub(crate) fn check_all(checks: &[Preflight], ctx: &ExecContext) -> Vec<String> {
+    checks
+        .iter()
+        .filter_map(|check| check_one(check, ctx).err())
+        .collect()
+}
+
+fn check_one(check: &Preflight, ctx: &ExecContext) -> Result<(), String> {
+    match check {
+        Preflight::Binary { name } => match find_binary(name, ctx) {
+            Some(_) => Ok(()),
+            None => Err(format!("{} not found on PATH", name)),
+        },
+        Preflight::Exists { path } => match ctx.fs().exists(Path::new(path)) {
+            true => Ok(()),
+            false => Err(format!("{} does not exist", path)),
+        },
+        Preflight::Writable { path } => check_writable(Path::new(path), ctx),
+        Preflight::SshReachable { host, user, ssh } => {
+            let spec = ProcessSpec::new("ssh")
+                .args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=10"])
+                .args(ssh.ssh_args())
+                .arg(format!("{}@{}", user, host))
+                .arg("true");
+            match ctx.runner().capture(&spec, ctx) {
+                Ok(output) if output.status.success() => Ok(()),
+                Ok(output) => Err(format!(
+                    "cannot reach {}@{} over SSH ({})",
+                    user,
+                    host,
+                    describe_exit(output.status.code)
+                )),
+                Err(e) => Err(format!("cannot reach {}@{} over SSH: {}", user, host, e)),
+            }
+        }
+    }
+}
+
+fn find_binary(name: &str, ctx: &ExecContext) -> Option<PathBuf> {
+    if name.contains(std::path::MAIN_SEPARATOR) {
+        return Some(PathBuf::from(name)).filter(|path| ctx.fs().is_file(path));
+    }
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| ctx.fs().is_file(candidate))
+}
+
+/// Create and remove a probe file in `path`, or in its nearest existing
+/// ancestor when the plan is going to create `path`.
+fn check_writable(path: &Path, ctx: &ExecContext) -> Result<(), String> {
+    let Some(dir) = path.ancestors().find(|dir| ctx.fs().exists(dir)) else {
+        return Err(format!("{} does not exist", path.display()));
+    };
+    if ctx.fs().is_file(dir) {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    let probe = dir.join(PROBE_NAME);
+    ctx.fs()
+        .write(&probe, b"")
+        .map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+    let _ = ctx.fs().remove_file(&probe);
+    Ok(())
+}
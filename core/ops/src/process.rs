@@ -0,0 +1,352 @@
+use crate::backend::{ProcessSpec, ProcessStatus};
+use crate::context::{ExecContext, Verbosity};
+use crate::events::{ExecEvent, OutputStream};
+use hymod_core_plan::RetryPolicy;
+use std::io::{self, BufRead, BufReader, IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+/// How often a running child is checked for exit, timeout and cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a child's process group gets to exit after SIGTERM before SIGKILL.
+const KILL_GRACE: Duration = Duration::from_secs(3);
+
+/// Run a child process to completion, honouring the context's verbosity.
+/// Inside a parallel branch the child's output is captured and printed as a
+/// single labelled block so concurrent steps don't interleave line by line.
+/// When observers are attached, output is streamed to them as events, and
+/// echoed line by line as well unless one of them renders it.
+///
+/// The child is killed with its whole process group if the step's timeout
+/// passes (`ErrorKind::TimedOut`) or the run is cancelled (`ErrorKind::Interrupted`).
+pub(crate) fn run_command(command: &mut Command, ctx: &ExecContext) -> io::Result<ExitStatus> {
+    if ctx.has_observers() {
+        return stream_to_observers(command, ctx);
+    }
+
+    let quiet = ctx.verbosity == Verbosity::Quiet;
+
+    let Some(label) = ctx.output_label.as_deref() else {
+        match ctx.verbosity {
+            Verbosity::Verbose => println!("{}", describe(command, ctx)),
+            Verbosity::Quiet => {
+                command.stdout(Stdio::null());
+            }
+            Verbosity::Normal => {}
+        }
+        return supervise(&mut spawn(command)?, ctx);
+    };
+
+    // Output is only shown once the child exits, so a prompt could never be answered.
+    let mut child = spawn(
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+    )?;
+    let stdout_pipe = child.stdout.take();
+    let stderr_pipe = child.stderr.take();
+    let (status, stdout, stderr) = std::thread::scope(|scope| {
+        let stdout = scope.spawn(|| read_all(stdout_pipe));
+        let stderr = scope.spawn(|| read_all(stderr_pipe));
+        let status = supervise(&mut child, ctx);
+        (
+            status,
+            stdout.join().unwrap_or_default(),
+            stderr.join().unwrap_or_default(),
+        )
+    });
+
+    let out_handle = io::stdout();
+    let mut out = out_handle.lock();
+    if ctx.verbosity == Verbosity::Verbose {
+        writeln!(out, "[{}] {}", label, describe(command, ctx))?;
+    }
+    if !quiet {
+        for line in String::from_utf8_lossy(&stdout).lines() {
+            writeln!(out, "[{}] {}", label, ctx.redact(line))?;
+        }
+    }
+    out.flush()?;
+
+    let err_handle = io::stderr();
+    let mut err = err_handle.lock();
+    for line in String::from_utf8_lossy(&stderr).lines() {
+        writeln!(err, "[{}] {}", label, ctx.redact(line))?;
+    }
+
+    status
+}
+
+/// Run a child without reporting anything and return its stdout, under the
+/// same timeout and cancellation rules as `run_command`.
+pub(crate) fn capture_command(
+    command: &mut Command,
+    ctx: &ExecContext,
+) -> io::Result<(ExitStatus, Vec<u8>)> {
+    let mut child = spawn(
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null()),
+    )?;
+    let stdout_pipe = child.stdout.take();
+    std::thread::scope(|scope| {
+        let stdout = scope.spawn(|| read_all(stdout_pipe));
+        let status = supervise(&mut child, ctx)?;
+        Ok((status, stdout.join().unwrap_or_default()))
+    })
+}
+
+/// Start `command`, which keeps hymod's stdin unless the caller set one, so
+/// prompts (sudo, ssh passwords, gradle) can still be answered. On Unix the
+/// child gets a process group of its own, so it and everything it starts can
+/// be killed together; not when stdin is a terminal, though, since a
+/// background group is stopped as soon as it reads from the terminal.
+fn spawn(command: &mut Command) -> io::Result<Child> {
+    #[cfg(unix)]
+    if !io::stdin().is_terminal() {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command.spawn()
+}
+
+/// Wait for `child`, killing it if the step's timeout passes or the run is cancelled.
+fn supervise(child: &mut Child, ctx: &ExecContext) -> io::Result<ExitStatus> {
+    let timeout = ctx.step_timeout();
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if ctx.is_cancelled() {
+            terminate(child)?;
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+        if let Some(timeout) = timeout.filter(|timeout| started.elapsed() >= *timeout) {
+            terminate(child)?;
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {}s", timeout.as_secs()),
+            ));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Stop `child` and everything it started: SIGTERM to its process group, then
+/// SIGKILL to whatever is left once the child exits or the grace period ends.
+/// A child that shares hymod's group (see `spawn`) is signalled on its own.
+#[cfg(unix)]
+fn terminate(child: &mut Child) -> io::Result<()> {
+    let pid = child.id() as libc::pid_t;
+    // SAFETY: getpgid(2) has no memory-safety preconditions.
+    let group = if unsafe { libc::getpgid(pid) } == pid {
+        -pid
+    } else {
+        pid
+    };
+    // SAFETY: kill(2) has no memory-safety preconditions.
+    unsafe { libc::kill(group, libc::SIGTERM) };
+    let deadline = Instant::now() + KILL_GRACE;
+    while child.try_wait()?.is_none() && Instant::now() < deadline {
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    // SAFETY: as above.
+    unsafe { libc::kill(group, libc::SIGKILL) };
+    child.wait().map(|_| ())
+}
+
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) -> io::Result<()> {
+    child.kill()?;
+    child.wait().map(|_| ())
+}
+
+fn read_all(pipe: Option<impl Read>) -> Vec<u8> {
+    let mut content = Vec::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_end(&mut content);
+    }
+    content
+}
+
+/// Report output that did not come from a live child (e.g. a scripted
+/// runner's canned stdout) the same way `run_command` reports real output.
+pub(crate) fn report_output(ctx: &ExecContext, stdout: &str) {
+    for line in stdout.lines() {
+        let line = ctx.redact(line);
+        if !ctx.is_observed() {
+            echo_line(ctx, OutputStream::Stdout, &line);
+        }
+        ctx.emit(ExecEvent::Output {
+            step: ctx.current_step(),
+            stream: OutputStream::Stdout,
+            line,
+        });
+    }
+}
+
+/// Tell observers that a child started for the current step has ended.
+pub(crate) fn report_exit(
+    ctx: &ExecContext,
+    command: &str,
+    exit_code: Option<i32>,
+    started: Instant,
+) {
+    ctx.emit(ExecEvent::ProcessExited {
+        step: ctx.current_step(),
+        command: ctx.redact(command),
+        exit_code,
+        duration_ms: started.elapsed().as_millis() as u64,
+    });
+}
+
+/// Print one line of child output the way plain text mode shows it.
+fn echo_line(ctx: &ExecContext, stream: OutputStream, line: &str) {
+    let prefix = ctx
+        .output_label
+        .as_deref()
+        .map(|label| format!("[{}] ", label))
+        .unwrap_or_default();
+    match stream {
+        OutputStream::Stdout if ctx.verbosity != Verbosity::Quiet => {
+            println!("{}{}", prefix, line)
+        }
+        OutputStream::Stdout => {}
+        OutputStream::Stderr => eprintln!("{}{}", prefix, line),
+    }
+}
+
+/// Run `spec` through the context's process runner, attempting it again after
+/// a retryable failure as long as `retry` allows. Returns the last attempt's status.
+pub(crate) fn run_with_retry(
+    spec: &ProcessSpec,
+    retry: Option<&RetryPolicy>,
+    ctx: &ExecContext,
+) -> io::Result<ProcessStatus> {
+    let attempts = retry.map_or(1, |policy| policy.attempts.max(1));
+    let mut attempt = 1;
+    loop {
+        let status = ctx.runner().run(spec, ctx)?;
+        let Some(policy) = retry else {
+            return Ok(status);
+        };
+        if status.success() || attempt >= attempts || !policy.is_retryable(status.code) {
+            return Ok(status);
+        }
+
+        let delay = policy.delay_after(attempt);
+        report_retry(ctx, attempt, attempts, status.code, delay);
+        sleep_unless_cancelled(delay, ctx)?;
+        attempt += 1;
+    }
+}
+
+fn sleep_unless_cancelled(delay: Duration, ctx: &ExecContext) -> io::Result<()> {
+    let deadline = Instant::now() + delay;
+    while Instant::now() < deadline {
+        if ctx.is_cancelled() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+        std::thread::sleep(POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())));
+    }
+    Ok(())
+}
+
+fn report_retry(
+    ctx: &ExecContext,
+    attempt: u32,
+    attempts: u32,
+    exit_code: Option<i32>,
+    delay: Duration,
+) {
+    ctx.emit(ExecEvent::StepRetrying {
+        step: ctx.current_step(),
+        attempt,
+        attempts,
+        exit_code,
+        delay_ms: delay.as_millis() as u64,
+    });
+    if ctx.is_observed() || ctx.verbosity == Verbosity::Quiet {
+        return;
+    }
+
+    let prefix = ctx
+        .output_label
+        .as_deref()
+        .map(|label| format!("[{}] ", label))
+        .unwrap_or_default();
+    eprintln!(
+        "{}Attempt {}/{} failed ({}), retrying in {:.1}s...",
+        prefix,
+        attempt,
+        attempts,
+        describe_exit(exit_code),
+        delay.as_secs_f64()
+    );
+}
+
+pub(crate) fn describe_exit(exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(code) => format!("exit {}", code),
+        None => "killed by signal".to_string(),
+    }
+}
+
+fn stream_to_observers(command: &mut Command, ctx: &ExecContext) -> io::Result<ExitStatus> {
+    if !ctx.is_observed() && ctx.verbosity == Verbosity::Verbose {
+        echo_line(ctx, OutputStream::Stdout, &describe(command, ctx));
+    }
+    let mut child = spawn(command.stdout(Stdio::piped()).stderr(Stdio::piped()))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    std::thread::scope(|scope| {
+        if let Some(stderr) = stderr {
+            scope.spawn(|| forward_lines(stderr, OutputStream::Stderr, ctx));
+        }
+        if let Some(stdout) = stdout {
+            scope.spawn(|| forward_lines(stdout, OutputStream::Stdout, ctx));
+        }
+        supervise(&mut child, ctx)
+    })
+}
+
+fn forward_lines(pipe: impl Read, stream: OutputStream, ctx: &ExecContext) {
+    let echo = !ctx.is_observed();
+    for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+        let line = ctx.redact(&line);
+        if echo {
+            echo_line(ctx, stream, &line);
+        }
+        ctx.emit(ExecEvent::Output {
+            step: ctx.current_step(),
+            stream,
+            line,
+        });
+    }
+}
+
+/// Quote `value` as a single POSIX shell word.
+pub(crate) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// The `EXEC` line shown in verbose mode, with the step's secrets masked.
+fn describe(command: &Command, ctx: &ExecContext) -> String {
+    let mut line = command.get_program().to_string_lossy().to_string();
+    for arg in command.get_args() {
+        line.push(' ');
+        line.push_str(&arg.to_string_lossy());
+    }
+    let cwd = command
+        .get_current_dir()
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default();
+    ctx.redact(&format!("EXEC      {}    (cwd: {})", line, cwd))
+}
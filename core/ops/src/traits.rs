@@ -1,3 +1,4 @@
+use crate::context::ExecContext;
 use anyhow::Result;
 use hymod_core_plan::Step;
 
@@ -6,5 +7,17 @@ pub trait OpHandler: Send + Sync {
     /// Execute the logic for the given step.
     /// Handlers should verify the correct Step variant is passed (e.g. via matching)
     /// and panic or error if mismatch (though Executor should prevent this).
-    fn handle(&self, step: &Step) -> Result<()>;
+    fn handle(&self, step: &Step, ctx: &ExecContext) -> Result<()>;
+
+    /// Whether the step's effect is already in place, so `handle` can be
+    /// skipped. Checks must not change anything; when in doubt return false.
+    fn is_up_to_date(&self, _step: &Step, _ctx: &ExecContext) -> bool {
+        false
+    }
+
+    /// How the step is shown in dry-run plans and progress output. `None`
+    /// falls back to the step's own `Display`; mainly useful for `Step::Custom`.
+    fn describe(&self, _step: &Step) -> Option<String> {
+        None
+    }
 }
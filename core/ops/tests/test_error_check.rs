@@ -4,6 +4,7 @@ use hymod_core_plan::{Plan, Step};
 #[test]
 fn test_execute_plan_check_failure() {
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::CheckFile {
             path: "/non/existent/path/should/fail".to_string(),
         }],
@@ -22,8 +23,11 @@ fn test_execute_plan_check_failure() {
 #[test]
 fn test_execute_plan_check_zip_failure() {
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::CheckZip {
             path: "/non/existent/artifact.zip".to_string(),
+            require: vec![],
+            require_main_class: false,
         }],
     };
 
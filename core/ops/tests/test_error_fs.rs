@@ -1,5 +1,5 @@
 use core_ops::Executor;
-use hymod_core_plan::{Plan, Step};
+use hymod_core_plan::{LinkMode, Plan, Step};
 use tempfile::tempdir;
 
 #[test]
@@ -9,6 +9,7 @@ fn test_execute_plan_copy_missing_source() {
     let dest = dir.path().join("dest.txt");
 
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::CopyFile {
             from: src.to_str().unwrap().to_string(),
             to: dest.to_str().unwrap().to_string(),
@@ -38,9 +39,11 @@ fn test_execute_plan_symlink_missing_source_allowed() {
     let link = dir.path().join("link_to_missing.txt");
 
     let plan = Plan {
-        steps: vec![Step::Symlink {
+        preflight: Vec::new(),
+        steps: vec![Step::Link {
             from: target.to_str().unwrap().to_string(),
             to: link.to_str().unwrap().to_string(),
+            mode: LinkMode::Symlink,
         }],
     };
 
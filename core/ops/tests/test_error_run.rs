@@ -1,13 +1,16 @@
 use core_ops::Executor;
-use hymod_core_plan::{Plan, Step};
+use hymod_core_plan::{Plan, Step, StepEnv};
 
 #[test]
 fn test_execute_plan_run_process_failure() {
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::RunProcess {
             cmd: "non_existent_command_12345".to_string(),
             args: vec![],
             cwd: None,
+            env: StepEnv::new(),
+            timeout_secs: None,
         }],
     };
 
@@ -21,10 +24,13 @@ fn test_execute_plan_run_process_failure() {
 #[test]
 fn test_execute_plan_run_process_exit_code_failure() {
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::RunProcess {
             cmd: "false".to_string(), // 'false' returns exit code 1
             args: vec![],
             cwd: None,
+            env: StepEnv::new(),
+            timeout_secs: None,
         }],
     };
 
@@ -0,0 +1,135 @@
+use core_ops::{Executor, MemoryFileSystem, RecordingProcessRunner, Verbosity};
+use hymod_core_plan::{Plan, SshOptions, Step};
+use std::fs;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+#[test]
+fn test_execute_plan_copy_leaves_no_temporary_file() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("mod.jar");
+    let mods = dir.path().join("mods");
+    fs::write(&source, "new jar").unwrap();
+    fs::create_dir(&mods).unwrap();
+    fs::write(mods.join("mod.jar"), "old jar").unwrap();
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::CopyFile {
+            from: source.to_string_lossy().to_string(),
+            to: mods.join("mod.jar").to_string_lossy().to_string(),
+        }],
+    };
+    Executor::new(false)
+        .with_fsync(true)
+        .execute(&plan)
+        .unwrap();
+
+    assert_eq!(fs::read_to_string(mods.join("mod.jar")).unwrap(), "new jar");
+    let names: Vec<String> = fs::read_dir(&mods)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, vec!["mod.jar".to_string()]);
+}
+
+#[test]
+fn test_execute_plan_failed_copy_keeps_old_file() {
+    let fs = Arc::new(MemoryFileSystem::new().with_file("/server/mods/mod.jar", "old jar"));
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::CopyFile {
+            from: "/mod/build/libs/missing.jar".to_string(),
+            to: "/server/mods/mod.jar".to_string(),
+        }],
+    };
+
+    Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_file_system(fs.clone())
+        .execute(&plan)
+        .unwrap_err();
+
+    assert_eq!(fs.file("/server/mods/mod.jar").unwrap(), b"old jar");
+    assert!(!fs
+        .paths()
+        .iter()
+        .any(|path| path.to_string_lossy().contains("hymod-tmp")));
+}
+
+#[test]
+fn test_execute_plan_upload_is_moved_into_place_after_sync() {
+    let fs = Arc::new(MemoryFileSystem::new().with_file("/mod/mod.jar", "jar"));
+    let runner = Arc::new(RecordingProcessRunner::new());
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::UploadRsync {
+            local: "/mod/mod.jar".to_string(),
+            remote: "deploy@example.com:/srv/mods/mod.jar".to_string(),
+            opts: "-avz".to_string(),
+            ssh: SshOptions::default(),
+            retry: None,
+            timeout_secs: None,
+        }],
+    };
+
+    Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_file_system(fs)
+        .with_process_runner(runner.clone())
+        .with_fsync(true)
+        .execute(&plan)
+        .unwrap();
+
+    let calls: Vec<String> = runner.calls().iter().map(|call| call.to_string()).collect();
+    assert_eq!(
+        calls[1..],
+        [
+            "ssh deploy@example.com test ! -f '/srv/mods/mod.jar' || cp -f -- '/srv/mods/mod.jar' '/srv/mods/.mod.jar.hymod-tmp'"
+                .to_string(),
+            "rsync -avz /mod/mod.jar deploy@example.com:/srv/mods/.mod.jar.hymod-tmp".to_string(),
+            "ssh deploy@example.com sync && mv -f -- '/srv/mods/.mod.jar.hymod-tmp' '/srv/mods/mod.jar'"
+                .to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_execute_plan_upload_parses_ipv6_and_windows_destinations() {
+    let fs = Arc::new(MemoryFileSystem::new().with_file("/mod/mod.jar", "jar"));
+    let runner = Arc::new(RecordingProcessRunner::new());
+    let upload = |remote: &str| Step::UploadScp {
+        local: "/mod/mod.jar".to_string(),
+        remote: remote.to_string(),
+        opts: String::new(),
+        ssh: SshOptions::default(),
+        retry: None,
+        timeout_secs: None,
+    };
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            upload("deploy@[::1]:/srv/mods/mod.jar"),
+            upload(r"C:\mods\mod.jar"),
+        ],
+    };
+
+    Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_file_system(fs)
+        .with_process_runner(runner.clone())
+        .execute(&plan)
+        .unwrap();
+
+    let calls: Vec<String> = runner.calls().iter().map(|call| call.to_string()).collect();
+    assert_eq!(
+        calls,
+        [
+            "ssh -o BatchMode=yes deploy@::1 sha256sum -- '/srv/mods/mod.jar'".to_string(),
+            "scp /mod/mod.jar deploy@[::1]:/srv/mods/.mod.jar.hymod-tmp".to_string(),
+            "ssh deploy@::1 mv -f -- '/srv/mods/.mod.jar.hymod-tmp' '/srv/mods/mod.jar'"
+                .to_string(),
+            r"scp /mod/mod.jar C:\mods\mod.jar".to_string(),
+        ]
+    );
+}
@@ -0,0 +1,100 @@
+use core_ops::Executor;
+use hymod_core_plan::{Plan, Step};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use tempfile::tempdir;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const MANIFEST: &str = r#"{ "Name": "Example", "Main": "com.example.Main" }"#;
+
+fn jar(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+    for (name, content) in entries {
+        writer.start_file(*name, options).unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn check(path: &Path, require: &[&str], require_main_class: bool) -> anyhow::Result<()> {
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::CheckZip {
+            path: path.to_string_lossy().to_string(),
+            require: require.iter().map(|entry| entry.to_string()).collect(),
+            require_main_class,
+        }],
+    };
+    Executor::new(false).execute(&plan)
+}
+
+#[test]
+fn test_execute_plan_check_zip_accepts_packaged_jar() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("mod.jar");
+    fs::write(
+        &path,
+        jar(&[
+            ("manifest.json", MANIFEST),
+            ("com/example/Main.class", "class bytes"),
+        ]),
+    )
+    .unwrap();
+
+    check(&path, &["manifest.json"], true).unwrap();
+}
+
+#[test]
+fn test_execute_plan_check_zip_names_missing_main_class() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("mod.jar");
+    fs::write(&path, jar(&[("manifest.json", MANIFEST)])).unwrap();
+
+    let err = check(&path, &["manifest.json"], true).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("missing main class entry 'com/example/Main.class'"));
+}
+
+#[test]
+fn test_execute_plan_check_zip_names_missing_required_entry() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("mod.jar");
+    fs::write(&path, jar(&[("com/example/Main.class", "class bytes")])).unwrap();
+
+    let err = check(&path, &["manifest.json"], false).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("missing required entry 'manifest.json'"));
+}
+
+#[test]
+fn test_execute_plan_check_zip_rejects_truncated_archive() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("mod.jar");
+    let bytes = jar(&[("manifest.json", MANIFEST)]);
+    fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+    let err = check(&path, &[], false).unwrap_err();
+    assert!(err.to_string().contains("is not a valid archive"));
+}
+
+#[test]
+fn test_execute_plan_check_zip_rejects_bad_crc() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("mod.jar");
+    let mut bytes = jar(&[("manifest.json", MANIFEST)]);
+    // Entries are stored uncompressed, so the manifest text is in the file as is.
+    let offset = bytes
+        .windows(7)
+        .position(|window| window == b"Example")
+        .unwrap();
+    bytes[offset] = b'X';
+    fs::write(&path, bytes).unwrap();
+
+    let err = check(&path, &[], false).unwrap_err();
+    assert!(format!("{:#}", err).contains("entry 'manifest.json' failed its CRC check"));
+}
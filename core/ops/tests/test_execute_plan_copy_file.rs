@@ -12,6 +12,7 @@ fn test_execute_plan_copy_file() {
     fs::write(&src, "hello world").unwrap();
 
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::CopyFile {
             from: src.to_str().unwrap().to_string(),
             to: dest.to_str().unwrap().to_string(),
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use core_ops::{ExecContext, ExecEvent, ExecObserver, Executor, OpHandler};
+use hymod_core_plan::{Plan, Step};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+/// Stand-in for an embedder's "notify via local relay" step.
+#[derive(Default)]
+struct NotifyHandler {
+    sent: Arc<Mutex<Vec<String>>>,
+}
+
+impl OpHandler for NotifyHandler {
+    fn handle(&self, step: &Step, _ctx: &ExecContext) -> Result<()> {
+        if let Step::Custom { payload, .. } = step {
+            let message = payload["message"].as_str().unwrap_or_default();
+            self.sent.lock().unwrap().push(message.to_string());
+            Ok(())
+        } else {
+            panic!("NotifyHandler called with non-Custom step");
+        }
+    }
+
+    fn describe(&self, step: &Step) -> Option<String> {
+        match step {
+            Step::Custom { payload, .. } => Some(format!("NOTIFY    {}", payload["message"])),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<ExecEvent>>,
+}
+
+impl ExecObserver for Recorder {
+    fn on_event(&self, event: &ExecEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+fn notify_step(message: &str) -> Step {
+    Step::Custom {
+        kind: "notify".to_string(),
+        payload: json!({ "message": message }),
+    }
+}
+
+#[test]
+fn test_execute_plan_custom_step_dispatches_by_kind() {
+    let handler = NotifyHandler::default();
+    let sent = handler.sent.clone();
+    let recorder = Arc::new(Recorder::default());
+
+    let executor = Executor::new(false)
+        .with_custom_handler("notify", Box::new(handler))
+        .with_observer(recorder.clone());
+    executor
+        .execute(&Plan {
+            preflight: Vec::new(),
+            steps: vec![notify_step("deploy started"), notify_step("deploy done")],
+        })
+        .unwrap();
+
+    assert_eq!(*sent.lock().unwrap(), vec!["deploy started", "deploy done"]);
+    assert!(recorder
+        .events
+        .lock()
+        .unwrap()
+        .contains(&ExecEvent::StepStarted {
+            step: "1".to_string(),
+            description: "NOTIFY    \"deploy started\"".to_string(),
+        }));
+}
+
+#[test]
+fn test_dry_run_lists_custom_step_as_described_by_its_handler() {
+    let executor =
+        Executor::new(true).with_custom_handler("notify", Box::new(NotifyHandler::default()));
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![notify_step("deploy started")],
+    };
+
+    assert_eq!(
+        executor.dry_run_listing(&plan),
+        "PLAN\n  1. NOTIFY    \"deploy started\"\n"
+    );
+    executor.execute(&plan).unwrap();
+}
+
+#[test]
+fn test_execute_plan_custom_step_without_handler_fails() {
+    let err = Executor::new(false)
+        .execute(&Plan {
+            preflight: Vec::new(),
+            steps: vec![notify_step("hello")],
+        })
+        .unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains("No handler registered for custom step kind 'notify'"));
+}
+
+#[test]
+fn test_register_replaces_builtin_handler() {
+    struct NoopMkdir;
+    impl OpHandler for NoopMkdir {
+        fn handle(&self, _step: &Step, _ctx: &ExecContext) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("never-created");
+
+    let mut executor = Executor::new(false);
+    executor.register(
+        Step::Mkdir {
+            path: String::new(),
+        },
+        Box::new(NoopMkdir),
+    );
+    executor
+        .execute(&Plan {
+            preflight: Vec::new(),
+            steps: vec![Step::Mkdir {
+                path: target.to_str().unwrap().to_string(),
+            }],
+        })
+        .unwrap();
+
+    assert!(!target.exists());
+}
+
+#[test]
+fn test_custom_step_default_display() {
+    assert_eq!(
+        notify_step("hi").to_string(),
+        r#"CUSTOM    notify {"message":"hi"}"#
+    );
+    assert_eq!(
+        Step::Custom {
+            kind: "snapshot_world".to_string(),
+            payload: serde_json::Value::Null,
+        }
+        .to_string(),
+        "CUSTOM    snapshot_world"
+    );
+}
@@ -0,0 +1,129 @@
+use core_ops::{ExecEvent, ExecObserver, Executor, RecordingProcessRunner, Verbosity};
+use hymod_core_plan::{EnvValue, Plan, SshOptions, Step, StepEnv};
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<ExecEvent>>,
+}
+
+impl ExecObserver for Recorder {
+    fn on_event(&self, event: &ExecEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+fn env() -> StepEnv {
+    StepEnv::from([
+        ("GREETING".to_string(), EnvValue::Plain("hello".to_string())),
+        (
+            "API_TOKEN".to_string(),
+            EnvValue::Secret {
+                secret: "hunter2".to_string(),
+            },
+        ),
+    ])
+}
+
+#[test]
+fn test_execute_plan_env_reaches_child_and_secret_is_masked() {
+    let recorder = Arc::new(Recorder::default());
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::RunProcess {
+            cmd: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                "echo \"$GREETING $API_TOKEN\"".to_string(),
+            ],
+            cwd: None,
+            env: env(),
+            timeout_secs: None,
+        }],
+    };
+
+    Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_observer(recorder.clone())
+        .execute(&plan)
+        .unwrap();
+
+    let events = recorder.events.lock().unwrap();
+    let output: Vec<&str> = events
+        .iter()
+        .filter_map(|event| match event {
+            ExecEvent::Output { line, .. } => Some(line.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(output, vec!["hello ********"]);
+    assert!(events.iter().any(|event| matches!(
+        event,
+        ExecEvent::StepStarted { description, .. }
+            if description.contains("API_TOKEN=********") && description.contains("GREETING=hello")
+    )));
+    let log = serde_json::to_string(&*events).unwrap();
+    assert!(!log.contains("hunter2"));
+}
+
+#[test]
+fn test_execute_plan_ssh_env_is_exported_remotely() {
+    let runner = Arc::new(RecordingProcessRunner::new());
+    let recorder = Arc::new(Recorder::default());
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::SshRun {
+            host: "example.com".to_string(),
+            user: "deploy".to_string(),
+            cmd: "./restart.sh".to_string(),
+            env: env(),
+            ssh: SshOptions::default(),
+            retry: None,
+            timeout_secs: None,
+        }],
+    };
+
+    Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_process_runner(runner.clone())
+        .with_observer(recorder.clone())
+        .execute(&plan)
+        .unwrap();
+
+    assert_eq!(
+        runner.calls()[0].args[1],
+        "export API_TOKEN='hunter2' GREETING='hello'; ./restart.sh"
+    );
+    let events = recorder.events.lock().unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        ExecEvent::ProcessExited { command, .. }
+            if command.contains("API_TOKEN='********'")
+    )));
+}
+
+#[test]
+fn test_execute_plan_ssh_env_rejects_invalid_name() {
+    let runner = Arc::new(RecordingProcessRunner::new());
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::SshRun {
+            host: "example.com".to_string(),
+            user: "deploy".to_string(),
+            cmd: "uptime".to_string(),
+            env: StepEnv::from([("BAD NAME".to_string(), EnvValue::Plain("x".to_string()))]),
+            ssh: SshOptions::default(),
+            retry: None,
+            timeout_secs: None,
+        }],
+    };
+
+    let err = Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_process_runner(runner.clone())
+        .execute(&plan)
+        .unwrap_err();
+
+    assert!(format!("{:#}", err).contains("Invalid environment variable name 'BAD NAME'"));
+    assert!(runner.calls().is_empty());
+}
@@ -0,0 +1,154 @@
+use core_ops::{
+    EntryKind, ExecEvent, ExecObserver, Executor, FileSystem, MemoryFileSystem, Verbosity,
+};
+use hymod_core_plan::{LinkMode, Plan, Step};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<ExecEvent>>,
+}
+
+impl ExecObserver for Recorder {
+    fn on_event(&self, event: &ExecEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+
+    fn renders_output(&self) -> bool {
+        false
+    }
+}
+
+fn link_plan(mode: LinkMode) -> Plan {
+    Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::Link {
+            from: "/store/mod.jar".to_string(),
+            to: "/server/mods/mod.jar".to_string(),
+            mode,
+        }],
+    }
+}
+
+fn run(fs: Arc<MemoryFileSystem>, mode: LinkMode) -> (anyhow::Result<()>, Vec<ExecEvent>) {
+    let recorder = Arc::new(Recorder::default());
+    let result = Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_file_system(fs)
+        .with_observer(recorder.clone())
+        .execute(&link_plan(mode));
+    let events = recorder.events.lock().unwrap().clone();
+    (result, events)
+}
+
+fn linked(events: &[ExecEvent]) -> Vec<(LinkMode, LinkMode)> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            ExecEvent::StepLinked {
+                requested, used, ..
+            } => Some((*requested, *used)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_link_falls_back_to_hardlink_without_symlinks() {
+    let fs = Arc::new(
+        MemoryFileSystem::new()
+            .with_file("/store/mod.jar", "jar")
+            .with_file("/server/mods/mod.jar", "old jar")
+            .without_symlinks(),
+    );
+
+    let (result, events) = run(fs.clone(), LinkMode::Symlink);
+
+    result.unwrap();
+    assert_eq!(
+        linked(&events),
+        vec![(LinkMode::Symlink, LinkMode::Hardlink)]
+    );
+    let link = Path::new("/server/mods/mod.jar");
+    assert_eq!(fs.entry_kind(link), Some(EntryKind::File));
+    assert_eq!(fs.file(link).unwrap(), b"jar");
+}
+
+#[test]
+fn test_link_falls_back_to_copy_without_any_links() {
+    let fs = Arc::new(
+        MemoryFileSystem::new()
+            .with_file("/store/mod.jar", "jar")
+            .with_dir("/server/mods")
+            .without_symlinks()
+            .without_hard_links(),
+    );
+
+    let (result, events) = run(fs.clone(), LinkMode::Hardlink);
+
+    result.unwrap();
+    assert_eq!(linked(&events), vec![(LinkMode::Hardlink, LinkMode::Copy)]);
+    assert_eq!(fs.file("/server/mods/mod.jar").unwrap(), b"jar");
+    assert!(!fs
+        .paths()
+        .iter()
+        .any(|path| path.to_string_lossy().contains("hymod-tmp")));
+}
+
+#[test]
+fn test_link_records_requested_mode_when_it_works() {
+    let fs = Arc::new(MemoryFileSystem::new().with_file("/store/mod.jar", "jar"));
+
+    let (result, events) = run(fs.clone(), LinkMode::Symlink);
+
+    result.unwrap();
+    assert_eq!(
+        linked(&events),
+        vec![(LinkMode::Symlink, LinkMode::Symlink)]
+    );
+    assert_eq!(
+        fs.read_link(Path::new("/server/mods/mod.jar")).unwrap(),
+        Path::new("/store/mod.jar")
+    );
+}
+
+#[test]
+fn test_link_already_in_place_is_skipped() {
+    let fs = Arc::new(
+        MemoryFileSystem::new()
+            .with_file("/store/mod.jar", "jar")
+            .with_dir("/server/mods"),
+    );
+    fs.symlink(
+        Path::new("/store/mod.jar"),
+        Path::new("/server/mods/mod.jar"),
+    )
+    .unwrap();
+
+    let (result, events) = run(fs, LinkMode::Symlink);
+
+    result.unwrap();
+    assert!(linked(&events).is_empty());
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, ExecEvent::StepSkipped { .. })));
+}
+
+#[test]
+fn test_link_refuses_to_replace_directory() {
+    let fs = Arc::new(
+        MemoryFileSystem::new()
+            .with_file("/store/mod.jar", "jar")
+            .with_dir("/server/mods/mod.jar"),
+    );
+
+    let (result, _) = run(fs.clone(), LinkMode::Copy);
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Refusing to replace directory"), "{}", error);
+    assert_eq!(
+        fs.entry_kind(Path::new("/server/mods/mod.jar")),
+        Some(EntryKind::Dir)
+    );
+}
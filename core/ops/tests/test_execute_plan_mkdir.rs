@@ -9,6 +9,7 @@ fn test_execute_plan_mkdir() {
     let new_dir = dir.path().join("subdir");
 
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::Mkdir {
             path: new_dir.to_str().unwrap().to_string(),
         }],
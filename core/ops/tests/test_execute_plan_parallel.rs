@@ -0,0 +1,93 @@
+use core_ops::Executor;
+use hymod_core_plan::{Plan, Step, StepEnv};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_execute_plan_parallel_runs_every_branch() {
+    let dir = tempdir().unwrap();
+    let branches = (0..4)
+        .map(|i| {
+            let branch_dir = dir.path().join(format!("host{}", i));
+            vec![
+                Step::Mkdir {
+                    path: branch_dir.to_str().unwrap().to_string(),
+                },
+                Step::WriteFile {
+                    path: branch_dir.join("mod.jar").to_str().unwrap().to_string(),
+                    content: format!("artifact {}", i),
+                },
+            ]
+        })
+        .collect();
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::Parallel { branches }],
+    };
+
+    Executor::new(false).with_jobs(2).execute(&plan).unwrap();
+
+    for i in 0..4 {
+        let jar = dir.path().join(format!("host{}", i)).join("mod.jar");
+        assert_eq!(fs::read_to_string(jar).unwrap(), format!("artifact {}", i));
+    }
+}
+
+#[test]
+fn test_execute_plan_parallel_failure_rolls_back_all_branches() {
+    let dir = tempdir().unwrap();
+    let ok_file = dir.path().join("ok").join("mod.jar");
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::Parallel {
+            branches: vec![
+                vec![Step::WriteFile {
+                    path: ok_file.to_str().unwrap().to_string(),
+                    content: "artifact".to_string(),
+                }],
+                vec![Step::CheckFile {
+                    path: "/non/existent/path/should/fail".to_string(),
+                }],
+            ],
+        }],
+    };
+
+    let result = Executor::new(false).with_jobs(1).execute(&plan);
+
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "File not found: /non/existent/path/should/fail"
+    );
+    assert!(!ok_file.exists());
+    assert!(!dir.path().join("ok").exists());
+}
+
+#[test]
+fn test_execute_plan_parallel_process_output() {
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::Parallel {
+            branches: vec![
+                vec![Step::RunProcess {
+                    cmd: "echo".to_string(),
+                    args: vec!["first".to_string()],
+                    cwd: None,
+                    env: StepEnv::new(),
+                    timeout_secs: None,
+                }],
+                vec![Step::RunProcess {
+                    cmd: "echo".to_string(),
+                    args: vec!["second".to_string()],
+                    cwd: None,
+                    env: StepEnv::new(),
+                    timeout_secs: None,
+                }],
+            ],
+        }],
+    };
+
+    Executor::new(false).execute(&plan).unwrap();
+}
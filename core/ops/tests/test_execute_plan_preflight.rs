@@ -0,0 +1,92 @@
+use core_error::HymodError;
+use core_ops::{CannedResult, Executor, FileSystem, MemoryFileSystem, RecordingProcessRunner};
+use hymod_core_plan::{Plan, Preflight, SshOptions, Step};
+use std::path::Path;
+use std::sync::Arc;
+
+fn executor(fs: &Arc<MemoryFileSystem>, runner: &Arc<RecordingProcessRunner>) -> Executor {
+    Executor::new(false)
+        .with_file_system(fs.clone())
+        .with_process_runner(runner.clone())
+}
+
+#[test]
+fn test_execute_plan_preflight_reports_every_problem_before_any_step() {
+    let fs = Arc::new(MemoryFileSystem::new().with_file("/srv/file", ""));
+    let runner =
+        Arc::new(RecordingProcessRunner::new().with_response("ssh", CannedResult::exit(255)));
+    let plan = Plan {
+        preflight: vec![
+            Preflight::Binary {
+                name: "hymod-no-such-binary".to_string(),
+            },
+            Preflight::Exists {
+                path: "/mod/build/libs/mod.jar".to_string(),
+            },
+            Preflight::Writable {
+                path: "/srv/file/mods".to_string(),
+            },
+            Preflight::SshReachable {
+                host: "example.com".to_string(),
+                user: "deploy".to_string(),
+                ssh: SshOptions::default(),
+            },
+        ],
+        steps: vec![Step::Mkdir {
+            path: "/server/mods".to_string(),
+        }],
+    };
+
+    let err = executor(&fs, &runner).execute(&plan).unwrap_err();
+
+    let Ok(HymodError::PreflightFailed { problems }) = err.downcast::<HymodError>() else {
+        panic!("expected a preflight failure");
+    };
+    assert_eq!(
+        problems,
+        vec![
+            "hymod-no-such-binary not found on PATH".to_string(),
+            "/mod/build/libs/mod.jar does not exist".to_string(),
+            "/srv/file is not a directory".to_string(),
+            "cannot reach deploy@example.com over SSH (exit 255)".to_string(),
+        ]
+    );
+    assert!(!fs.exists(Path::new("/server")));
+}
+
+#[test]
+fn test_execute_plan_preflight_passes_and_leaves_no_probe() {
+    let fs = Arc::new(
+        MemoryFileSystem::new()
+            .with_dir("/server")
+            .with_file("/mod/gradlew", ""),
+    );
+    let runner = Arc::new(RecordingProcessRunner::new());
+    let plan = Plan {
+        preflight: vec![
+            Preflight::Exists {
+                path: "/mod/gradlew".to_string(),
+            },
+            Preflight::Writable {
+                path: "/server/mods".to_string(),
+            },
+            Preflight::SshReachable {
+                host: "example.com".to_string(),
+                user: "deploy".to_string(),
+                ssh: SshOptions::default(),
+            },
+        ],
+        steps: vec![Step::Mkdir {
+            path: "/server/mods".to_string(),
+        }],
+    };
+
+    executor(&fs, &runner).execute(&plan).unwrap();
+
+    assert!(fs.exists(Path::new("/server/mods")));
+    assert!(!fs.exists(Path::new("/server/.hymod-preflight")));
+    assert_eq!(
+        runner.calls()[0].to_string(),
+        "ssh -o BatchMode=yes -o ConnectTimeout=10 deploy@example.com true"
+    );
+}
@@ -0,0 +1,153 @@
+use core_ops::{ExecEvent, ExecObserver, Executor};
+use hymod_core_plan::{Plan, RetryPolicy, SshOptions, Step, StepEnv};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tempfile::tempdir;
+
+/// Put a fake `ssh` on PATH. It counts its invocations in the file named by
+/// the first word of the remote command and exits with 255 until the count
+/// reaches the second word.
+fn install_fake_ssh() {
+    static FAKE_BIN: OnceLock<PathBuf> = OnceLock::new();
+    FAKE_BIN.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("hymod-fake-ssh-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let ssh = dir.join("ssh");
+        fs::write(
+            &ssh,
+            "#!/bin/sh\nset -- $2\nn=$(cat \"$1\" 2>/dev/null || echo 0)\nn=$((n+1))\necho $n > \"$1\"\n[ $n -ge $2 ] && exit 0\nexit 255\n",
+        )
+        .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&ssh, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let mut dirs = vec![dir.clone()];
+        dirs.extend(std::env::split_paths(&path));
+        std::env::set_var("PATH", std::env::join_paths(dirs).unwrap());
+        dir
+    });
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<ExecEvent>>,
+}
+
+impl ExecObserver for Recorder {
+    fn on_event(&self, event: &ExecEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+fn ssh_plan(counter: &Path, succeed_at: u32, retry: Option<RetryPolicy>) -> Plan {
+    Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::SshRun {
+            host: "example.com".to_string(),
+            user: "root".to_string(),
+            cmd: format!("{} {}", counter.display(), succeed_at),
+            env: StepEnv::new(),
+            ssh: SshOptions::default(),
+            retry,
+            timeout_secs: None,
+        }],
+    }
+}
+
+fn policy(attempts: u32, retry_on: Vec<i32>) -> Option<RetryPolicy> {
+    Some(RetryPolicy {
+        attempts,
+        backoff_ms: 1,
+        max_backoff_ms: 5,
+        retry_on,
+    })
+}
+
+fn attempts_made(counter: &Path) -> u32 {
+    fs::read_to_string(counter).unwrap().trim().parse().unwrap()
+}
+
+#[test]
+fn test_execute_plan_retry_until_success() {
+    install_fake_ssh();
+    let dir = tempdir().unwrap();
+    let counter = dir.path().join("count");
+    let recorder = Arc::new(Recorder::default());
+
+    Executor::new(false)
+        .with_observer(recorder.clone())
+        .execute(&ssh_plan(&counter, 3, policy(5, vec![])))
+        .unwrap();
+
+    assert_eq!(attempts_made(&counter), 3);
+    let retries: Vec<u32> = recorder
+        .events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|event| match event {
+            ExecEvent::StepRetrying {
+                attempt,
+                attempts: 5,
+                exit_code: Some(255),
+                ..
+            } => Some(*attempt),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(retries, vec![1, 2]);
+}
+
+#[test]
+fn test_execute_plan_retry_gives_up_after_attempts() {
+    install_fake_ssh();
+    let dir = tempdir().unwrap();
+    let counter = dir.path().join("count");
+
+    let result = Executor::new(false).execute(&ssh_plan(&counter, 10, policy(2, vec![])));
+
+    assert!(result.is_err());
+    assert_eq!(attempts_made(&counter), 2);
+}
+
+#[test]
+fn test_execute_plan_retry_only_on_listed_exit_codes() {
+    install_fake_ssh();
+    let dir = tempdir().unwrap();
+    let counter = dir.path().join("count");
+
+    let result = Executor::new(false).execute(&ssh_plan(&counter, 2, policy(3, vec![1])));
+
+    assert!(result.is_err());
+    assert_eq!(attempts_made(&counter), 1);
+}
+
+#[test]
+fn test_execute_plan_without_retry_runs_once() {
+    install_fake_ssh();
+    let dir = tempdir().unwrap();
+    let counter = dir.path().join("count");
+
+    let result = Executor::new(false).execute(&ssh_plan(&counter, 2, None));
+
+    assert!(result.is_err());
+    assert_eq!(attempts_made(&counter), 1);
+}
+
+#[test]
+fn test_retry_policy_backoff_is_exponential_and_capped() {
+    let policy = RetryPolicy {
+        attempts: 6,
+        backoff_ms: 100,
+        max_backoff_ms: 500,
+        retry_on: vec![],
+    };
+    let delays: Vec<u128> = (1..=5)
+        .map(|attempt| policy.delay_after(attempt).as_millis())
+        .collect();
+    assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+}
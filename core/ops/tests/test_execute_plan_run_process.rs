@@ -1,13 +1,16 @@
 use core_ops::Executor;
-use hymod_core_plan::{Plan, Step};
+use hymod_core_plan::{Plan, Step, StepEnv};
 
 #[test]
 fn test_execute_plan_run_process() {
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::RunProcess {
             cmd: "echo".to_string(),
             args: vec!["hello".to_string()],
             cwd: None,
+            env: StepEnv::new(),
+            timeout_secs: None,
         }],
     };
 
@@ -1,5 +1,5 @@
 use core_ops::Executor;
-use hymod_core_plan::{Plan, Step};
+use hymod_core_plan::{LinkMode, Plan, Step};
 use std::fs;
 use tempfile::tempdir;
 
@@ -13,9 +13,11 @@ fn test_execute_plan_symlink() {
     fs::write(&target, "content").unwrap();
 
     let plan = Plan {
-        steps: vec![Step::Symlink {
+        preflight: Vec::new(),
+        steps: vec![Step::Link {
             from: target.to_str().unwrap().to_string(),
             to: link.to_str().unwrap().to_string(),
+            mode: LinkMode::Symlink,
         }],
     };
 
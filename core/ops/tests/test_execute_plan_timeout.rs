@@ -0,0 +1,173 @@
+use core_error::HymodError;
+use core_ops::{CancelToken, Executor, Verbosity};
+use hymod_core_plan::{Plan, Step, StepEnv};
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+
+fn sh(script: &str, timeout_secs: Option<u64>) -> Step {
+    Step::RunProcess {
+        cmd: "sh".to_string(),
+        args: vec!["-c".to_string(), script.to_string()],
+        cwd: None,
+        env: StepEnv::new(),
+        timeout_secs,
+    }
+}
+
+fn executor() -> Executor {
+    Executor::new(false).with_verbosity(Verbosity::Quiet)
+}
+
+fn typed(err: &anyhow::Error) -> &HymodError {
+    err.downcast_ref::<HymodError>()
+        .unwrap_or_else(|| panic!("expected a HymodError, got {:#}", err))
+}
+
+#[test]
+fn test_execute_plan_step_timeout_kills_child_and_rolls_back() {
+    let dir = tempdir().unwrap();
+    let created = dir.path().join("mods");
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::Mkdir {
+                path: created.to_str().unwrap().to_string(),
+            },
+            sh("sleep 30", Some(1)),
+        ],
+    };
+
+    let started = Instant::now();
+    let err = executor().execute(&plan).unwrap_err();
+
+    assert!(started.elapsed() < Duration::from_secs(10));
+    match typed(&err) {
+        HymodError::StepTimedOut {
+            step,
+            op,
+            timeout_secs,
+        } => {
+            assert!(step.starts_with("2. RUN sh -c sleep 30"), "{}", step);
+            assert_eq!(op, "run_process");
+            assert_eq!(*timeout_secs, 1);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(typed(&err).exit_code(), 30);
+    assert!(!created.exists());
+}
+
+#[test]
+fn test_execute_plan_default_timeout_per_step_kind() {
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![sh("sleep 30", None)],
+    };
+
+    let err = executor()
+        .with_default_timeout("run_process", Duration::from_secs(1))
+        .execute(&plan)
+        .unwrap_err();
+    assert!(matches!(typed(&err), HymodError::StepTimedOut { .. }));
+
+    // Defaults for other kinds and generous step timeouts leave the step alone.
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![sh("true", Some(30))],
+    };
+    executor()
+        .with_default_timeout("ssh_run", Duration::from_secs(1))
+        .execute(&plan)
+        .unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_execute_plan_timeout_kills_whole_process_group() {
+    let dir = tempdir().unwrap();
+    let pid_file = dir.path().join("grandchild.pid");
+    let script = format!("sleep 30 & echo $! > {}; wait", pid_file.display());
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![sh(&script, Some(1))],
+    };
+
+    executor().execute(&plan).unwrap_err();
+
+    let pid = fs::read_to_string(&pid_file).unwrap();
+    let stat = Path::new("/proc").join(pid.trim()).join("stat");
+    // The grandchild is gone, or at most a zombie waiting to be reaped.
+    if let Ok(stat) = fs::read_to_string(stat) {
+        let state = stat.rsplit(')').next().unwrap().split_whitespace().next();
+        assert_eq!(state, Some("Z"), "grandchild still running: {}", stat);
+    }
+}
+
+#[test]
+fn test_execute_plan_cancel_interrupts_running_step() {
+    let dir = tempdir().unwrap();
+    let created = dir.path().join("mods");
+    let never_written = dir.path().join("after.txt");
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::Mkdir {
+                path: created.to_str().unwrap().to_string(),
+            },
+            sh("sleep 30", None),
+            Step::WriteFile {
+                path: never_written.to_str().unwrap().to_string(),
+                content: "late".to_string(),
+            },
+        ],
+    };
+
+    let cancel = CancelToken::new();
+    let trigger = cancel.clone();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(300));
+        trigger.cancel();
+    });
+
+    let started = Instant::now();
+    let err = executor()
+        .with_cancel_token(cancel)
+        .execute(&plan)
+        .unwrap_err();
+    canceller.join().unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(10));
+    match typed(&err) {
+        HymodError::Interrupted { step } => assert!(step.starts_with("2. "), "{}", step),
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(typed(&err).exit_code(), 130);
+    assert!(!created.exists());
+    assert!(!never_written.exists());
+}
+
+#[test]
+fn test_execute_plan_cancelled_before_start_runs_nothing() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::WriteFile {
+            path: path.to_str().unwrap().to_string(),
+            content: "content".to_string(),
+        }],
+    };
+
+    let cancel = CancelToken::new();
+    cancel.cancel();
+    let err = executor()
+        .with_cancel_token(cancel)
+        .execute(&plan)
+        .unwrap_err();
+
+    assert!(matches!(typed(&err), HymodError::Interrupted { .. }));
+    assert!(!path.exists());
+}
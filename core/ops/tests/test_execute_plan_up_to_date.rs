@@ -0,0 +1,85 @@
+use core_ops::{ExecEvent, ExecObserver, Executor};
+use hymod_core_plan::{Plan, Step};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use tempfile::tempdir;
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<ExecEvent>>,
+}
+
+impl ExecObserver for Recorder {
+    fn on_event(&self, event: &ExecEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+fn skipped_steps(plan: &Plan) -> Vec<String> {
+    let recorder = Arc::new(Recorder::default());
+    Executor::new(false)
+        .with_observer(recorder.clone())
+        .execute(plan)
+        .unwrap();
+    let events = recorder.events.lock().unwrap();
+    events
+        .iter()
+        .filter_map(|event| match event {
+            ExecEvent::StepSkipped { step, reason } => {
+                assert_eq!(reason, "up-to-date");
+                Some(step.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_execute_plan_copy_file_skipped_when_identical() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("mod.jar");
+    let dest = dir.path().join("mods").join("mod.jar");
+    fs::write(&source, b"jar bytes").unwrap();
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::CopyFile {
+            from: source.to_str().unwrap().to_string(),
+            to: dest.to_str().unwrap().to_string(),
+        }],
+    };
+
+    assert!(skipped_steps(&plan).is_empty());
+    assert_eq!(skipped_steps(&plan), vec!["1"]);
+
+    // Changed content is copied again
+    fs::write(&source, b"new jar bytes").unwrap();
+    assert!(skipped_steps(&plan).is_empty());
+    assert_eq!(fs::read(&dest).unwrap(), b"new jar bytes");
+}
+
+#[test]
+fn test_execute_plan_write_steps_skipped_when_identical() {
+    let dir = tempdir().unwrap();
+    let text = dir.path().join("config.txt");
+    let bytes = dir.path().join("data.bin");
+    fs::write(&text, "same").unwrap();
+    fs::write(&bytes, [0u8, 1, 2]).unwrap();
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::WriteFile {
+                path: text.to_str().unwrap().to_string(),
+                content: "same".to_string(),
+            },
+            Step::WriteBytes {
+                path: bytes.to_str().unwrap().to_string(),
+                content: vec![0, 1, 3],
+            },
+        ],
+    };
+
+    assert_eq!(skipped_steps(&plan), vec!["1"]);
+    assert_eq!(fs::read(&bytes).unwrap(), vec![0, 1, 3]);
+}
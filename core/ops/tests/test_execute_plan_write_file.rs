@@ -9,6 +9,7 @@ fn test_execute_plan_write_file() {
     let file = dir.path().join("output.txt");
 
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![Step::WriteFile {
             path: file.to_str().unwrap().to_string(),
             content: "payload".to_string(),
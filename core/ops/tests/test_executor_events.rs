@@ -0,0 +1,143 @@
+use core_ops::{ExecEvent, ExecObserver, Executor, OutputStream};
+use hymod_core_plan::{Plan, Step, StepEnv};
+use std::sync::{Arc, Mutex};
+use tempfile::tempdir;
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<ExecEvent>>,
+}
+
+impl ExecObserver for Recorder {
+    fn on_event(&self, event: &ExecEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+fn run(plan: &Plan) -> (anyhow::Result<()>, Vec<ExecEvent>) {
+    let recorder = Arc::new(Recorder::default());
+    let result = Executor::new(false)
+        .with_observer(recorder.clone())
+        .execute(plan);
+    let events = recorder.events.lock().unwrap().clone();
+    (result, events)
+}
+
+#[test]
+fn test_executor_events_for_successful_plan() {
+    let dir = tempdir().unwrap();
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::Mkdir {
+                path: dir.path().join("out").to_str().unwrap().to_string(),
+            },
+            Step::RunProcess {
+                cmd: "sh".to_string(),
+                args: vec!["-c".to_string(), "echo hello; echo oops >&2".to_string()],
+                cwd: None,
+                env: StepEnv::new(),
+                timeout_secs: None,
+            },
+        ],
+    };
+
+    let (result, events) = run(&plan);
+    result.unwrap();
+
+    assert_eq!(events.first(), Some(&ExecEvent::PlanStarted { steps: 2 }));
+    assert!(matches!(
+        events.last(),
+        Some(ExecEvent::PlanFinished { success: true, .. })
+    ));
+    assert!(events.contains(&ExecEvent::Output {
+        step: "2".to_string(),
+        stream: OutputStream::Stdout,
+        line: "hello".to_string(),
+    }));
+    assert!(events.contains(&ExecEvent::Output {
+        step: "2".to_string(),
+        stream: OutputStream::Stderr,
+        line: "oops".to_string(),
+    }));
+
+    let finished: Vec<&str> = events
+        .iter()
+        .filter_map(|event| match event {
+            ExecEvent::StepFinished { step, .. } => Some(step.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(finished, vec!["1", "2"]);
+}
+
+#[test]
+fn test_executor_events_for_failed_step() {
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::RunProcess {
+            cmd: "sh".to_string(),
+            args: vec!["-c".to_string(), "exit 3".to_string()],
+            cwd: None,
+            env: StepEnv::new(),
+            timeout_secs: None,
+        }],
+    };
+
+    let (result, events) = run(&plan);
+    assert!(result.is_err());
+
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, ExecEvent::StepFailed { step, .. } if step == "1")));
+    assert!(matches!(
+        events.last(),
+        Some(ExecEvent::PlanFinished { success: false, .. })
+    ));
+}
+
+#[test]
+fn test_executor_events_label_parallel_branches() {
+    let dir = tempdir().unwrap();
+    let branches = (0..2)
+        .map(|i| {
+            vec![Step::Mkdir {
+                path: dir
+                    .path()
+                    .join(format!("host{}", i))
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            }]
+        })
+        .collect();
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::Parallel { branches }],
+    };
+
+    let (result, events) = run(&plan);
+    result.unwrap();
+
+    for label in ["1", "1.1.1", "1.2.1"] {
+        assert!(
+            events.iter().any(
+                |event| matches!(event, ExecEvent::StepFinished { step, .. } if step == label)
+            ),
+            "missing step_finished for {}",
+            label
+        );
+    }
+}
+
+#[test]
+fn test_exec_event_serializes_as_tagged_json() {
+    let event = ExecEvent::StepFinished {
+        step: "2.1".to_string(),
+        duration_ms: 15,
+    };
+    assert_eq!(
+        serde_json::to_string(&event).unwrap(),
+        r#"{"event":"step_finished","step":"2.1","duration_ms":15}"#
+    );
+}
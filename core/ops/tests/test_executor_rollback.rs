@@ -0,0 +1,112 @@
+use core_ops::Executor;
+use hymod_core_plan::{LinkMode, Plan, Step};
+use std::fs;
+use tempfile::tempdir;
+
+fn failing_step() -> Step {
+    Step::CheckFile {
+        path: "/non/existent/path/should/fail".to_string(),
+    }
+}
+
+#[test]
+fn test_rollback_removes_created_files_and_dirs() {
+    let dir = tempdir().unwrap();
+    let project = dir.path().join("newmod");
+    let file = project.join("src").join("Main.java");
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::Mkdir {
+                path: project.to_str().unwrap().to_string(),
+            },
+            Step::WriteFile {
+                path: file.to_str().unwrap().to_string(),
+                content: "class Main {}".to_string(),
+            },
+            failing_step(),
+        ],
+    };
+
+    let result = Executor::new(false).execute(&plan);
+
+    assert!(result.is_err());
+    assert!(!file.exists());
+    assert!(!project.exists());
+    assert!(dir.path().exists());
+}
+
+#[test]
+fn test_rollback_restores_overwritten_file() {
+    let dir = tempdir().unwrap();
+    let src = dir.path().join("mod-1.1.0.jar");
+    let dest = dir.path().join("mods").join("mod.jar");
+    fs::create_dir_all(dest.parent().unwrap()).unwrap();
+    fs::write(&src, "new build").unwrap();
+    fs::write(&dest, "previous build").unwrap();
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::CopyFile {
+                from: src.to_str().unwrap().to_string(),
+                to: dest.to_str().unwrap().to_string(),
+            },
+            failing_step(),
+        ],
+    };
+
+    let result = Executor::new(false).execute(&plan);
+
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "previous build");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_rollback_restores_previous_symlink() {
+    let dir = tempdir().unwrap();
+    let old_target = dir.path().join("old.jar");
+    let new_target = dir.path().join("new.jar");
+    let link = dir.path().join("mods").join("mod.jar");
+    fs::create_dir_all(link.parent().unwrap()).unwrap();
+    fs::write(&old_target, "old").unwrap();
+    fs::write(&new_target, "new").unwrap();
+    std::os::unix::fs::symlink(&old_target, &link).unwrap();
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::Link {
+                from: new_target.to_str().unwrap().to_string(),
+                to: link.to_str().unwrap().to_string(),
+                mode: LinkMode::Symlink,
+            },
+            failing_step(),
+        ],
+    };
+
+    let result = Executor::new(false).execute(&plan);
+
+    assert!(result.is_err());
+    assert_eq!(fs::read_link(&link).unwrap(), old_target);
+}
+
+#[test]
+fn test_successful_plan_is_not_rolled_back() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("nested").join("kept.txt");
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::WriteFile {
+            path: file.to_str().unwrap().to_string(),
+            content: "kept".to_string(),
+        }],
+    };
+
+    Executor::new(false).execute(&plan).unwrap();
+
+    assert_eq!(fs::read_to_string(file).unwrap(), "kept");
+}
@@ -0,0 +1,283 @@
+use core_ops::{
+    CannedResult, ExecEvent, ExecObserver, Executor, FileSystem, MemoryFileSystem, ProcessSpec,
+    RecordingProcessRunner, Verbosity,
+};
+use hymod_core_plan::{Plan, Preflight, RetryPolicy, SshOptions, Step, StepEnv};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<ExecEvent>>,
+}
+
+impl ExecObserver for Recorder {
+    fn on_event(&self, event: &ExecEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+fn executor(fs: &Arc<MemoryFileSystem>, runner: &Arc<RecordingProcessRunner>) -> Executor {
+    Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_file_system(fs.clone())
+        .with_process_runner(runner.clone())
+}
+
+fn deploy_plan() -> Plan {
+    Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::RunProcess {
+                cmd: "/mod/gradlew".to_string(),
+                args: vec!["build".to_string()],
+                cwd: Some("/mod".to_string()),
+                env: StepEnv::new(),
+                timeout_secs: None,
+            },
+            Step::CheckFile {
+                path: "/mod/build/libs/mod.jar".to_string(),
+            },
+            Step::Mkdir {
+                path: "/server/mods".to_string(),
+            },
+            Step::CopyFile {
+                from: "/mod/build/libs/mod.jar".to_string(),
+                to: "/server/mods/mod.jar".to_string(),
+            },
+            Step::UploadScp {
+                local: "/mod/build/libs/mod.jar".to_string(),
+                remote: "deploy@example.com:/srv/mods/mod.jar".to_string(),
+                opts: String::new(),
+                ssh: SshOptions::default(),
+                retry: None,
+                timeout_secs: None,
+            },
+            Step::SshRun {
+                host: "example.com".to_string(),
+                user: "deploy".to_string(),
+                cmd: "systemctl restart hytale".to_string(),
+                env: StepEnv::new(),
+                ssh: SshOptions::default(),
+                retry: None,
+                timeout_secs: None,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_hermetic_plan_records_processes_and_writes_in_memory() {
+    let fs = Arc::new(MemoryFileSystem::new().with_file("/mod/build/libs/mod.jar", "jar bytes"));
+    // The up-to-date probe for the upload finds nothing on the remote side.
+    let runner = Arc::new(RecordingProcessRunner::new());
+
+    executor(&fs, &runner).execute(&deploy_plan()).unwrap();
+
+    assert_eq!(fs.file("/server/mods/mod.jar").unwrap(), b"jar bytes");
+    assert!(!Path::new("/server/mods/mod.jar").exists());
+
+    let programs: Vec<String> = runner.calls().iter().map(|call| call.to_string()).collect();
+    assert_eq!(
+        programs,
+        vec![
+            "/mod/gradlew build".to_string(),
+            "ssh -o BatchMode=yes deploy@example.com sha256sum -- '/srv/mods/mod.jar'".to_string(),
+            "scp /mod/build/libs/mod.jar deploy@example.com:/srv/mods/.mod.jar.hymod-tmp"
+                .to_string(),
+            "ssh deploy@example.com mv -f -- '/srv/mods/.mod.jar.hymod-tmp' '/srv/mods/mod.jar'"
+                .to_string(),
+            "ssh deploy@example.com systemctl restart hytale".to_string(),
+        ]
+    );
+    assert_eq!(
+        runner.calls()[0],
+        ProcessSpec::new("/mod/gradlew").arg("build").cwd("/mod")
+    );
+}
+
+#[test]
+fn test_hermetic_failure_rolls_back_memory_filesystem() {
+    let fs = Arc::new(
+        MemoryFileSystem::new()
+            .with_file("/mod/build/libs/mod.jar", "new jar")
+            .with_file("/server/mods/mod.jar", "old jar"),
+    );
+    let runner =
+        Arc::new(RecordingProcessRunner::new().with_response("ssh", CannedResult::exit(1)));
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::Mkdir {
+                path: "/server/config".to_string(),
+            },
+            Step::CopyFile {
+                from: "/mod/build/libs/mod.jar".to_string(),
+                to: "/server/mods/mod.jar".to_string(),
+            },
+            Step::SshRun {
+                host: "example.com".to_string(),
+                user: "deploy".to_string(),
+                cmd: "systemctl restart hytale".to_string(),
+                env: StepEnv::new(),
+                ssh: SshOptions::default(),
+                retry: None,
+                timeout_secs: None,
+            },
+        ],
+    };
+
+    let err = executor(&fs, &runner).execute(&plan).unwrap_err();
+    assert!(format!("{:#}", err).contains("Ssh command failed"));
+
+    assert_eq!(fs.file("/server/mods/mod.jar").unwrap(), b"old jar");
+    assert!(!fs.exists(Path::new("/server/config")));
+}
+
+#[test]
+fn test_hermetic_scripted_failure_is_retried() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    let runner = Arc::new(
+        RecordingProcessRunner::new()
+            .with_response("ssh", CannedResult::exit(255))
+            .with_response("ssh", CannedResult::success("restarted\n")),
+    );
+    let recorder = Arc::new(Recorder::default());
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::SshRun {
+            host: "example.com".to_string(),
+            user: "deploy".to_string(),
+            cmd: "systemctl restart hytale".to_string(),
+            env: StepEnv::new(),
+            ssh: SshOptions::default(),
+            retry: Some(RetryPolicy {
+                attempts: 2,
+                backoff_ms: 1,
+                max_backoff_ms: 1,
+                retry_on: vec![],
+            }),
+            timeout_secs: None,
+        }],
+    };
+
+    executor(&fs, &runner)
+        .with_observer(recorder.clone())
+        .execute(&plan)
+        .unwrap();
+
+    assert_eq!(runner.calls().len(), 2);
+    let events = recorder.events.lock().unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        ExecEvent::StepRetrying {
+            attempt: 1,
+            exit_code: Some(255),
+            ..
+        }
+    )));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        ExecEvent::Output { line, .. } if line == "restarted"
+    )));
+}
+
+#[test]
+fn test_hermetic_copy_skipped_when_memory_content_matches() {
+    let fs = Arc::new(
+        MemoryFileSystem::new()
+            .with_file("/mod/mod.jar", "same")
+            .with_file("/server/mods/mod.jar", "same"),
+    );
+    let runner = Arc::new(RecordingProcessRunner::new());
+    let recorder = Arc::new(Recorder::default());
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::CopyFile {
+            from: "/mod/mod.jar".to_string(),
+            to: "/server/mods/mod.jar".to_string(),
+        }],
+    };
+    executor(&fs, &runner)
+        .with_observer(recorder.clone())
+        .execute(&plan)
+        .unwrap();
+
+    let events = recorder.events.lock().unwrap();
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, ExecEvent::StepSkipped { .. })));
+}
+
+#[test]
+fn test_hermetic_ssh_options_reach_every_remote_command() {
+    let fs = Arc::new(MemoryFileSystem::new().with_file("/mod/mod.jar", "jar bytes"));
+    let runner = Arc::new(RecordingProcessRunner::new());
+    let ssh = SshOptions {
+        port: Some(2222),
+        identity_file: Some("/keys/hymod".to_string()),
+        known_hosts_file: Some("/keys/known_hosts".to_string()),
+    };
+
+    let plan = Plan {
+        preflight: vec![Preflight::SshReachable {
+            host: "example.com".to_string(),
+            user: "deploy".to_string(),
+            ssh: ssh.clone(),
+        }],
+        steps: vec![
+            Step::UploadRsync {
+                local: "/mod/mod.jar".to_string(),
+                remote: "deploy@example.com:/srv/mods/a.jar".to_string(),
+                opts: "-az --partial".to_string(),
+                ssh: ssh.clone(),
+                retry: None,
+                timeout_secs: None,
+            },
+            Step::UploadScp {
+                local: "/mod/mod.jar".to_string(),
+                remote: "deploy@example.com:/srv/mods/b.jar".to_string(),
+                opts: "-C".to_string(),
+                ssh: ssh.clone(),
+                retry: None,
+                timeout_secs: None,
+            },
+            Step::SshRun {
+                host: "example.com".to_string(),
+                user: "deploy".to_string(),
+                cmd: "systemctl restart hytale".to_string(),
+                env: StepEnv::new(),
+                ssh,
+                retry: None,
+                timeout_secs: None,
+            },
+        ],
+    };
+    executor(&fs, &runner).execute(&plan).unwrap();
+
+    let calls: Vec<String> = runner.calls().iter().map(|call| call.to_string()).collect();
+    let opts = "-p 2222 -i /keys/hymod -o UserKnownHostsFile=/keys/known_hosts";
+    assert_eq!(
+        calls,
+        vec![
+            format!("ssh -o BatchMode=yes -o ConnectTimeout=10 {} deploy@example.com true", opts),
+            format!("ssh {} -o BatchMode=yes deploy@example.com sha256sum -- '/srv/mods/a.jar'", opts),
+            format!("ssh {} deploy@example.com test ! -f '/srv/mods/a.jar' || cp -f -- '/srv/mods/a.jar' '/srv/mods/.a.jar.hymod-tmp'", opts),
+            format!("rsync -az --partial -e ssh {} /mod/mod.jar deploy@example.com:/srv/mods/.a.jar.hymod-tmp", opts),
+            format!("ssh {} deploy@example.com mv -f -- '/srv/mods/.a.jar.hymod-tmp' '/srv/mods/a.jar'", opts),
+            format!("ssh {} -o BatchMode=yes deploy@example.com sha256sum -- '/srv/mods/b.jar'", opts),
+            "scp -P 2222 -i /keys/hymod -o UserKnownHostsFile=/keys/known_hosts -C /mod/mod.jar deploy@example.com:/srv/mods/.b.jar.hymod-tmp".to_string(),
+            format!("ssh {} deploy@example.com mv -f -- '/srv/mods/.b.jar.hymod-tmp' '/srv/mods/b.jar'", opts),
+            format!("ssh {} deploy@example.com systemctl restart hytale", opts),
+        ]
+    );
+    let rsync = &runner.calls()[3];
+    assert_eq!(
+        rsync.args[3],
+        format!("ssh {}", opts),
+        "the ssh command reaches rsync as a single -e argument"
+    );
+}
@@ -1,26 +1,32 @@
 use core_ops::Executor;
-use hymod_core_plan::{Plan, Step};
+use hymod_core_plan::{LinkMode, Plan, Step, StepEnv};
 
 #[test]
 fn test_dry_run_output() {
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![
             Step::RunProcess {
                 cmd: "./gradlew".to_string(),
                 args: vec!["test".to_string(), "build".to_string()],
                 cwd: Some("/repo".to_string()),
+                env: StepEnv::new(),
+                timeout_secs: None,
             },
             Step::CheckFile {
                 path: "/repo/build/hymod/artifact.zip".to_string(),
             },
-            Step::Symlink {
+            Step::Link {
                 from: "/repo/build/hymod/artifact.zip".to_string(),
                 to: "/opt/hytale-local/mods/com.acme.power".to_string(),
+                mode: LinkMode::Symlink,
             },
             Step::RunProcess {
                 cmd: "/opt/hytale-local/bin/restart.sh".to_string(),
                 args: vec![],
                 cwd: None,
+                env: StepEnv::new(),
+                timeout_secs: None,
             },
         ],
     };
@@ -0,0 +1,138 @@
+use core_ops::observers::RunLogWriter;
+use core_ops::{ExecEvent, ExecObserver, Executor, Verbosity};
+use hymod_core_plan::{Plan, Preflight, Step, StepEnv};
+use std::fs;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn sh(script: &str) -> Step {
+    Step::RunProcess {
+        cmd: "sh".to_string(),
+        args: vec!["-c".to_string(), script.to_string()],
+        cwd: None,
+        env: StepEnv::new(),
+        timeout_secs: None,
+    }
+}
+
+#[test]
+fn test_run_log_records_plan_output_and_exit_codes() {
+    let dir = tempdir().unwrap();
+    let log_path = dir.path().join("logs").join("deploy-test.log");
+    let writer = Arc::new(RunLogWriter::new(&log_path, "$ hymod deploy prod"));
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::Mkdir {
+                path: dir.path().join("out").to_str().unwrap().to_string(),
+            },
+            sh("echo uploading; echo 'disk full' >&2; exit 3"),
+        ],
+    };
+    let result = Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_observer(writer.clone())
+        .execute(&plan);
+    assert!(result.is_err());
+    assert!(writer.is_written());
+
+    let log = fs::read_to_string(&log_path).unwrap();
+    assert!(log.starts_with("$ hymod deploy prod\n"));
+    assert!(log.contains("== plan (2 steps)"));
+    assert!(log.contains("  2. RUN      sh -c echo uploading"));
+    assert!(log.contains("[2] uploading"));
+    assert!(log.contains("[2 stderr] disk full"));
+    assert!(log.contains("-- 2: exit 3 `sh -c echo uploading; echo 'disk full' >&2; exit 3`"));
+    assert!(log.contains("-- 1: finished in"));
+    assert!(log.contains("!! 2: failed after"));
+    assert!(log
+        .trim_end()
+        .lines()
+        .last()
+        .unwrap()
+        .starts_with("== plan failed after"));
+}
+
+#[test]
+fn test_run_log_appends_every_plan_of_a_command() {
+    let dir = tempdir().unwrap();
+    let log_path = dir.path().join("dev-test.log");
+    let writer = Arc::new(RunLogWriter::new(&log_path, "$ hymod dev"));
+    let executor = Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_observer(writer.clone());
+
+    executor
+        .execute(&Plan {
+            preflight: Vec::new(),
+            steps: vec![sh("echo build")],
+        })
+        .unwrap();
+    executor
+        .execute(&Plan {
+            preflight: Vec::new(),
+            steps: vec![sh("echo copy")],
+        })
+        .unwrap();
+
+    let log = fs::read_to_string(&log_path).unwrap();
+    assert_eq!(log.matches("$ hymod dev").count(), 1);
+    assert_eq!(log.matches("== plan succeeded").count(), 2);
+    assert!(log.contains("[1] build") && log.contains("[1] copy"));
+}
+
+#[test]
+fn test_run_log_records_failed_preflight() {
+    let dir = tempdir().unwrap();
+    let log_path = dir.path().join("deploy-test.log");
+    let writer = Arc::new(RunLogWriter::new(&log_path, "$ hymod deploy prod"));
+    let missing = dir.path().join("build.gradle");
+
+    let result = Executor::new(false)
+        .with_verbosity(Verbosity::Quiet)
+        .with_observer(writer.clone())
+        .execute(&Plan {
+            preflight: vec![Preflight::Exists {
+                path: missing.to_str().unwrap().to_string(),
+            }],
+            steps: vec![sh("echo never")],
+        });
+    assert!(result.is_err());
+    assert!(writer.is_written());
+
+    let log = fs::read_to_string(&log_path).unwrap();
+    assert!(log.contains("== preflight failed"));
+    assert!(log.contains(&format!("!! {} does not exist", missing.display())));
+    assert!(!log.contains("== plan ("));
+}
+
+#[test]
+fn test_run_log_is_not_created_without_a_run() {
+    let dir = tempdir().unwrap();
+    let log_path = dir.path().join("server-test.log");
+    let writer = Arc::new(RunLogWriter::new(&log_path, "$ hymod server list"));
+
+    Executor::new(true)
+        .with_observer(writer.clone())
+        .execute(&Plan {
+            preflight: Vec::new(),
+            steps: vec![sh("true")],
+        })
+        .unwrap();
+
+    assert!(!writer.is_written());
+    assert!(!log_path.exists());
+}
+
+#[test]
+fn test_run_log_writer_leaves_text_output_in_place() {
+    struct Renderer;
+    impl ExecObserver for Renderer {
+        fn on_event(&self, _event: &ExecEvent) {}
+    }
+
+    let writer = RunLogWriter::new("unused.log", "");
+    assert!(!writer.renders_output());
+    assert!(Renderer.renders_output());
+}
@@ -39,6 +39,23 @@ pub fn resolve_mod_artifact(mod_dir: &Path) -> ResolvedArtifact {
     }
 }
 
+/// Like `resolve_mod_artifact`, but an `artifact_path` set for the project
+/// (relative to `mod_dir`) names the artifact instead.
+pub fn resolve_mod_artifact_with(mod_dir: &Path, artifact_path: Option<&str>) -> ResolvedArtifact {
+    let Some(artifact_path) = artifact_path else {
+        return resolve_mod_artifact(mod_dir);
+    };
+    let source_path = mod_dir.join(artifact_path);
+    let target_file_name = source_path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| artifact_path.to_string());
+    ResolvedArtifact {
+        source_path,
+        target_file_name,
+    }
+}
+
 fn find_existing_artifact(build_libs: &Path) -> Option<PathBuf> {
     let entries = fs::read_dir(build_libs).ok()?;
     let mut candidates = Vec::new();
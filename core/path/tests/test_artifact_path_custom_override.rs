@@ -1,4 +1,15 @@
+use core_path::resolve_mod_artifact_with;
+use std::path::Path;
+
 #[test]
 fn test_artifact_path_custom_override() {
-    panic!("Must use custom artifact path from hymod.yaml build.gradle.artifact_path when set.");
+    let mod_dir = Path::new("/repo");
+
+    let artifact = resolve_mod_artifact_with(mod_dir, Some("build/custom/my-mod-all.jar"));
+
+    assert_eq!(
+        artifact.source_path,
+        Path::new("/repo/build/custom/my-mod-all.jar")
+    );
+    assert_eq!(artifact.target_file_name, "my-mod-all.jar");
 }
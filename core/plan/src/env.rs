@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Environment variables a step sets for its child process, by name.
+pub type StepEnv = BTreeMap<String, EnvValue>;
+
+/// What plan rendering, logs and events show instead of a secret value.
+pub const SECRET_MASK: &str = "********";
+
+/// Value of one environment variable. Written in plan files either as a plain
+/// string or as `{ secret: "..." }`; secrets are passed to the child process
+/// unchanged but masked wherever hymod displays them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EnvValue {
+    Plain(String),
+    Secret { secret: String },
+}
+
+impl EnvValue {
+    /// The actual value handed to the child process.
+    pub fn value(&self) -> &str {
+        match self {
+            EnvValue::Plain(value) | EnvValue::Secret { secret: value } => value,
+        }
+    }
+
+    pub fn is_secret(&self) -> bool {
+        matches!(self, EnvValue::Secret { .. })
+    }
+}
+
+impl fmt::Display for EnvValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvValue::Plain(value) => write!(f, "{}", value),
+            EnvValue::Secret { .. } => write!(f, "{}", SECRET_MASK),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::plan::Plan;
+use crate::preflight::Preflight;
+use crate::step::Step;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Version of the on-disk plan format written by `save_plan`.
+pub const PLAN_FORMAT_VERSION: u32 = 1;
+
+/// On-disk representation of a plan: a format version, the preflight checks
+/// and the ordered steps.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlanFile {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preflight: Vec<Preflight>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Serialize)]
+struct PlanFileRef<'a> {
+    version: u32,
+    #[serde(skip_serializing_if = "<[Preflight]>::is_empty")]
+    preflight: &'a [Preflight],
+    steps: &'a [Step],
+}
+
+enum PlanFormat {
+    Json,
+    Yaml,
+}
+
+fn format_for(path: &Path) -> PlanFormat {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml") | Some("yml") => PlanFormat::Yaml,
+        _ => PlanFormat::Json,
+    }
+}
+
+/// Write `plan` to `path`. Files ending in `.yaml`/`.yml` are written as YAML, anything else as JSON.
+/// Plans with secret environment values are refused, since the file would hold them in clear text.
+pub fn save_plan(plan: &Plan, path: &Path) -> Result<(), String> {
+    let mut secrets = Vec::new();
+    collect_secret_env_names(&plan.steps, &mut secrets);
+    if !secrets.is_empty() {
+        return Err(format!(
+            "Refusing to write plan {}: the secret values of {} would be saved in clear text",
+            path.display(),
+            secrets.join(", ")
+        ));
+    }
+    save_plan_with_secrets(plan, path)
+}
+
+/// Like `save_plan`, but writes secret environment values in clear text.
+pub fn save_plan_with_secrets(plan: &Plan, path: &Path) -> Result<(), String> {
+    let file = PlanFileRef {
+        version: PLAN_FORMAT_VERSION,
+        preflight: &plan.preflight,
+        steps: &plan.steps,
+    };
+
+    let content = match format_for(path) {
+        PlanFormat::Yaml => serde_yaml::to_string(&file).map_err(|e| e.to_string())?,
+        PlanFormat::Json => {
+            let mut json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+            json.push('\n');
+            json
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write plan {}: {}", path.display(), e))
+}
+
+/// Names of the secret environment variables set by `steps`, including
+/// those of parallel branches.
+fn collect_secret_env_names(steps: &[Step], names: &mut Vec<String>) {
+    for step in steps {
+        match step {
+            Step::RunProcess { env, .. } | Step::SshRun { env, .. } => {
+                for (name, value) in env {
+                    if value.is_secret() && !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+            }
+            Step::Parallel { branches } => {
+                for branch in branches {
+                    collect_secret_env_names(branch, names);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Read a plan previously written by `save_plan`.
+pub fn load_plan(path: &Path) -> Result<Plan, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read plan {}: {}", path.display(), e))?;
+
+    let parsed: Result<PlanFile, String> = match format_for(path) {
+        PlanFormat::Yaml => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+        PlanFormat::Json => serde_json::from_str(&content).map_err(|e| e.to_string()),
+    };
+    let file = parsed.map_err(|e| format!("Invalid plan file {}: {}", path.display(), e))?;
+
+    if file.version != PLAN_FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported plan version {} in {} (expected {})",
+            file.version,
+            path.display(),
+            PLAN_FORMAT_VERSION
+        ));
+    }
+
+    Ok(Plan {
+        preflight: file.preflight,
+        steps: file.steps,
+    })
+}
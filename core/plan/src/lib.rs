@@ -1,5 +1,17 @@
+pub mod env;
+pub mod file;
+pub mod link;
 pub mod plan;
+pub mod preflight;
+pub mod retry;
+pub mod ssh;
 pub mod step;
 
+pub use env::{EnvValue, StepEnv, SECRET_MASK};
+pub use file::{load_plan, save_plan, save_plan_with_secrets, PlanFile, PLAN_FORMAT_VERSION};
+pub use link::LinkMode;
 pub use plan::Plan;
+pub use preflight::Preflight;
+pub use retry::RetryPolicy;
+pub use ssh::SshOptions;
 pub use step::Step;
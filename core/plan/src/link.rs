@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How a `Step::Link` puts a file in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    #[default]
+    Symlink,
+    Hardlink,
+    Copy,
+}
+
+impl LinkMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkMode::Symlink => "symlink",
+            LinkMode::Hardlink => "hardlink",
+            LinkMode::Copy => "copy",
+        }
+    }
+
+    /// This mode followed by the ones to try, in order, when it fails: a
+    /// symlink falls back to a hardlink, and a hardlink to a copy.
+    pub fn fallback_chain(self) -> &'static [LinkMode] {
+        match self {
+            LinkMode::Symlink => &[LinkMode::Symlink, LinkMode::Hardlink, LinkMode::Copy],
+            LinkMode::Hardlink => &[LinkMode::Hardlink, LinkMode::Copy],
+            LinkMode::Copy => &[LinkMode::Copy],
+        }
+    }
+}
+
+impl fmt::Display for LinkMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
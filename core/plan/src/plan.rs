@@ -1,6 +1,11 @@
+use crate::preflight::Preflight;
 use crate::step::Step;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Plan {
+    /// Checked before the first step runs; see `Preflight`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preflight: Vec<Preflight>,
     pub steps: Vec<Step>,
 }
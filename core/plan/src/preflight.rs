@@ -0,0 +1,36 @@
+use crate::ssh::SshOptions;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A condition checked before any step of a plan runs. The executor checks
+/// every entry of `Plan::preflight` and fails with all problems at once,
+/// before the plan changes anything.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum Preflight {
+    /// An executable `name` is on `PATH`.
+    Binary { name: String },
+    /// Something exists at `path`, e.g. the mod's build script or artifact.
+    Exists { path: String },
+    /// Files can be created in the directory `path`, or in its nearest
+    /// existing ancestor if the plan is going to create it.
+    Writable { path: String },
+    /// `user@host` accepts a non-interactive SSH login.
+    SshReachable {
+        host: String,
+        user: String,
+        #[serde(default, skip_serializing_if = "SshOptions::is_default")]
+        ssh: SshOptions,
+    },
+}
+
+impl fmt::Display for Preflight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Preflight::Binary { name } => write!(f, "BINARY    {}", name),
+            Preflight::Exists { path } => write!(f, "EXISTS    {}", path),
+            Preflight::Writable { path } => write!(f, "WRITABLE  {}", path),
+            Preflight::SshReachable { host, user, .. } => write!(f, "SSH       {}@{}", user, host),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How often a network step is attempted before the plan fails.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub attempts: u32,
+    /// Delay before the second attempt; doubled after every further failure.
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Upper bound for the delay between two attempts.
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Exit codes worth retrying. Empty means any non-zero exit code.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retry_on: Vec<i32>,
+}
+
+fn default_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
+impl RetryPolicy {
+    /// Whether a failed attempt with this exit code (`None` if the process was
+    /// killed by a signal) should be retried.
+    pub fn is_retryable(&self, exit_code: Option<i32>) -> bool {
+        self.retry_on.is_empty() || exit_code.is_some_and(|code| self.retry_on.contains(&code))
+    }
+
+    /// Delay after the given failed attempt (1-based) before trying again.
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(32);
+        Duration::from_millis(
+            self.backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// How to reach a remote host beyond `user@host`. Unset fields leave the
+/// choice to ssh and its `~/.ssh/config`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SshOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_hosts_file: Option<String>,
+}
+
+impl SshOptions {
+    pub fn is_default(&self) -> bool {
+        *self == SshOptions::default()
+    }
+
+    /// Options for `ssh` itself.
+    pub fn ssh_args(&self) -> Vec<String> {
+        self.args("-p")
+    }
+
+    /// Options for `scp`, which takes the port as `-P`.
+    pub fn scp_args(&self) -> Vec<String> {
+        self.args("-P")
+    }
+
+    /// The remote shell for `rsync -e`, or `None` when plain ssh will do.
+    pub fn rsync_shell(&self) -> Option<String> {
+        if self.is_default() {
+            return None;
+        }
+        let mut shell = vec!["ssh".to_string()];
+        shell.extend(self.ssh_args().iter().map(|arg| shell_word(arg)));
+        Some(shell.join(" "))
+    }
+
+    fn args(&self, port_flag: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(port) = self.port {
+            args.extend([port_flag.to_string(), port.to_string()]);
+        }
+        if let Some(identity_file) = &self.identity_file {
+            args.extend(["-i".to_string(), identity_file.clone()]);
+        }
+        if let Some(known_hosts_file) = &self.known_hosts_file {
+            args.extend([
+                "-o".to_string(),
+                format!("UserKnownHostsFile={}", known_hosts_file),
+            ]);
+        }
+        args
+    }
+}
+
+/// `arg` quoted for the command line rsync hands to the shell.
+fn shell_word(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=~:@".contains(c))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
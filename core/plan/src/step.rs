@@ -1,9 +1,23 @@
-#[derive(Debug, PartialEq)]
+use crate::env::StepEnv;
+use crate::link::LinkMode;
+use crate::retry::RetryPolicy;
+use crate::ssh::SshOptions;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
 pub enum Step {
     RunProcess {
         cmd: String,
         args: Vec<String>,
         cwd: Option<String>,
+        /// Extra environment variables for the child process.
+        #[serde(default, skip_serializing_if = "StepEnv::is_empty")]
+        env: StepEnv,
+        /// Kill the child if it runs longer than this; falls back to the
+        /// executor's default for the step kind.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
     },
     WriteFile {
         path: String,
@@ -20,61 +34,248 @@ pub enum Step {
         from: String,
         to: String,
     },
-    Symlink {
+    /// Deletes a file; a missing file is already removed.
+    RemoveFile {
+        path: String,
+    },
+    /// Makes `to` point at `from`. If `mode` does not work on this system,
+    /// the next mode of `LinkMode::fallback_chain` is tried.
+    #[serde(alias = "symlink")]
+    Link {
         from: String,
         to: String,
+        #[serde(default)]
+        mode: LinkMode,
     },
     UploadRsync {
         local: String,
         remote: String,
         opts: String,
+        /// Port, key and known_hosts file to connect with.
+        #[serde(default, skip_serializing_if = "SshOptions::is_default")]
+        ssh: SshOptions,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry: Option<RetryPolicy>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
     },
     UploadScp {
         local: String,
         remote: String,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        opts: String,
+        /// Port, key and known_hosts file to connect with.
+        #[serde(default, skip_serializing_if = "SshOptions::is_default")]
+        ssh: SshOptions,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry: Option<RetryPolicy>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
     },
     SshRun {
         host: String,
         user: String,
         cmd: String,
+        /// Port, key and known_hosts file to connect with.
+        #[serde(default, skip_serializing_if = "SshOptions::is_default")]
+        ssh: SshOptions,
+        /// Exported in the remote shell before `cmd` runs.
+        #[serde(default, skip_serializing_if = "StepEnv::is_empty")]
+        env: StepEnv,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry: Option<RetryPolicy>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
     },
     CheckFile {
         path: String,
     },
+    /// Opens the archive and verifies its central directory and the CRC of
+    /// every entry, then that the `require`d entries are present.
     CheckZip {
         path: String,
+        /// Entries that must be in the archive, e.g. `manifest.json`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        require: Vec<String>,
+        /// Also require the class named by `Main` in the archive's `manifest.json`.
+        #[serde(default, skip_serializing_if = "is_false")]
+        require_main_class: bool,
+    },
+    /// A stage whose branches run concurrently; steps within a branch run in order.
+    Parallel {
+        branches: Vec<Vec<Step>>,
+    },
+    /// A step kind defined outside hymod, run by the handler the embedding
+    /// tool registered for `kind`. `payload` is passed through untouched.
+    Custom {
+        kind: String,
+        #[serde(default)]
+        payload: serde_json::Value,
     },
 }
 
+impl Step {
+    /// The step's `op` tag as written in plan files, e.g. `run_process`.
+    pub fn op(&self) -> &'static str {
+        match self {
+            Step::RunProcess { .. } => "run_process",
+            Step::WriteFile { .. } => "write_file",
+            Step::WriteBytes { .. } => "write_bytes",
+            Step::Mkdir { .. } => "mkdir",
+            Step::CopyFile { .. } => "copy_file",
+            Step::RemoveFile { .. } => "remove_file",
+            Step::Link { .. } => "link",
+            Step::UploadRsync { .. } => "upload_rsync",
+            Step::UploadScp { .. } => "upload_scp",
+            Step::SshRun { .. } => "ssh_run",
+            Step::CheckFile { .. } => "check_file",
+            Step::CheckZip { .. } => "check_zip",
+            Step::Parallel { .. } => "parallel",
+            Step::Custom { .. } => "custom",
+        }
+    }
+
+    /// Values of the step's secret environment variables, which must never be displayed.
+    pub fn secret_values(&self) -> Vec<&str> {
+        match self {
+            Step::RunProcess { env, .. } | Step::SshRun { env, .. } => env
+                .values()
+                .filter(|value| value.is_secret())
+                .map(|value| value.value())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Timeout written on the step itself, for the kinds that start a child process.
+    pub fn timeout_secs(&self) -> Option<u64> {
+        match self {
+            Step::RunProcess { timeout_secs, .. }
+            | Step::UploadRsync { timeout_secs, .. }
+            | Step::UploadScp { timeout_secs, .. }
+            | Step::SshRun { timeout_secs, .. } => *timeout_secs,
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for Step {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Step::RunProcess { cmd, args, cwd } => {
+            Step::RunProcess {
+                cmd,
+                args,
+                cwd,
+                env,
+                timeout_secs,
+            } => {
                 let args_str = args.join(" ");
                 let cwd_str = cwd
                     .as_ref()
                     .map(|c| format!("(cwd: {})", c))
                     .unwrap_or_default();
-                write!(f, "RUN      {} {}    {}", cmd, args_str, cwd_str)
+                write!(f, "RUN      {} {}    {}", cmd, args_str, cwd_str)?;
+                write_env(f, env)?;
+                write_timeout(f, timeout_secs)
             }
             Step::WriteFile { path, .. } => write!(f, "WRITE     {}", path),
             Step::WriteBytes { path, .. } => write!(f, "WRITE(B)  {}", path),
             Step::Mkdir { path } => write!(f, "MKDIR     {}", path),
             Step::CopyFile { from, to } => write!(f, "COPY      {} -> {}", from, to),
-            Step::Symlink { from, to } => write!(f, "SYMLINK   {} -> {}", to, from), // "link -> target" visual flow
+            Step::RemoveFile { path } => write!(f, "REMOVE    {}", path),
+            // "link -> target" visual flow
+            Step::Link { from, to, mode } => {
+                write!(f, "LINK      {} -> {}    ({})", to, from, mode)
+            }
             Step::UploadRsync {
                 local,
                 remote,
-                opts: _,
-            } => write!(f, "UPLOAD(RSYNC) {} -> {}", local, remote),
-            Step::UploadScp { local, remote } => write!(f, "UPLOAD(SCP)   {} -> {}", local, remote),
+                retry,
+                timeout_secs,
+                ..
+            } => {
+                write!(f, "UPLOAD(RSYNC) {} -> {}", local, remote)?;
+                write_retry(f, retry)?;
+                write_timeout(f, timeout_secs)
+            }
+            Step::UploadScp {
+                local,
+                remote,
+                retry,
+                timeout_secs,
+                ..
+            } => {
+                write!(f, "UPLOAD(SCP)   {} -> {}", local, remote)?;
+                write_retry(f, retry)?;
+                write_timeout(f, timeout_secs)
+            }
             Step::SshRun {
-                host: _,
-                user: _,
                 cmd,
-            } => write!(f, "RUN       {}", cmd), // Simplified for dry-run visibility
+                env,
+                retry,
+                timeout_secs,
+                ..
+            } => {
+                write!(f, "RUN       {}", cmd)?; // Simplified for dry-run visibility
+                write_env(f, env)?;
+                write_retry(f, retry)?;
+                write_timeout(f, timeout_secs)
+            }
             Step::CheckFile { path } => write!(f, "CHECK     {}", path),
-            Step::CheckZip { path } => write!(f, "CHECK     {}", path),
+            Step::CheckZip {
+                path,
+                require,
+                require_main_class,
+            } => {
+                write!(f, "CHECK     {}", path)?;
+                let mut required = require.clone();
+                if *require_main_class {
+                    required.push("main class".to_string());
+                }
+                if !required.is_empty() {
+                    write!(f, "    (requires: {})", required.join(", "))?;
+                }
+                Ok(())
+            }
+            Step::Parallel { branches } => write!(f, "PARALLEL  {} branches", branches.len()),
+            Step::Custom { kind, payload } => {
+                write!(f, "CUSTOM    {}", kind)?;
+                if !payload.is_null() {
+                    write!(f, " {}", payload)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_retry(f: &mut std::fmt::Formatter<'_>, retry: &Option<RetryPolicy>) -> std::fmt::Result {
+    match retry {
+        Some(policy) if policy.attempts > 1 => {
+            write!(f, "    (retry: {} attempts)", policy.attempts)
         }
+        _ => Ok(()),
+    }
+}
+
+fn write_timeout(f: &mut std::fmt::Formatter<'_>, timeout_secs: &Option<u64>) -> std::fmt::Result {
+    match timeout_secs {
+        Some(secs) => write!(f, "    (timeout: {}s)", secs),
+        None => Ok(()),
+    }
+}
+
+fn write_env(f: &mut std::fmt::Formatter<'_>, env: &StepEnv) -> std::fmt::Result {
+    if env.is_empty() {
+        return Ok(());
+    }
+    write!(f, "    (env:")?;
+    for (name, value) in env {
+        write!(f, " {}={}", name, value)?;
     }
+    write!(f, ")")
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
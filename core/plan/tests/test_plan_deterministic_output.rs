@@ -1,13 +1,16 @@
-use core_plan::{Plan, Step};
+use core_plan::{Plan, Step, StepEnv};
 
 #[test]
 fn test_plan_deterministic_output() {
     let plan = Plan {
+        preflight: Vec::new(),
         steps: vec![
             Step::RunProcess {
                 cmd: "echo".to_string(),
                 args: vec!["hello".to_string()],
                 cwd: Some("/repo".to_string()),
+                env: StepEnv::new(),
+                timeout_secs: None,
             },
             Step::CheckFile {
                 path: "/path/to/file".to_string(),
@@ -1,4 +1,4 @@
-use core_plan::Step;
+use core_plan::{SshOptions, Step};
 
 #[test]
 fn test_plan_dry_run_format() {
@@ -11,6 +11,9 @@ fn test_plan_dry_run_format() {
         local: "src".to_string(),
         remote: "dest".to_string(),
         opts: "-avz".to_string(),
+        ssh: SshOptions::default(),
+        retry: None,
+        timeout_secs: None,
     };
     assert_eq!(format!("{}", step), "UPLOAD    src -> dest");
 }
@@ -0,0 +1,223 @@
+use core_plan::{
+    load_plan, save_plan, save_plan_with_secrets, LinkMode, Plan, Preflight, RetryPolicy,
+    SshOptions, Step, StepEnv,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn sample_plan() -> Plan {
+    Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::RunProcess {
+                cmd: "./gradlew".to_string(),
+                args: vec!["build".to_string()],
+                cwd: Some("/repo".to_string()),
+                env: StepEnv::new(),
+                timeout_secs: Some(1800),
+            },
+            Step::WriteBytes {
+                path: "/repo/lib.bin".to_string(),
+                content: vec![0, 1, 254, 255],
+            },
+            Step::UploadScp {
+                local: "/repo/build/libs/mod-1.0.0.jar".to_string(),
+                remote: "user@host:/srv/hytale/mods/mod-1.0.0.jar".to_string(),
+                opts: String::new(),
+                ssh: SshOptions::default(),
+                retry: Some(RetryPolicy {
+                    attempts: 3,
+                    backoff_ms: 500,
+                    max_backoff_ms: 4000,
+                    retry_on: vec![255],
+                }),
+                timeout_secs: None,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_plan_file_roundtrip_json() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.json");
+
+    save_plan(&sample_plan(), &path).unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("\"version\": 1"));
+    assert!(content.contains("\"op\": \"upload_scp\""));
+
+    let loaded = load_plan(&path).unwrap();
+    assert_eq!(loaded.steps, sample_plan().steps);
+}
+
+#[test]
+fn test_plan_file_roundtrip_yaml() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.yaml");
+
+    save_plan(&sample_plan(), &path).unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("version: 1"));
+    assert!(content.contains("op: run_process"));
+
+    let loaded = load_plan(&path).unwrap();
+    assert_eq!(loaded.steps, sample_plan().steps);
+}
+
+#[test]
+fn test_plan_file_rejects_unknown_version() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.json");
+    fs::write(&path, r#"{"version": 99, "steps": []}"#).unwrap();
+
+    let err = load_plan(&path).unwrap_err();
+    assert!(err.contains("Unsupported plan version 99"));
+}
+
+#[test]
+fn test_plan_file_retry_defaults() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.yaml");
+    fs::write(
+        &path,
+        "version: 1\nsteps:\n  - op: ssh_run\n    host: example.com\n    user: root\n    cmd: uptime\n    retry:\n      attempts: 4\n",
+    )
+    .unwrap();
+
+    let plan = load_plan(&path).unwrap();
+    let Step::SshRun { retry, .. } = &plan.steps[0] else {
+        panic!("expected ssh_run step");
+    };
+    let retry = retry.as_ref().unwrap();
+    assert_eq!(retry.attempts, 4);
+    assert_eq!(retry.backoff_ms, 1000);
+    assert!(retry.retry_on.is_empty());
+}
+
+#[test]
+fn test_plan_file_custom_step_roundtrip_yaml() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.yaml");
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![Step::Custom {
+            kind: "snapshot_world".to_string(),
+            payload: serde_json::json!({ "world": "default", "keep": 3 }),
+        }],
+    };
+
+    save_plan(&plan, &path).unwrap();
+    let loaded = load_plan(&path).unwrap();
+
+    assert_eq!(loaded.steps, plan.steps);
+    assert!(fs::read_to_string(&path).unwrap().contains("op: custom"));
+}
+
+#[test]
+fn test_plan_file_timeout_is_optional() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.yaml");
+    fs::write(
+        &path,
+        "version: 1\nsteps:\n  - op: ssh_run\n    host: example.com\n    user: root\n    cmd: uptime\n    timeout_secs: 60\n  - op: run_process\n    cmd: ./gradlew\n    args: [build]\n    cwd: null\n",
+    )
+    .unwrap();
+
+    let plan = load_plan(&path).unwrap();
+    assert_eq!(plan.steps[0].timeout_secs(), Some(60));
+    assert_eq!(plan.steps[1].timeout_secs(), None);
+    assert_eq!(
+        plan.steps[0].to_string(),
+        "RUN       uptime    (timeout: 60s)"
+    );
+
+    save_plan(&plan, &path).unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    assert_eq!(content.matches("timeout_secs").count(), 1);
+}
+
+#[test]
+fn test_plan_file_env_secret_roundtrip_yaml() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.yaml");
+    fs::write(
+        &path,
+        "version: 1\nsteps:\n  - op: run_process\n    cmd: ./deploy.sh\n    args: []\n    cwd: null\n    env:\n      REGION: eu-west\n      API_TOKEN:\n        secret: hunter2\n",
+    )
+    .unwrap();
+
+    let plan = load_plan(&path).unwrap();
+    assert_eq!(plan.steps[0].secret_values(), vec!["hunter2"]);
+    let rendered = plan.steps[0].to_string();
+    assert!(rendered.ends_with("(env: API_TOKEN=******** REGION=eu-west)"));
+    assert!(!rendered.contains("hunter2"));
+
+    // Saving would put the secret in clear text, so it takes an explicit opt-in.
+    let saved = dir.path().join("saved.yaml");
+    let err = save_plan(&plan, &saved).unwrap_err();
+    assert!(err.contains("API_TOKEN"), "{}", err);
+    assert!(!saved.exists());
+
+    save_plan_with_secrets(&plan, &saved).unwrap();
+    assert_eq!(load_plan(&saved).unwrap().steps, plan.steps);
+    assert!(fs::read_to_string(&saved)
+        .unwrap()
+        .contains("secret: hunter2"));
+}
+
+#[test]
+fn test_plan_file_preflight_roundtrip_yaml() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.yaml");
+    let plan = Plan {
+        preflight: vec![
+            Preflight::Binary {
+                name: "scp".to_string(),
+            },
+            Preflight::SshReachable {
+                host: "example.com".to_string(),
+                user: "deploy".to_string(),
+                ssh: SshOptions::default(),
+            },
+        ],
+        steps: sample_plan().steps,
+    };
+
+    save_plan(&plan, &path).unwrap();
+    let loaded = load_plan(&path).unwrap();
+
+    assert_eq!(loaded.preflight, plan.preflight);
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("check: ssh_reachable"));
+
+    save_plan(&sample_plan(), &path).unwrap();
+    assert!(!fs::read_to_string(&path).unwrap().contains("preflight"));
+}
+
+#[test]
+fn test_plan_file_link_accepts_old_symlink_op() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("plan.yaml");
+    fs::write(
+        &path,
+        "version: 1\nsteps:\n  - op: symlink\n    from: /store/mod.jar\n    to: /server/mods/mod.jar\n",
+    )
+    .unwrap();
+
+    let plan = load_plan(&path).unwrap();
+    assert_eq!(
+        plan.steps,
+        vec![Step::Link {
+            from: "/store/mod.jar".to_string(),
+            to: "/server/mods/mod.jar".to_string(),
+            mode: LinkMode::Symlink,
+        }]
+    );
+
+    save_plan(&plan, &path).unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("op: link"));
+    assert!(content.contains("mode: symlink"));
+}
@@ -10,6 +10,11 @@ pub fn generate_plan(args: BuildArgs) -> core_plan::Plan {
         cmd: "echo".to_string(),
         args: vec!["Building project...".to_string()],
         cwd: None,
+        env: core_plan::StepEnv::new(),
+        timeout_secs: None,
     };
-    core_plan::Plan { steps: vec![step] }
+    core_plan::Plan {
+        preflight: Vec::new(),
+        steps: vec![step],
+    }
 }
@@ -1,33 +1,50 @@
 pub struct DeployArgs {
-    pub server_name: Option<String>,
+    /// Servers to deploy to; empty means the default remote server.
+    /// Several servers are uploaded to concurrently.
+    pub server_names: Vec<String>,
     pub transport: Option<String>,
     pub restart: bool,
     pub dry_run: bool,
     pub path: Option<PathBuf>,
 }
 
-use core_config::{get_default_server_for_kind, load_server_config, ServerKind};
-use core_path::resolve_mod_artifact;
-use core_plan::{Plan, Step};
+use core_config::{
+    find_server_config, find_server_config_path, ConfigResolver, RemoteBlock, ServerConfig,
+    ServerKind,
+};
+use core_error::HymodError;
+use core_path::resolve_mod_artifact_with;
+use core_plan::{LinkMode, Plan, Preflight, RetryPolicy, SshOptions, Step, StepEnv};
 use std::path::Path;
 use std::path::PathBuf;
 
-pub fn generate_plan(args: DeployArgs) -> core_plan::Plan {
-    let server_name = args
-        .server_name
-        .or_else(|| {
-            get_default_server_for_kind(&ServerKind::Remote)
-                .ok()
-                .flatten()
-        })
-        .expect("No server specified and no default remote server configured");
-
-    let config = load_server_config(&server_name).expect("Failed to load server config");
+const DEFAULT_SSH_PORT: u16 = 22;
+const DEFAULT_RSYNC_OPTS: &str = "-avz";
 
+pub fn generate_plan(args: DeployArgs) -> Result<Plan, HymodError> {
     // Resolve mod directory
-    let mod_dir = args
-        .path
-        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"));
+    let mod_dir = match args.path {
+        Some(path) => path,
+        None => std::env::current_dir()
+            .map_err(|e| HymodError::Other(format!("Failed to get current directory: {}", e)))?,
+    };
+
+    let resolver = ConfigResolver::load(&mod_dir)?;
+    let server_names = if args.server_names.is_empty() {
+        vec![resolver.default_server(&ServerKind::Remote)?]
+    } else {
+        args.server_names
+    };
+
+    let configs = server_names
+        .iter()
+        .map(|name| find_server_config(name, &mod_dir))
+        .collect::<Result<Vec<ServerConfig>, HymodError>>()?;
+
+    let transports = configs
+        .iter()
+        .map(|config| upload_transport(config, args.transport.as_deref(), !args.dry_run))
+        .collect::<Result<Vec<&str>, HymodError>>()?;
 
     let mut steps = Vec::new();
 
@@ -40,93 +57,274 @@ pub fn generate_plan(args: DeployArgs) -> core_plan::Plan {
 
     steps.push(Step::RunProcess {
         cmd: gradlew.to_string(),
-        args: vec!["build".to_string()],
+        args: resolver.gradle_tasks(),
         cwd: Some(mod_dir.to_string_lossy().to_string()),
+        env: StepEnv::new(),
+        timeout_secs: None,
     });
 
     // 2. Identify Artifact
-    let artifact = resolve_mod_artifact(&mod_dir);
+    let artifact = resolve_mod_artifact_with(&mod_dir, resolver.artifact_path().as_deref());
     let jar_name = artifact.target_file_name;
     let source_path = artifact.source_path.to_string_lossy().to_string();
+    steps.push(Step::CheckZip {
+        path: source_path.clone(),
+        require: vec!["manifest.json".to_string()],
+        require_main_class: true,
+    });
+
+    // 3. Deploy (and 4. restart), one branch per server
+    let mut branches = configs
+        .iter()
+        .zip(&transports)
+        .map(|(config, transport)| {
+            server_steps(
+                config,
+                &mod_dir,
+                &source_path,
+                &jar_name,
+                transport,
+                args.restart,
+            )
+        })
+        .collect::<Result<Vec<Vec<Step>>, HymodError>>()?;
+
+    if branches.len() == 1 {
+        steps.append(&mut branches[0]);
+    } else {
+        steps.push(Step::Parallel { branches });
+    }
+
+    Ok(Plan {
+        preflight: preflight_checks(
+            &configs,
+            &transports,
+            &mod_dir.join(gradlew.trim_start_matches("./")),
+        ),
+        steps,
+    })
+}
+
+/// What the deploy needs before it starts the build: the build script,
+/// then what `server_checks` asks of each server.
+fn preflight_checks(
+    configs: &[ServerConfig],
+    transports: &[&str],
+    gradlew: &Path,
+) -> Vec<Preflight> {
+    let mut checks = vec![Preflight::Exists {
+        path: gradlew.to_string_lossy().to_string(),
+    }];
+    for (config, transport) in configs.iter().zip(transports) {
+        for check in server_checks(config, transport) {
+            if !checks.contains(&check) {
+                checks.push(check);
+            }
+        }
+    }
+    checks
+}
+
+/// What deploying to `config` needs: a writable mods directory for a local
+/// server; ssh, the upload `transport` and SSH access for a remote one.
+pub fn server_checks(config: &ServerConfig, transport: &str) -> Vec<Preflight> {
+    match (&config.server.kind, &config.server.remote) {
+        (ServerKind::Local, _) => vec![Preflight::Writable {
+            path: PathBuf::from(&config.server.server_root)
+                .join(&config.server.mods_dir)
+                .to_string_lossy()
+                .to_string(),
+        }],
+        (ServerKind::Remote, Some(remote)) => vec![
+            Preflight::Binary {
+                name: "ssh".to_string(),
+            },
+            Preflight::Binary {
+                name: transport.to_string(),
+            },
+            Preflight::SshReachable {
+                host: remote.host.clone(),
+                user: remote.user.clone(),
+                ssh: ssh_options(remote),
+            },
+        ],
+        (ServerKind::Remote, None) => Vec::new(),
+    }
+}
+
+/// The port, key and known_hosts file of `remote`, leaving ssh's defaults
+/// out of the plan.
+pub fn ssh_options(remote: &RemoteBlock) -> SshOptions {
+    SshOptions {
+        port: (remote.port != DEFAULT_SSH_PORT).then_some(remote.port),
+        identity_file: remote.identity_file.clone(),
+        known_hosts_file: remote.known_hosts_file.clone(),
+    }
+}
+
+fn server_steps(
+    config: &ServerConfig,
+    mod_dir: &Path,
+    source_path: &str,
+    jar_name: &str,
+    transport: &str,
+    restart: bool,
+) -> Result<Vec<Step>, HymodError> {
+    let mut steps = Vec::new();
+    let source_path = source_path.to_string();
 
-    // 3. Deploy
     match config.server.kind {
         ServerKind::Local => {
             let full_dest = PathBuf::from(&config.server.server_root)
                 .join(&config.server.mods_dir)
-                .join(&jar_name);
+                .join(jar_name);
 
-            steps.push(Step::CopyFile {
-                from: source_path,
-                to: full_dest.to_string_lossy().to_string(),
+            let to = full_dest.to_string_lossy().to_string();
+            steps.push(match config.server.link_mode {
+                Some(mode) => Step::Link {
+                    from: source_path,
+                    to,
+                    mode: link_mode(mode),
+                },
+                None => Step::CopyFile {
+                    from: source_path,
+                    to,
+                },
             });
         }
         ServerKind::Remote => {
-            if let Some(remote) = config.server.remote.as_ref() {
-                let remote_mods_dir = PathBuf::from(&config.server.server_root)
-                    .join(&config.server.mods_dir)
-                    .to_string_lossy()
-                    .to_string();
-
-                // Ensure remote destination directory exists before upload.
-                steps.push(Step::SshRun {
-                    host: remote.host.clone(),
-                    user: remote.user.clone(),
-                    cmd: format!("mkdir -p {}", remote_mods_dir),
+            let Some(remote) = config.server.remote.as_ref() else {
+                return Err(HymodError::InvalidConfig {
+                    path: find_server_config_path(&config.server.name, mod_dir)
+                        .ok()
+                        .flatten()
+                        .unwrap_or_default(),
+                    reason: "remote server has no `remote` block".to_string(),
                 });
+            };
+            let remote_mods_dir = PathBuf::from(&config.server.server_root)
+                .join(&config.server.mods_dir)
+                .to_string_lossy()
+                .to_string();
+
+            let retry = config.server.retry.as_ref().map(|block| RetryPolicy {
+                attempts: block.attempts,
+                backoff_ms: block.backoff_ms,
+                max_backoff_ms: block.max_backoff_ms,
+                retry_on: block.retry_on.clone(),
+            });
+
+            let ssh = ssh_options(remote);
+            let upload = config.server.upload.as_ref();
+
+            // Ensure remote destination directory exists before upload.
+            steps.push(Step::SshRun {
+                host: remote.host.clone(),
+                user: remote.user.clone(),
+                cmd: format!("mkdir -p {}", remote_mods_dir),
+                env: StepEnv::new(),
+                ssh: ssh.clone(),
+                retry: retry.clone(),
+                timeout_secs: None,
+            });
+
+            let remote_file_path = PathBuf::from(&config.server.server_root)
+                .join(&config.server.mods_dir)
+                .join(jar_name)
+                .to_string_lossy()
+                .to_string();
+            let remote_dest = format!("{}@{}:{}", remote.user, remote.host, remote_file_path);
 
-                let remote_file_path = PathBuf::from(&config.server.server_root)
-                    .join(&config.server.mods_dir)
-                    .join(&jar_name)
-                    .to_string_lossy()
-                    .to_string();
-                let remote_dest = format!("{}@{}:{}", remote.user, remote.host, remote_file_path);
-                let transport = resolve_transport(args.transport.as_deref());
-
-                if transport == "rsync" {
-                    steps.push(Step::UploadRsync {
-                        local: source_path,
-                        remote: remote_dest,
-                        opts: "-avz".to_string(),
-                    });
-                } else {
-                    // Default SCP
-                    steps.push(Step::UploadScp {
-                        local: source_path,
-                        remote: remote_dest,
-                    });
-                }
+            if transport == "rsync" {
+                steps.push(Step::UploadRsync {
+                    local: source_path,
+                    remote: remote_dest,
+                    opts: upload
+                        .and_then(|upload| upload.rsync.as_ref())
+                        .map_or(DEFAULT_RSYNC_OPTS.to_string(), |rsync| rsync.opts.clone()),
+                    ssh,
+                    retry,
+                    timeout_secs: None,
+                });
             } else {
-                panic!("Remote server config missing remote block");
+                // Default SCP
+                steps.push(Step::UploadScp {
+                    local: source_path,
+                    remote: remote_dest,
+                    opts: upload
+                        .and_then(|upload| upload.scp.as_ref())
+                        .map_or(String::new(), |scp| scp.opts.clone()),
+                    ssh,
+                    retry,
+                    timeout_secs: None,
+                });
             }
         }
     }
 
-    // 4. Restart (opt-in)
-    if args.restart {
+    // Restart (opt-in)
+    if restart {
         steps.push(Step::RunProcess {
             cmd: config.server.restart.cmd.clone(),
             args: vec![],
             cwd: None,
+            env: StepEnv::new(),
+            timeout_secs: None,
         });
     }
 
-    Plan { steps }
+    Ok(steps)
+}
+
+/// The upload transport for `config`: `explicit` (`--transport`) if given,
+/// else the server's `upload.method`, else rsync when it is installed and scp
+/// otherwise. With `check_installed`, a transport asked for by name must be
+/// on `PATH` when the server is remote.
+pub fn upload_transport(
+    config: &ServerConfig,
+    explicit: Option<&str>,
+    check_installed: bool,
+) -> Result<&'static str, HymodError> {
+    let configured = config
+        .server
+        .upload
+        .as_ref()
+        .map(|upload| upload.method.as_str());
+    resolve_transport(
+        explicit.or(configured),
+        check_installed && config.server.kind == ServerKind::Remote,
+    )
 }
 
-fn resolve_transport(explicit: Option<&str>) -> &'static str {
-    match explicit {
+/// Pick the upload transport, preferring rsync when available. With
+/// `check_installed`, an explicitly requested transport must be on `PATH`.
+fn resolve_transport(
+    explicit: Option<&str>,
+    check_installed: bool,
+) -> Result<&'static str, HymodError> {
+    let transport = match explicit {
         Some("rsync") => "rsync",
         Some("scp") => "scp",
-        Some(other) => panic!("Invalid transport: must be rsync or scp (got '{other}')"),
+        Some(other) => {
+            return Err(HymodError::InvalidArgument(format!(
+                "Invalid transport: must be rsync or scp (got '{other}')"
+            )))
+        }
         None => {
-            if is_command_available("rsync") {
+            return Ok(if is_command_available("rsync") {
                 "rsync"
             } else {
                 "scp"
-            }
+            })
         }
+    };
+
+    if check_installed && !is_command_available(transport) {
+        return Err(HymodError::TransportUnavailable {
+            transport: transport.to_string(),
+        });
     }
+    Ok(transport)
 }
 
 fn is_command_available(cmd: &str) -> bool {
@@ -149,3 +347,11 @@ fn command_exists_in_dir(dir: &Path, cmd: &str) -> bool {
         dir.join(cmd).is_file()
     }
 }
+
+fn link_mode(mode: core_config::LinkMode) -> LinkMode {
+    match mode {
+        core_config::LinkMode::Symlink => LinkMode::Symlink,
+        core_config::LinkMode::Hardlink => LinkMode::Hardlink,
+        core_config::LinkMode::Copy => LinkMode::Copy,
+    }
+}
@@ -1,19 +1,32 @@
 use crate::args::DevArgs;
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result};
 use colored::*;
-use core_path::resolve_mod_artifact;
+use core_error::HymodError;
+use core_ops::{Executor, Verbosity};
+use core_path::resolve_mod_artifact_with;
+use core_plan::{Plan, Step, StepEnv};
 use std::env;
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+
+pub fn run(args: DevArgs, executor: &Executor) -> Result<()> {
+    let quiet = executor.verbosity() == Verbosity::Quiet;
+    let say = |line: String| {
+        if !quiet {
+            println!("{}", line);
+        }
+    };
 
-pub fn run(args: DevArgs) -> Result<()> {
     // 1. Resolve mod directory
     let mod_dir = match args.path {
         Some(p) => p,
         None => env::current_dir().context("Failed to get current directory")?,
     };
 
-    println!("{} {}", "Dev Loop:".blue().bold(), mod_dir.display());
+    say(format!(
+        "{} {}",
+        "Dev Loop:".blue().bold(),
+        mod_dir.display()
+    ));
 
     // 2. Run ./gradlew
     #[cfg(windows)]
@@ -22,134 +35,159 @@ pub fn run(args: DevArgs) -> Result<()> {
     let gradle_wrapper = mod_dir.join("gradlew");
 
     if !gradle_wrapper.exists() {
-        bail!(
-            "No gradle wrapper found at {}. Is this a mod directory?",
-            gradle_wrapper.display()
-        );
+        return Err(HymodError::NotAModDirectory {
+            path: gradle_wrapper,
+        }
+        .into());
     }
     // canonicalize causes path issues on Windows (\\?\) so we use the path as-is.
     // Ensure executable on Unix
     #[cfg(unix)]
-    {
+    if !executor.is_dry_run() {
         use std::os::unix::fs::PermissionsExt;
         if let Ok(metadata) = std::fs::metadata(&gradle_wrapper) {
             let mut perms = metadata.permissions();
             if perms.mode() & 0o111 == 0 {
-                println!(
+                say(format!(
                     "{} Gradle wrapper not executable. Attempting to fix...",
                     "!!".yellow()
-                );
+                ));
                 perms.set_mode(perms.mode() | 0o755);
                 let _ = std::fs::set_permissions(&gradle_wrapper, perms);
             }
         }
     }
 
-    println!("{} Running Gradle build...", ">>".green());
-
-    let config = core_config::UserConfig::load();
-
-    let mut cmd = Command::new(&gradle_wrapper);
-    cmd.current_dir(&mod_dir).arg("build");
+    let config = core_config::ConfigResolver::load(&mod_dir)?;
 
-    if let Some(username) = config.username {
-        cmd.arg(format!("-Pusername={}", username));
+    let mut build_args = config.gradle_tasks();
+    if let Some(username) = config.get("username") {
+        build_args.push(format!("-Pusername={}", username));
     }
 
-    let status = cmd
-        .status()
-        .context("Failed to execute gradle wrapper. Check permissions.")?;
+    let build_step = Step::RunProcess {
+        cmd: gradle_wrapper.to_string_lossy().to_string(),
+        args: build_args,
+        cwd: Some(mod_dir.to_string_lossy().to_string()),
+        env: StepEnv::new(),
+        timeout_secs: None,
+    };
 
-    if !status.success() {
-        bail!("Gradle build failed");
+    if executor.is_dry_run() {
+        // The artifact does not exist yet, so resolve it the same way deploy does: up front.
+        let artifact = resolve_mod_artifact_with(&mod_dir, config.artifact_path().as_deref());
+        let destination_dir = resolve_destination(args.target, &config, &mod_dir)?;
+        let dest_file = destination_dir.join(&artifact.target_file_name);
+
+        let mut steps = vec![build_step];
+        steps.extend(deploy_steps(
+            &artifact.source_path,
+            &destination_dir,
+            &dest_file,
+        ));
+        return executor.execute(&Plan {
+            preflight: Vec::new(),
+            steps,
+        });
     }
 
+    say(format!("{} Running Gradle build...", ">>".green()));
+
+    executor
+        .execute(&Plan {
+            preflight: Vec::new(),
+            steps: vec![build_step],
+        })
+        .map_err(|e| match e.downcast::<HymodError>() {
+            // Keep interruptions and timeouts distinct from a failing build.
+            Ok(err @ (HymodError::Interrupted { .. } | HymodError::StepTimedOut { .. })) => err,
+            Ok(err) => HymodError::BuildFailed {
+                reason: err.to_string(),
+            },
+            Err(e) => HymodError::BuildFailed {
+                reason: format!("{:#}", e),
+            },
+        })?;
+
     // 3. Identify artifact
-    let artifact = resolve_mod_artifact(&mod_dir);
+    let artifact = resolve_mod_artifact_with(&mod_dir, config.artifact_path().as_deref());
     if !artifact.source_path.exists() {
-        bail!(
-            "Could not find a suitable .jar artifact in build/libs/ (expected {}).",
-            artifact.source_path.display()
-        );
+        return Err(HymodError::ArtifactMissing {
+            path: artifact.source_path,
+        }
+        .into());
     }
 
     // 4. Resolve Target and deploy
-    let destination_dir = if let Some(target_str) = args.target {
-        // Check if target_str is a valid directory path
-        let target_path = PathBuf::from(&target_str);
-        if target_path.exists() && target_path.is_dir() {
-            // Check for "mods" subdirectory
-            let mods_sub = target_path.join("mods");
-            if mods_sub.exists() && mods_sub.is_dir() {
-                mods_sub
-            } else {
-                target_path
-            }
-        } else {
-            // Assume it's a server name
-            let server_cfg = core_config::load_server_config(&target_str).map_err(|e| {
-                anyhow::anyhow!(
-                    "Target '{}' is not a directory and not a known server. Error: {}",
-                    target_str,
-                    e
-                )
-            })?;
-
-            if let core_config::ServerKind::Local = server_cfg.server.kind {
-                PathBuf::from(server_cfg.server.server_root).join(server_cfg.server.mods_dir)
-            } else {
-                bail!("Dev command currently only supports local servers or paths.");
-            }
-        }
-    } else {
-        // Use default server
-        let default_server_name =
-            core_config::get_default_server_for_kind(&core_config::ServerKind::Local)
-                .map_err(|e| anyhow::anyhow!("Failed to get default local server: {}", e))?
-                .context(
-                    "No target provided and no default local server configured. Use 'hymod server default local <NAME>' or provide a target argument.",
-                )?;
-
-        let server_cfg = core_config::load_server_config(&default_server_name)
-            .map_err(|e| anyhow::anyhow!("Failed to load server config: {}", e))?;
-        if let core_config::ServerKind::Local = server_cfg.server.kind {
-            PathBuf::from(server_cfg.server.server_root).join(server_cfg.server.mods_dir)
-        } else {
-            bail!(
-                "Default server '{}' is remote. Dev command only supports local servers.",
-                default_server_name
-            );
-        }
-    };
-
-    if !destination_dir.exists() {
-        std::fs::create_dir_all(&destination_dir).with_context(|| {
-            format!(
-                "Failed to create destination directory: {}",
-                destination_dir.display()
-            )
-        })?;
-    }
+    let destination_dir = resolve_destination(args.target, &config, &mod_dir)?;
 
     // 5. Copy artifact
     let target_name = artifact.target_file_name;
 
     let dest_file = destination_dir.join(&target_name);
 
-    println!(
+    say(format!(
         "{} Deploying {} to: {}",
         ">>".green(),
         target_name,
         destination_dir.display()
-    );
-    std::fs::copy(&artifact.source_path, &dest_file)
-        .with_context(|| format!("Failed to copy artifact to {}", destination_dir.display()))?;
+    ));
+    executor.execute(&Plan {
+        preflight: Vec::new(),
+        steps: deploy_steps(&artifact.source_path, &destination_dir, &dest_file),
+    })?;
 
-    println!(
+    say(format!(
         "{} Deployed successfully: {}",
         "SUCCESS".green().bold(),
         dest_file.display().to_string().cyan()
-    );
+    ));
 
     Ok(())
 }
+
+/// Check the built jar, then copy it into `destination_dir`.
+fn deploy_steps(source: &Path, destination_dir: &Path, dest_file: &Path) -> Vec<Step> {
+    vec![
+        Step::CheckZip {
+            path: source.to_string_lossy().to_string(),
+            require: vec!["manifest.json".to_string()],
+            require_main_class: true,
+        },
+        Step::Mkdir {
+            path: destination_dir.to_string_lossy().to_string(),
+        },
+        Step::CopyFile {
+            from: source.to_string_lossy().to_string(),
+            to: dest_file.to_string_lossy().to_string(),
+        },
+    ]
+}
+
+fn resolve_destination(
+    target: Option<String>,
+    config: &core_config::ConfigResolver,
+    mod_dir: &Path,
+) -> Result<PathBuf, HymodError> {
+    let local = core_config::ServerKind::Local;
+    let server_name = if let Some(target_str) = target {
+        // Check if target_str is a valid directory path
+        let target_path = PathBuf::from(&target_str);
+        if target_path.exists() && target_path.is_dir() {
+            // Check for "mods" subdirectory
+            let mods_sub = target_path.join("mods");
+            if mods_sub.exists() && mods_sub.is_dir() {
+                return Ok(mods_sub);
+            }
+            return Ok(target_path);
+        }
+        // Assume it's a server name
+        target_str
+    } else {
+        // Use default server
+        config.default_server(&local)?
+    };
+
+    let server_cfg = core_config::find_server_config_of_kind(&server_name, &local, mod_dir)?;
+    Ok(PathBuf::from(server_cfg.server.server_root).join(server_cfg.server.mods_dir))
+}
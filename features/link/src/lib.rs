@@ -2,7 +2,55 @@ pub struct LinkArgs {
     pub server_name: Option<String>,
 }
 
-pub fn generate_plan(args: LinkArgs) -> core_plan::Plan {
-    let _ = args;
-    todo!()
+use core_config::{find_server_config_of_kind, ConfigResolver, ServerKind};
+use core_error::HymodError;
+use core_path::resolve_mod_artifact_with;
+use core_plan::{LinkMode, Plan, Step};
+use std::path::PathBuf;
+
+/// Link the mod in the current directory into a local server's mods
+/// directory, using the server's `link_mode` (a symlink by default).
+pub fn generate_plan(args: LinkArgs) -> Result<Plan, HymodError> {
+    let mod_dir = std::env::current_dir()
+        .map_err(|e| HymodError::Other(format!("Failed to get current directory: {}", e)))?;
+
+    let local = ServerKind::Local;
+    let resolver = ConfigResolver::load(&mod_dir)?;
+    let artifact_path = resolver.artifact_path();
+    let server_name = resolver
+        .with_cli("server", "<server_name>", args.server_name.as_deref())
+        .default_server(&local)?;
+    let config = find_server_config_of_kind(&server_name, &local, &mod_dir)?;
+    let artifact = resolve_mod_artifact_with(&mod_dir, artifact_path.as_deref());
+    if !artifact.source_path.is_file() {
+        return Err(HymodError::ArtifactMissing {
+            path: artifact.source_path,
+        });
+    }
+
+    let link = PathBuf::from(&config.server.server_root)
+        .join(&config.server.mods_dir)
+        .join(&artifact.target_file_name);
+    let mode = match config.server.link_mode {
+        Some(core_config::LinkMode::Hardlink) => LinkMode::Hardlink,
+        Some(core_config::LinkMode::Copy) => LinkMode::Copy,
+        Some(core_config::LinkMode::Symlink) | None => LinkMode::Symlink,
+    };
+
+    let source_path = artifact.source_path.to_string_lossy().to_string();
+    Ok(Plan {
+        preflight: Vec::new(),
+        steps: vec![
+            Step::CheckZip {
+                path: source_path.clone(),
+                require: vec!["manifest.json".to_string()],
+                require_main_class: true,
+            },
+            Step::Link {
+                from: source_path,
+                to: link.to_string_lossy().to_string(),
+                mode,
+            },
+        ],
+    })
 }
@@ -1,7 +1,7 @@
 pub mod skeleton;
 pub mod templates;
 
-use core_config::UserConfig;
+use core_config::{ConfigLayer, ConfigResolver};
 use dialoguer::{theme::ColorfulTheme, Input};
 use std::collections::HashMap;
 
@@ -18,50 +18,35 @@ pub struct NewArgs {
 }
 
 pub fn generate_plan(args: NewArgs, skeleton_bytes: &[u8]) -> core_plan::Plan {
-    let config = UserConfig::load();
+    // The new project has no hymod.yaml yet, and one found above the
+    // current directory belongs to another project, so only the global
+    // config and the flags apply.
+    let config = ConfigResolver::global()
+        .with_cli("author", "--author", args.author.as_deref())
+        .with_cli("group", "--group", args.group.as_deref())
+        .with_cli("version", "--version", args.version.as_deref())
+        .with_cli("description", "--desc", args.desc.as_deref());
 
     let name = args.name.clone();
 
     // Resolve Author
-    let author = resolve_val(
-        "Author",
-        args.interactive,
-        args.author.as_deref(),
-        config.author.as_deref(),
-        Some("Your Name"),
-    );
+    let author = resolve_val("Author", args.interactive, &config, "author");
 
     // Resolve Group
-    let group = resolve_val(
-        "Group ID",
-        args.interactive,
-        args.group.as_deref(),
-        config.group.as_deref(),
-        Some("com.example"),
-    );
+    let group = resolve_val("Group ID", args.interactive, &config, "group");
 
     // Resolve Version
-    let version = resolve_val(
-        "Version",
-        args.interactive,
-        args.version.as_deref(),
-        config.version.as_deref(),
-        Some("0.1.0"),
-    );
+    let version = resolve_val("Version", args.interactive, &config, "version");
 
     // Resolve Description
-    let desc = resolve_val(
-        "Description",
-        args.interactive,
-        args.desc.as_deref(),
-        config.desc.as_deref(),
-        Some("A Hytale mod"),
-    );
+    let desc = resolve_val("Description", args.interactive, &config, "description");
 
     // Derived: Username (from config only, not args for now)
     // If not set, we can leave it as <USERNAME> or default to system user?
     // Requirement says "properly used", implying replacement.
-    let username = config.username.as_deref().unwrap_or("HytaleUser");
+    let username = config
+        .get("username")
+        .unwrap_or_else(|| "HytaleUser".to_string());
 
     // Prepare replacements
     let mut replacements = HashMap::new();
@@ -71,7 +56,7 @@ pub fn generate_plan(args: NewArgs, skeleton_bytes: &[u8]) -> core_plan::Plan {
     replacements.insert("<GROUP_ID>".to_string(), group.clone());
     replacements.insert("<VERSION>".to_string(), version);
     replacements.insert("<DESCRIPTION>".to_string(), desc);
-    replacements.insert("<USERNAME>".to_string(), username.to_string());
+    replacements.insert("<USERNAME>".to_string(), username);
 
     // Derived: Package
     // If not provided, derive from group + name (slugified)
@@ -94,32 +79,34 @@ pub fn generate_plan(args: NewArgs, skeleton_bytes: &[u8]) -> core_plan::Plan {
     // "com.example.skeleton" is more specific than "com.example" so it should be fine if we don't have "com.example" rule.
 
     let steps = skeleton::generate_plan(&name, skeleton_bytes, &replacements);
-    core_plan::Plan { steps }
+    core_plan::Plan {
+        preflight: Vec::new(),
+        steps,
+    }
 }
 
-fn resolve_val(
-    prompt: &str,
-    interactive: bool,
-    flag: Option<&str>,
-    config: Option<&str>,
-    default: Option<&str>,
-) -> String {
+fn resolve_val(prompt: &str, interactive: bool, config: &ConfigResolver, key: &str) -> String {
+    let resolved = config.resolve(key);
+
     // 1. Flag
-    if let Some(v) = flag {
-        return v.to_string();
+    if let Some(value) = resolved
+        .as_ref()
+        .filter(|value| matches!(value.layer, ConfigLayer::Cli { .. }))
+    {
+        return value.value.clone();
     }
 
-    let default_val = config.or(default).unwrap_or("");
+    let default_val = resolved.map(|value| value.value).unwrap_or_default();
 
     // 2. Interactive
     if interactive {
         return Input::with_theme(&ColorfulTheme::default())
             .with_prompt(prompt)
-            .default(default_val.to_string())
+            .default(default_val.clone())
             .interact_text()
-            .unwrap_or_else(|_| default_val.to_string());
+            .unwrap_or(default_val);
     }
 
-    // 3. Config / Default
-    default_val.to_string()
+    // 3. Environment / config / default
+    default_val
 }
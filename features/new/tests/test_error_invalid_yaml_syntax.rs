@@ -1,4 +1,19 @@
+use core_config::ProjectConfig;
+use std::fs;
+
 #[test]
 fn test_error_invalid_yaml_syntax() {
-    panic!("Error must include: file path, line number, YAML parse error details.");
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hymod.yaml");
+    fs::write(
+        &path,
+        "mod:\n  id: my-mod\n  name: [unclosed\n  version: 1.0.0\n",
+    )
+    .unwrap();
+
+    let message = ProjectConfig::load(&path).unwrap_err().to_string();
+
+    assert!(message.contains(&path.display().to_string()), "{}", message);
+    assert!(message.contains("line "), "{}", message);
+    assert!(message.contains("column "), "{}", message);
 }
@@ -1,6 +1,16 @@
+#[derive(Debug, Clone, Default)]
 pub struct ServerAddArgs {
     pub name: String,
     pub kind: String,
     pub uri: String,
+    /// Overrides the path given in (or defaulted for) `uri`.
     pub server_root: Option<String>,
+    pub mods_dir: Option<String>,
+    pub restart_cmd: Option<String>,
+    pub port: Option<u16>,
+    pub identity_file: Option<String>,
+    pub known_hosts_file: Option<String>,
+    pub upload_method: Option<String>,
+    pub rsync_opts: Option<String>,
+    pub scp_opts: Option<String>,
 }
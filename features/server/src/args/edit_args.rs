@@ -0,0 +1,4 @@
+#[derive(Debug, Clone)]
+pub struct ServerEditArgs {
+    pub name: String,
+}
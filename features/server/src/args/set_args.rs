@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct ServerSetArgs {
+    pub name: String,
+    /// Dotted field of the server block, e.g. `restart.cmd` or `remote.port`.
+    pub key: String,
+    pub value: String,
+}
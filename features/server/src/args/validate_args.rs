@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct ServerValidateArgs {
+    /// Server to check; `None` checks every server file.
+    pub name: Option<String>,
+}
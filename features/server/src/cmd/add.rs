@@ -1,40 +1,94 @@
 use crate::args::add_args::ServerAddArgs;
+use crate::{apply, current_dir, parse_kind, write_step};
 use core_config::server::{
-    load_server_config, save_server_config, RemoteBlock, RestartBlock, ServerBlock, ServerConfig,
-    ServerKind,
+    load_server_config, server_config_to_string, user_server_config_path, RemoteBlock,
+    RestartBlock, RsyncBlock, ScpBlock, ServerBlock, ServerConfig, ServerKind, UploadBlock,
 };
+use core_config::validate::parse_server_config;
+use core_error::HymodError;
+use core_ops::Executor;
+use core_plan::Plan;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use std::path::PathBuf;
 
-pub fn run(args: ServerAddArgs) {
-    if load_server_config(&args.name).is_ok() {
-        eprintln!("Server '{}' already exists.", args.name);
-        std::process::exit(1);
-    }
+const DEFAULT_MODS_DIR: &str = "mods";
+const DEFAULT_RESTART_CMD: &str = "systemctl restart hytale";
+
+pub fn run(args: ServerAddArgs, executor: &Executor) -> Result<(), HymodError> {
+    let config = build_config(&args)?;
+    save(&config, executor)
+}
 
-    let kind = match args.kind.as_str() {
-        "local" => ServerKind::Local,
-        "remote" | "ssh" => ServerKind::Remote,
-        _ => {
-            eprintln!(
-                "Invalid server kind: {}. Must be 'local' or 'remote'.",
-                args.kind
-            );
-            std::process::exit(1);
+/// Ask for every setting of the new server, offering `args` as defaults,
+/// then offer to check that the server can be reached before saving it.
+/// A dry run would only list the checks, so there the check is skipped.
+pub fn run_interactive(args: ServerAddArgs, executor: &Executor) -> Result<(), HymodError> {
+    let args = wizard(args);
+    let config = build_config(&args)?;
+
+    if executor.is_dry_run() {
+        println!("Skipped the connectivity check (dry run).");
+        return save(&config, executor);
+    }
+    if confirm("Check that the server can be reached now?", true) {
+        if let Err(e) = executor.execute(&connectivity_check(&config)?) {
+            eprintln!("{}", e);
+            if !confirm("Save the server anyway?", false) {
+                println!("Server '{}' was not saved.", config.server.name);
+                return Ok(());
+            }
         }
-    };
+    }
+    save(&config, executor)
+}
 
-    let (server_root, remote_block) = match kind {
-        ServerKind::Local => (args.uri.clone(), None),
+/// The server `args` describe, checked like a server file read from disk.
+pub fn build_config(args: &ServerAddArgs) -> Result<ServerConfig, HymodError> {
+    if load_server_config(&args.name, &current_dir()?).is_ok() {
+        return Err(HymodError::ServerExists {
+            name: args.name.clone(),
+        });
+    }
+
+    let kind = parse_kind(&args.kind)?;
+
+    let (server_root, remote_block, upload) = match kind {
+        ServerKind::Local => {
+            let remote_only = [
+                ("--port", args.port.is_some()),
+                ("--identity-file", args.identity_file.is_some()),
+                ("--known-hosts", args.known_hosts_file.is_some()),
+                ("--upload-method", args.upload_method.is_some()),
+                ("--rsync-opts", args.rsync_opts.is_some()),
+                ("--scp-opts", args.scp_opts.is_some()),
+            ];
+            if let Some((flag, _)) = remote_only.iter().find(|(_, given)| *given) {
+                return Err(HymodError::InvalidArgument(format!(
+                    "{} only applies to remote servers",
+                    flag
+                )));
+            }
+            (args.uri.clone(), None, None)
+        }
         ServerKind::Remote => {
+            let host = args.uri.rsplit('@').next().unwrap_or_default();
+            if host.is_empty() || host.starts_with(':') {
+                return Err(HymodError::InvalidArgument(format!(
+                    "Invalid remote URI '{}': host is required",
+                    args.uri
+                )));
+            }
             let (user, host, parsed_server_root) = parse_remote_uri(&args.uri);
             (
                 parsed_server_root,
                 Some(RemoteBlock {
                     host,
                     user,
-                    port: 22,
-                    identity_file: None,
-                    known_hosts_file: None,
+                    port: args.port.unwrap_or(22),
+                    identity_file: args.identity_file.clone(),
+                    known_hosts_file: args.known_hosts_file.clone(),
                 }),
+                upload_block(args),
             )
         }
     };
@@ -43,22 +97,166 @@ pub fn run(args: ServerAddArgs) {
         server: ServerBlock {
             name: args.name.clone(),
             kind,
-            server_root,
-            mods_dir: "mods".to_string(),
+            server_root: args.server_root.clone().unwrap_or(server_root),
+            mods_dir: args
+                .mods_dir
+                .clone()
+                .unwrap_or_else(|| DEFAULT_MODS_DIR.to_string()),
             restart: RestartBlock {
-                cmd: "systemctl restart hytale".to_string(),
+                cmd: args
+                    .restart_cmd
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_RESTART_CMD.to_string()),
             },
             remote: remote_block,
-            upload: None,
+            upload,
+            retry: None,
+            link_mode: None,
         },
     };
 
-    if let Err(e) = save_server_config(&config) {
-        eprintln!("Failed to save server config: {}", e);
-        std::process::exit(1);
+    let content = server_config_to_string(&config).map_err(HymodError::Other)?;
+    let path = PathBuf::from(format!("{}.yaml", args.name));
+    parse_server_config(&content, &path)
+        .map_err(|problems| HymodError::InvalidArgument(problems.join("; ")))?;
+    Ok(config)
+}
+
+/// Preflight checks for `config`, the same ones `hymod deploy` runs for it:
+/// a writable mods directory for a local server; ssh, the upload tool and SSH
+/// access on the configured port and key for a remote one.
+pub fn connectivity_check(config: &ServerConfig) -> Result<Plan, HymodError> {
+    let transport = features_deploy::upload_transport(config, None, false)?;
+    Ok(Plan {
+        preflight: features_deploy::server_checks(config, transport),
+        steps: Vec::new(),
+    })
+}
+
+fn save(config: &ServerConfig, executor: &Executor) -> Result<(), HymodError> {
+    let path = user_server_config_path(&config.server.name).map_err(HymodError::Other)?;
+    let content = server_config_to_string(config).map_err(HymodError::Other)?;
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![write_step(&path, content)],
+    };
+    apply(
+        executor,
+        &plan,
+        &format!("Server '{}' added successfully.", config.server.name),
+    )
+}
+
+/// The upload block for the upload flags, if any was given. Transport
+/// options without a method pick the method they belong to.
+fn upload_block(args: &ServerAddArgs) -> Option<UploadBlock> {
+    let method = match (&args.upload_method, &args.rsync_opts, &args.scp_opts) {
+        (Some(method), _, _) => method.clone(),
+        (None, Some(_), _) => "rsync".to_string(),
+        (None, None, Some(_)) => "scp".to_string(),
+        (None, None, None) => return None,
+    };
+    Some(UploadBlock {
+        method,
+        rsync: args.rsync_opts.clone().map(|opts| RsyncBlock { opts }),
+        scp: args.scp_opts.clone().map(|opts| ScpBlock { opts }),
+    })
+}
+
+/// `args` completed from prompts. Without a terminal every prompt takes its
+/// default, as `hymod new -i` does.
+fn wizard(mut args: ServerAddArgs) -> ServerAddArgs {
+    let theme = ColorfulTheme::default();
+    let kinds = ["local", "remote"];
+    let current = kinds
+        .iter()
+        .position(|kind| parse_kind(&args.kind).is_ok_and(|parsed| parsed.as_str() == *kind))
+        .unwrap_or(0);
+    let kind = Select::with_theme(&theme)
+        .with_prompt("Kind")
+        .items(&kinds)
+        .default(current)
+        .interact()
+        .unwrap_or(current);
+    args.kind = kinds[kind].to_string();
+    args.name = input("Name", args.name);
+
+    if args.kind == "local" {
+        let root = args.server_root.take().unwrap_or(args.uri);
+        args.uri = input("Server root", root);
+    } else {
+        let (user, host, root) = match args.uri.as_str() {
+            "" => ("root".to_string(), String::new(), "/opt/hytale".to_string()),
+            uri => parse_remote_uri(uri),
+        };
+        let host = input("Host", host);
+        let user = input("User", user);
+        let port = args.port.unwrap_or(22);
+        args.port = Some(
+            Input::with_theme(&theme)
+                .with_prompt("Port")
+                .default(port)
+                .interact_text()
+                .unwrap_or(port),
+        );
+        args.uri = format!("{}@{}", user, host);
+        args.server_root = Some(input("Server root", args.server_root.unwrap_or(root)));
+        args.identity_file = optional_input("Identity file", args.identity_file);
+        args.known_hosts_file = optional_input("known_hosts file", args.known_hosts_file);
+
+        let methods = ["rsync", "scp"];
+        let current = methods
+            .iter()
+            .position(|method| args.upload_method.as_deref() == Some(*method))
+            .unwrap_or(0);
+        let method = Select::with_theme(&theme)
+            .with_prompt("Upload method")
+            .items(&methods)
+            .default(current)
+            .interact()
+            .unwrap_or(current);
+        args.upload_method = Some(methods[method].to_string());
     }
 
-    println!("Server '{}' added successfully.", args.name);
+    args.mods_dir = Some(input(
+        "Mods directory",
+        args.mods_dir
+            .unwrap_or_else(|| DEFAULT_MODS_DIR.to_string()),
+    ));
+    args.restart_cmd = Some(input(
+        "Restart command",
+        args.restart_cmd
+            .unwrap_or_else(|| DEFAULT_RESTART_CMD.to_string()),
+    ));
+    args
+}
+
+fn input(prompt: &str, default: String) -> String {
+    Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(default.clone())
+        .interact_text()
+        .unwrap_or(default)
+}
+
+/// Like `input`, but an empty answer leaves the setting unset.
+fn optional_input(prompt: &str, default: Option<String>) -> Option<String> {
+    let answer: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(default.clone().unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()
+        .unwrap_or(default.unwrap_or_default());
+    (!answer.trim().is_empty()).then_some(answer)
+}
+
+/// Ask a yes/no question; without a terminal the answer is no.
+fn confirm(prompt: &str, default: bool) -> bool {
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(default)
+        .interact()
+        .unwrap_or(false)
 }
 
 fn parse_remote_uri(uri: &str) -> (String, String, String) {
@@ -83,7 +281,34 @@ fn parse_remote_uri(uri: &str) -> (String, String, String) {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_remote_uri;
+    use super::{connectivity_check, parse_remote_uri};
+    use core_config::validate::parse_server_config;
+    use core_plan::{Preflight, SshOptions};
+    use std::path::Path;
+
+    #[test]
+    fn checks_the_configured_endpoint_and_upload_method() {
+        let config = parse_server_config(
+            "server:\n  name: prod\n  kind: remote\n  server_root: /srv/hytale\n  mods_dir: mods\n  restart:\n    cmd: 'true'\n  remote:\n    host: example.com\n    user: deploy\n    port: 2222\n    identity_file: /keys/hymod\n  upload:\n    method: scp\n",
+            Path::new("prod.yaml"),
+        )
+        .unwrap();
+
+        let plan = connectivity_check(&config).unwrap();
+
+        assert!(plan.preflight.contains(&Preflight::Binary {
+            name: "scp".to_string()
+        }));
+        assert!(plan.preflight.contains(&Preflight::SshReachable {
+            host: "example.com".to_string(),
+            user: "deploy".to_string(),
+            ssh: SshOptions {
+                port: Some(2222),
+                identity_file: Some("/keys/hymod".to_string()),
+                known_hosts_file: None,
+            },
+        }));
+    }
 
     #[test]
     fn parses_user_host_and_path() {
@@ -1,42 +1,23 @@
 use crate::args::default_args::ServerDefaultArgs;
-use core_config::server::{load_server_config, ServerKind};
+use crate::{apply, current_dir, parse_kind, write_step};
+use core_config::server::{default_server_path, find_server_config_of_kind};
+use core_error::HymodError;
+use core_ops::Executor;
+use core_plan::Plan;
 
-pub fn run(args: ServerDefaultArgs) {
-    let expected_kind = match args.kind.as_str() {
-        "local" => ServerKind::Local,
-        "remote" | "ssh" => ServerKind::Remote,
-        _ => {
-            eprintln!(
-                "Invalid server kind: {}. Must be 'local' or 'remote'.",
-                args.kind
-            );
-            std::process::exit(1);
-        }
-    };
+pub fn run(args: ServerDefaultArgs, executor: &Executor) -> Result<(), HymodError> {
+    let expected_kind = parse_kind(&args.kind)?;
+    find_server_config_of_kind(&args.name, &expected_kind, &current_dir()?)?;
 
-    let config = match load_server_config(&args.name) {
-        Ok(c) => c,
-        Err(_) => {
-            eprintln!(
-                "Server '{}' does not exist. Please add it first.",
-                args.name
-            );
-            std::process::exit(1);
-        }
+    let path = default_server_path(&expected_kind)
+        .map_err(|e| HymodError::Other(format!("Failed to set default server: {}", e)))?;
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![write_step(&path, args.name.clone())],
     };
-
-    if config.server.kind != expected_kind {
-        eprintln!(
-            "Server '{}' is {:?}, not {:?}.",
-            args.name, config.server.kind, expected_kind
-        );
-        std::process::exit(1);
-    }
-
-    if let Err(e) = core_config::server::set_default_server_for_kind(&expected_kind, &args.name) {
-        eprintln!("Failed to set default server: {}", e);
-        std::process::exit(1);
-    }
-
-    println!("Default {} server set to '{}'", args.kind, args.name);
+    apply(
+        executor,
+        &plan,
+        &format!("Default {} server set to '{}'", args.kind, args.name),
+    )
 }
@@ -0,0 +1,76 @@
+use crate::args::edit_args::ServerEditArgs;
+use crate::{apply, current_dir, write_step};
+use core_config::server::server_config_path;
+use core_config::validate::parse_server_config;
+use core_error::HymodError;
+use core_ops::Executor;
+use core_plan::Plan;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+pub fn run(args: ServerEditArgs, executor: &Executor) -> Result<(), HymodError> {
+    let path = server_config_path(&args.name, &current_dir()?)?;
+    let original = fs::read_to_string(&path).map_err(|e| HymodError::InvalidConfig {
+        path: path.clone(),
+        reason: e.to_string(),
+    })?;
+
+    // The file is edited as a draft next to it and only replaced once the
+    // draft is valid. The extension keeps the draft out of the registry.
+    let draft = path.with_file_name(format!(".{}.yaml.edit", args.name));
+    fs::write(&draft, &original)
+        .map_err(|e| HymodError::Other(format!("Failed to write {}: {}", draft.display(), e)))?;
+    open_editor(&draft)?;
+    let edited = fs::read_to_string(&draft)
+        .map_err(|e| HymodError::Other(format!("Failed to read {}: {}", draft.display(), e)))?;
+
+    if edited == original {
+        fs::remove_file(&draft).ok();
+        println!("No changes to server '{}'.", args.name);
+        return Ok(());
+    }
+    if let Err(problems) = parse_server_config(&edited, &path) {
+        for problem in &problems {
+            println!("  - {}", problem);
+        }
+        return Err(HymodError::InvalidConfig {
+            path: draft,
+            reason: format!(
+                "{} problem(s); {} was left unchanged",
+                problems.len(),
+                path.display()
+            ),
+        });
+    }
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![write_step(&path, edited)],
+    };
+    apply(executor, &plan, &format!("Server '{}' updated.", args.name))?;
+    fs::remove_file(&draft).ok();
+    Ok(())
+}
+
+/// Open `path` in `$VISUAL`, `$EDITOR` or `vi` and wait for it to exit.
+fn open_editor(path: &Path) -> Result<(), HymodError> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Through the shell, so the editor may come with arguments (`code --wait`).
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(path)
+        .status()
+        .map_err(|e| HymodError::Other(format!("Failed to start editor '{}': {}", editor, e)))?;
+    if !status.success() {
+        return Err(HymodError::Other(format!(
+            "Editor '{}' exited with {}; nothing was saved",
+            editor, status
+        )));
+    }
+    Ok(())
+}
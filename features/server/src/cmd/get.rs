@@ -1,14 +1,10 @@
 use crate::args::get_args::ServerGetArgs;
-use core_config::server::load_server_config;
+use crate::current_dir;
+use core_config::server::find_server_config;
+use core_error::HymodError;
 
-pub fn run(args: ServerGetArgs) {
-    match load_server_config(&args.name) {
-        Ok(config) => {
-            println!("{}", config.server.server_root);
-        }
-        Err(_) => {
-            eprintln!("Server '{}' not found", args.name);
-            std::process::exit(1);
-        }
-    }
+pub fn run(args: ServerGetArgs) -> Result<(), HymodError> {
+    let config = find_server_config(&args.name, &current_dir()?)?;
+    println!("{}", config.server.server_root);
+    Ok(())
 }
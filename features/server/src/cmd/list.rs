@@ -1,17 +1,15 @@
 use crate::args::list_args::ServerListArgs;
+use crate::current_dir;
 use colored::*;
 use core_config::server::{
     get_default_server_for_kind, list_servers, load_server_config, ServerConfig, ServerKind,
 };
+use core_error::HymodError;
 
-pub fn run(args: ServerListArgs) {
-    let servers = match list_servers() {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("Failed to list servers: {}", e);
-            std::process::exit(1);
-        }
-    };
+pub fn run(args: ServerListArgs) -> Result<(), HymodError> {
+    let cwd = current_dir()?;
+    let servers = list_servers(&cwd)
+        .map_err(|e| HymodError::Other(format!("Failed to list servers: {}", e)))?;
 
     let default_local = get_default_server_for_kind(&ServerKind::Local).unwrap_or(None);
     let default_remote = get_default_server_for_kind(&ServerKind::Remote).unwrap_or(None);
@@ -20,7 +18,7 @@ pub fn run(args: ServerListArgs) {
     let mut remote_servers = Vec::new();
 
     for name in servers {
-        let config = match load_server_config(&name) {
+        let config = match load_server_config(&name, &cwd) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -55,6 +53,7 @@ pub fn run(args: ServerListArgs) {
     } else {
         println!(); // Extra newline at the end
     }
+    Ok(())
 }
 
 fn print_local_table(servers: &[(String, ServerConfig)], default_name: &str) {
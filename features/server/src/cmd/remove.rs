@@ -1,11 +1,23 @@
 use crate::args::remove_args::ServerRemoveArgs;
-use core_config::server::remove_server_config;
+use crate::{apply, current_dir, remove_step};
+use core_config::server::{find_server_config, removable_server_config_path};
+use core_error::HymodError;
+use core_ops::Executor;
+use core_plan::Plan;
 
-pub fn run(args: ServerRemoveArgs) {
-    if let Err(e) = remove_server_config(&args.name) {
-        eprintln!("Failed to remove server: {}", e);
-        std::process::exit(1);
-    }
+pub fn run(args: ServerRemoveArgs, executor: &Executor) -> Result<(), HymodError> {
+    let cwd = current_dir()?;
+    find_server_config(&args.name, &cwd)?;
+    let path = removable_server_config_path(&args.name, &cwd)
+        .map_err(|e| HymodError::Other(format!("Failed to remove server: {}", e)))?;
 
-    println!("Server '{}' removed successfully.", args.name);
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps: vec![remove_step(&path)],
+    };
+    apply(
+        executor,
+        &plan,
+        &format!("Server '{}' removed successfully.", args.name),
+    )
 }
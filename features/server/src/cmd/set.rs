@@ -0,0 +1,65 @@
+use crate::args::set_args::ServerSetArgs;
+use crate::{apply, current_dir, remove_step, write_step};
+use core_config::server::{
+    default_server_path, find_server_config, find_server_config_path, get_default_server_for_kind,
+    server_config_path, server_config_to_string, ServerKind,
+};
+use core_config::validate::parse_server_config;
+use core_error::HymodError;
+use core_ops::Executor;
+use core_plan::Plan;
+
+pub fn run(args: ServerSetArgs, executor: &Executor) -> Result<(), HymodError> {
+    let cwd = current_dir()?;
+    let mut config = find_server_config(&args.name, &cwd)?;
+    let path = server_config_path(&args.name, &cwd)?;
+    config
+        .set(&args.key, &args.value)
+        .map_err(HymodError::InvalidArgument)?;
+
+    let name = config.server.name.clone();
+    let renamed = name != args.name;
+    if renamed
+        && find_server_config_path(&name, &cwd)
+            .map_err(HymodError::Other)?
+            .is_some()
+    {
+        return Err(HymodError::ServerExists { name });
+    }
+    let target = path.with_file_name(format!("{}.yaml", name));
+
+    // Check the result the way it will be read back, so `set` cannot leave
+    // behind a server that no longer loads.
+    let content = server_config_to_string(&config).map_err(HymodError::Other)?;
+    parse_server_config(&content, &target).map_err(|problems| {
+        HymodError::InvalidArgument(format!(
+            "Not saved: setting {} to '{}' would leave server '{}' invalid: {}",
+            args.key,
+            args.value,
+            args.name,
+            problems.join("; ")
+        ))
+    })?;
+    let mut steps = vec![write_step(&target, content)];
+
+    if renamed {
+        steps.push(remove_step(&path));
+        for kind in [ServerKind::Local, ServerKind::Remote] {
+            let default = get_default_server_for_kind(&kind).map_err(HymodError::Other)?;
+            if default.as_deref() == Some(args.name.as_str()) {
+                let default_path = default_server_path(&kind).map_err(HymodError::Other)?;
+                steps.push(write_step(&default_path, name.clone()));
+            }
+        }
+    }
+
+    let plan = Plan {
+        preflight: Vec::new(),
+        steps,
+    };
+    apply(
+        executor,
+        &plan,
+        &format!("Set {} = {} on server '{}'.", args.key, args.value, name),
+    )
+}
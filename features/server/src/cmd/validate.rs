@@ -0,0 +1,45 @@
+use crate::args::validate_args::ServerValidateArgs;
+use crate::current_dir;
+use colored::*;
+use core_config::server::{get_server_config_dir, server_config_path};
+use core_config::validate::{validate_all_servers, validate_server_file};
+use core_error::HymodError;
+
+pub fn run(args: ServerValidateArgs) -> Result<(), HymodError> {
+    let cwd = current_dir()?;
+    let reports = match &args.name {
+        Some(name) => {
+            vec![validate_server_file(&server_config_path(name, &cwd)?)]
+        }
+        None => validate_all_servers(&cwd).map_err(HymodError::Other)?,
+    };
+
+    if reports.is_empty() {
+        println!("No servers configured.");
+        return Ok(());
+    }
+    for report in &reports {
+        if report.is_valid() {
+            println!("{}    {}", "OK".green(), report.path.display());
+        } else {
+            println!("{}  {}", "FAIL".red(), report.path.display());
+            for problem in &report.problems {
+                println!("  - {}", problem);
+            }
+        }
+    }
+
+    let invalid: Vec<_> = reports.iter().filter(|report| !report.is_valid()).collect();
+    let problems: usize = invalid.iter().map(|report| report.problems.len()).sum();
+    match invalid.as_slice() {
+        [] => Ok(()),
+        [report] => Err(HymodError::InvalidConfig {
+            path: report.path.clone(),
+            reason: format!("{} problem(s)", problems),
+        }),
+        _ => Err(HymodError::InvalidConfig {
+            path: get_server_config_dir().map_err(HymodError::Other)?,
+            reason: format!("{} problem(s) in {} files", problems, invalid.len()),
+        }),
+    }
+}
@@ -1,22 +1,34 @@
 pub mod args {
     pub mod add_args;
     pub mod default_args;
+    pub mod edit_args;
     pub mod get_args;
     pub mod list_args;
     pub mod remove_args;
+    pub mod set_args;
+    pub mod validate_args;
 }
 
 pub mod cmd {
     pub mod add;
     pub mod default;
+    pub mod edit;
     pub mod get;
     pub mod list;
     pub mod remove;
+    pub mod set;
+    pub mod validate;
 }
 
+use core_error::HymodError;
+use core_ops::{Executor, Verbosity};
+use core_plan::{Plan, Step};
+use std::path::Path;
+
 use args::{
-    add_args::ServerAddArgs, default_args::ServerDefaultArgs, get_args::ServerGetArgs,
-    list_args::ServerListArgs, remove_args::ServerRemoveArgs,
+    add_args::ServerAddArgs, default_args::ServerDefaultArgs, edit_args::ServerEditArgs,
+    get_args::ServerGetArgs, list_args::ServerListArgs, remove_args::ServerRemoveArgs,
+    set_args::ServerSetArgs, validate_args::ServerValidateArgs,
 };
 
 pub enum ServerCommand {
@@ -25,14 +37,68 @@ pub enum ServerCommand {
     Default(ServerDefaultArgs),
     Remove(ServerRemoveArgs),
     Get(ServerGetArgs),
+    Validate(ServerValidateArgs),
+    Set(ServerSetArgs),
+    Edit(ServerEditArgs),
 }
 
-pub fn execute(cmd: ServerCommand) {
+/// Run `cmd`. Commands that change server files do so through `executor`,
+/// so `--dry-run` and `--diff` only show the change.
+pub fn execute(cmd: ServerCommand, executor: &Executor) -> Result<(), HymodError> {
     match cmd {
-        ServerCommand::Add(args) => cmd::add::run(args),
+        ServerCommand::Add(args) => cmd::add::run(args, executor),
         ServerCommand::List(args) => cmd::list::run(args),
-        ServerCommand::Default(args) => cmd::default::run(args),
-        ServerCommand::Remove(args) => cmd::remove::run(args),
+        ServerCommand::Default(args) => cmd::default::run(args, executor),
+        ServerCommand::Remove(args) => cmd::remove::run(args, executor),
         ServerCommand::Get(args) => cmd::get::run(args),
+        ServerCommand::Validate(args) => cmd::validate::run(args),
+        ServerCommand::Set(args) => cmd::set::run(args, executor),
+        ServerCommand::Edit(args) => cmd::edit::run(args, executor),
+    }
+}
+
+/// Execute `plan`, then print `done` unless it was only a dry run or output
+/// is quiet.
+pub(crate) fn apply(executor: &Executor, plan: &Plan, done: &str) -> Result<(), HymodError> {
+    executor
+        .execute(plan)
+        .map_err(|e| match e.downcast::<HymodError>() {
+            Ok(err) => err,
+            Err(e) => HymodError::Other(format!("{:#}", e)),
+        })?;
+    if !executor.is_dry_run() && executor.verbosity() != Verbosity::Quiet {
+        println!("{}", done);
+    }
+    Ok(())
+}
+
+pub(crate) fn write_step(path: &Path, content: String) -> Step {
+    Step::WriteFile {
+        path: path.to_string_lossy().to_string(),
+        content,
+    }
+}
+
+pub(crate) fn remove_step(path: &Path) -> Step {
+    Step::RemoveFile {
+        path: path.to_string_lossy().to_string(),
+    }
+}
+
+/// The directory server registries are looked up from.
+pub(crate) fn current_dir() -> Result<std::path::PathBuf, HymodError> {
+    std::env::current_dir()
+        .map_err(|e| HymodError::Other(format!("Failed to get current directory: {}", e)))
+}
+
+/// Parse a server kind argument; `ssh` is accepted as an alias for `remote`.
+pub(crate) fn parse_kind(kind: &str) -> Result<core_config::ServerKind, HymodError> {
+    match kind {
+        "local" => Ok(core_config::ServerKind::Local),
+        "remote" | "ssh" => Ok(core_config::ServerKind::Remote),
+        _ => Err(HymodError::InvalidArgument(format!(
+            "Invalid server kind: {}. Must be 'local' or 'remote'.",
+            kind
+        ))),
     }
 }
@@ -1,6 +1,29 @@
 // Error Message Quality Tests (PRD §13)
 
+use core_error::HymodError;
+use core_ops::Executor;
+use features_server::args::get_args::ServerGetArgs;
+use features_server::{execute, ServerCommand};
+
 #[test]
 fn test_error_server_not_found() {
-    panic!("Error must include: server name, 'not found in ~/.hymod/servers.d/', suggestion 'run: hymod server list'.");
+    let home = tempfile::tempdir().expect("failed to create home dir");
+    std::env::set_var("HOME", home.path());
+
+    let err = execute(
+        ServerCommand::Get(ServerGetArgs {
+            name: "nonexistent".to_string(),
+        }),
+        &Executor::new(false),
+    )
+    .expect_err("unknown server should be an error");
+
+    assert!(matches!(err, HymodError::ServerNotFound { .. }));
+    let message = err.to_string();
+    assert!(message.contains("nonexistent"));
+    assert!(message.contains(&format!(
+        "not found in {}",
+        home.path().join(".hymod").join("servers.d").display()
+    )));
+    assert_eq!(err.hint().as_deref(), Some("run: hymod server list"));
 }
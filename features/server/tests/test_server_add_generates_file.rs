@@ -1,4 +1,57 @@
+use anyhow::Result;
+use core_ops::{ExecContext, Executor, OpHandler};
+use core_plan::Step;
+use features_server::args::add_args::ServerAddArgs;
+use std::sync::{Arc, Mutex};
+
+struct RecordWrites(Arc<Mutex<Vec<(String, String)>>>);
+
+impl OpHandler for RecordWrites {
+    fn handle(&self, step: &Step, _ctx: &ExecContext) -> Result<()> {
+        if let Step::WriteFile { path, content } = step {
+            self.0.lock().unwrap().push((path.clone(), content.clone()));
+        }
+        Ok(())
+    }
+}
+
 #[test]
 fn test_server_add_generates_file() {
-    panic!("Must WriteFile(~/.hymod/servers.d/prod.yaml) with default template.");
+    let home = tempfile::tempdir().unwrap();
+    std::env::set_var("HYMOD_HOME", home.path());
+
+    let writes = Arc::new(Mutex::new(Vec::new()));
+    let mut executor = Executor::new(false);
+    executor.register(
+        Step::WriteFile {
+            path: String::new(),
+            content: String::new(),
+        },
+        Box::new(RecordWrites(writes.clone())),
+    );
+
+    let args = ServerAddArgs {
+        name: "prod".to_string(),
+        kind: "remote".to_string(),
+        uri: "deploy@example.com:/srv/hytale".to_string(),
+        ..Default::default()
+    };
+    features_server::cmd::add::run(args, &executor).unwrap();
+
+    let writes = writes.lock().unwrap();
+    assert_eq!(writes.len(), 1);
+    let (path, content) = &writes[0];
+    assert_eq!(
+        path,
+        home.path()
+            .join("servers.d")
+            .join("prod.yaml")
+            .to_str()
+            .unwrap()
+    );
+    assert!(content.contains("name: prod"));
+    assert!(content.contains("kind: remote"));
+    assert!(content.contains("server_root: /srv/hytale"));
+    assert!(content.contains("cmd: systemctl restart hytale"));
+    assert!(!home.path().join("servers.d").exists());
 }